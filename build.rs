@@ -0,0 +1,27 @@
+//! Generates the gRPC server for `proto/dashboard.proto`
+//!
+//! Uses tonic-build's manual service definition so no `protoc` is needed at build
+//! time; the message types live in `grpc_push.rs` and mirror the proto by hand.
+
+fn main() {
+    let dashboard_stream = tonic_build::manual::Service::builder()
+        .name("DashboardStream")
+        .package("dashboard")
+        .method(
+            tonic_build::manual::Method::builder()
+                .name("subscribe")
+                .route_name("Subscribe")
+                .input_type("super::SubscribeRequest")
+                .output_type("super::DashboardUpdate")
+                .codec_path("tonic::codec::ProstCodec")
+                .server_streaming()
+                .build(),
+        )
+        .build();
+
+    tonic_build::manual::Builder::new()
+        .build_client(false)
+        .compile(&[dashboard_stream]);
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,153 @@
+//! Signed Admin Requests
+//!
+//! With `ADMIN_HMAC_SECRET` set, every admin request must also carry
+//! `X-Admin-Timestamp` (Unix seconds) and `X-Admin-Signature`, the hex
+//! HMAC-SHA256 of `<timestamp>.<METHOD> <path>.<body>` under the secret, so a
+//! signature is only good for the endpoint it was made for. Requests whose
+//! timestamp is more than `MAX_CLOCK_SKEW` away from the server clock, whose
+//! signature doesn't verify, or whose signature was already used are rejected,
+//! so a captured request (or bearer token) can't be replayed. Used signatures
+//! are also claimed in Redis (see `LeaderElectionService::try_claim_admin_signature`),
+//! so a request accepted by one instance is refused by the others.
+//!
+//! `sign` computes the header value; `cargo run --bin admin-sign -- POST /admin/broadcast '<body>'`
+//! prints both headers for scripting.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+use parking_lot::Mutex;
+
+/// Header carrying the request's Unix timestamp (seconds)
+pub const TIMESTAMP_HEADER: &str = "x-admin-timestamp";
+
+/// Header carrying the hex HMAC-SHA256 of `<timestamp>.<METHOD> <path>.<body>`
+pub const SIGNATURE_HEADER: &str = "x-admin-signature";
+
+/// Largest accepted distance between a request's timestamp and the server clock
+pub const MAX_CLOCK_SKEW_SECONDS: i64 = 30;
+
+/// HMAC-SHA256 of `message` under `key`
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+    let key = PKey::hmac(key)?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    signer.update(message)?;
+    Ok(signer.sign_to_vec()?)
+}
+
+/// `X-Admin-Signature` value for a `method` request to `path`, sent at `timestamp` with `body`
+pub fn sign(secret: &str, timestamp: i64, method: &str, path: &str, body: &str) -> Result<String> {
+    let message = format!("{}.{} {}.{}", timestamp, method.to_ascii_uppercase(), path, body);
+    Ok(hmac_sha256(secret.as_bytes(), message.as_bytes())?
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Whether a bearer token equals `ADMIN_TOKEN`, in time independent of either
+///
+/// Both sides are hashed to SHA-256 first, so the comparison always covers 32 bytes
+/// and reveals neither the first differing byte nor the configured token's length.
+pub fn tokens_match(provided: &str, expected: &str) -> bool {
+    openssl::memcmp::eq(&openssl::sha::sha256(provided.as_bytes()), &openssl::sha::sha256(expected.as_bytes()))
+}
+
+/// Checks admin request signatures and remembers recent ones to refuse replays
+pub struct AdminRequestVerifier {
+    secret: String,
+    /// Signatures accepted within the clock-skew window, with their timestamps
+    seen: Mutex<HashMap<String, i64>>,
+}
+
+impl AdminRequestVerifier {
+    /// Verifier for `ADMIN_HMAC_SECRET`
+    pub fn new(secret: String) -> Self {
+        Self {
+            secret,
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Accept a request once: timestamp within the window, valid signature, not seen before
+    ///
+    /// `now` is the server's Unix time in seconds. Returns the accepted signature, for
+    /// claiming it across instances.
+    pub fn verify(
+        &self,
+        timestamp: Option<&str>,
+        signature: Option<&str>,
+        method: &str,
+        path: &str,
+        body: &str,
+        now: i64,
+    ) -> Result<String> {
+        let (Some(timestamp), Some(signature)) = (timestamp, signature) else {
+            bail!("Missing {} or {} header", TIMESTAMP_HEADER, SIGNATURE_HEADER);
+        };
+        let Ok(timestamp) = timestamp.trim().parse::<i64>() else {
+            bail!("Invalid {} header", TIMESTAMP_HEADER);
+        };
+        if (now - timestamp).abs() > MAX_CLOCK_SKEW_SECONDS {
+            bail!("Request timestamp outside the ±{}s window", MAX_CLOCK_SKEW_SECONDS);
+        }
+
+        let expected = sign(&self.secret, timestamp, method, path, body)?;
+        let signature = signature.trim().to_ascii_lowercase();
+        let matches = signature.len() == expected.len()
+            && openssl::memcmp::eq(signature.as_bytes(), expected.as_bytes());
+        if !matches {
+            bail!("Invalid request signature");
+        }
+
+        let mut seen = self.seen.lock();
+        seen.retain(|_, seen_at| (now - *seen_at).abs() <= MAX_CLOCK_SKEW_SECONDS);
+        if seen.insert(signature.clone(), timestamp).is_some() {
+            bail!("Request already used");
+        }
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_match_only_the_exact_token() {
+        assert!(tokens_match("s3cret-token", "s3cret-token"));
+        assert!(!tokens_match("s3cret-tokeN", "s3cret-token"));
+        assert!(!tokens_match("s3cret", "s3cret-token"));
+        assert!(!tokens_match("s3cret-token-and-more", "s3cret-token"));
+        assert!(!tokens_match("", "s3cret-token"));
+    }
+
+    #[test]
+    fn test_signed_requests_are_accepted_once() {
+        // RFC 4231 test case 2
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?").unwrap();
+        let hex: String = mac.iter().map(|byte| format!("{:02x}", byte)).collect();
+        assert_eq!(hex, "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+
+        let verifier = AdminRequestVerifier::new("secret".to_string());
+        let body = r#"{"level":"info","message":"hi"}"#;
+        let now = 1_700_000_000;
+        let sign = |timestamp: i64, method: &str, path: &str, body: &str| sign("secret", timestamp, method, path, body).unwrap();
+        let signature = sign(now - 5, "POST", "/admin/broadcast", body);
+        let verify = |timestamp: i64, signature: &str, path: &str, body: &str| {
+            verifier.verify(Some(&timestamp.to_string()), Some(signature), "POST", path, body, now)
+        };
+
+        // Bound to the endpoint: the same signature fails on another path
+        assert!(verify(now - 5, &signature, "/admin/broadcast/pause", body).is_err());
+        assert_eq!(verify(now - 5, &signature, "/admin/broadcast", body).unwrap(), signature);
+        assert!(verify(now - 5, &signature, "/admin/broadcast", body).unwrap_err().to_string().contains("already used"));
+        assert!(verify(now - 5, &sign(now - 5, "POST", "/admin/broadcast", "{}"), "/admin/broadcast", body).is_err());
+        assert!(verify(now - 31, &sign(now - 31, "POST", "/admin/broadcast", body), "/admin/broadcast", body)
+            .unwrap_err().to_string().contains("window"));
+        assert!(verify(now, &sign(now, "GET", "/admin/broadcast", body), "/admin/broadcast", body).is_err());
+        assert!(verifier.verify(None, Some(&signature), "POST", "/admin/broadcast", body, now).is_err());
+    }
+}
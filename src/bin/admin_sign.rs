@@ -0,0 +1,23 @@
+//! Print the signature headers for an admin request
+//!
+//! Usage: `ADMIN_HMAC_SECRET=… cargo run --bin admin-sign -- POST /admin/broadcast '{"level":"info","message":"…"}'`
+//! (omit the body for body-less requests). Pass the printed lines to `curl -H` within
+//! 30 seconds; each signature is accepted once, for that method and path only.
+
+use anyhow::Context;
+use web_server_report_websocket::admin_auth::{sign, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+
+fn main() -> anyhow::Result<()> {
+    dotenvy::dotenv().ok();
+    let secret = std::env::var("ADMIN_HMAC_SECRET").context("ADMIN_HMAC_SECRET is not set")?;
+    let mut args = std::env::args().skip(1);
+    let usage = "usage: admin-sign <METHOD> <PATH> [BODY]";
+    let method = args.next().context(usage)?;
+    let path = args.next().context(usage)?;
+    let body = args.next().unwrap_or_default();
+    let timestamp = chrono::Utc::now().timestamp();
+
+    println!("{}: {}", TIMESTAMP_HEADER, timestamp);
+    println!("{}: {}", SIGNATURE_HEADER, sign(&secret, timestamp, &method, &path, &body)?);
+    Ok(())
+}
@@ -0,0 +1,22 @@
+//! Write the WebSocket protocol's JSON Schema
+//!
+//! Usage: `cargo run --bin schema [DIR]` (default `schema/`). Writes
+//! `client_message.json` and `server_message.json`, derived with `schema_for!` from
+//! `ClientMessage` and `ServerMessage`; commit them after a protocol change, the test
+//! suite fails while they are stale.
+
+use anyhow::Context;
+use std::path::PathBuf;
+use web_server_report_websocket::dto::schema::{render, SCHEMA_FILES};
+
+fn main() -> anyhow::Result<()> {
+    let dir = PathBuf::from(std::env::args().nth(1).unwrap_or_else(|| "schema".to_string()));
+    std::fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+
+    for (name, build) in SCHEMA_FILES {
+        let path = dir.join(name);
+        std::fs::write(&path, render(&build())).with_context(|| format!("writing {}", path.display()))?;
+        println!("{}", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,583 @@
+//! Configuration Module
+//!
+//! Typed service configuration, parsed and validated once from environment
+//! variables at startup so misconfiguration fails fast with a readable message.
+
+use anyhow::{bail, Context, Result};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::performance::{HttpClientOptions, DEFAULT_HTTP_USER_AGENT};
+use crate::service_islands::layer1_infrastructure::cache_system_island::CacheL2;
+use crate::service_islands::layer1_infrastructure::cache_system_island::cache_manager::DEFAULT_REALTIME_TTL_SECONDS;
+use crate::service_islands::layer2_external_services::external_apis_island::api_aggregator::DEFAULT_MAX_CONCURRENT_FETCHES;
+use crate::service_islands::layer2_external_services::external_apis_island::market_data_api::{
+    GlobalField, DASHBOARD_SYMBOLS, DEFAULT_REQUIRED_GLOBAL_FIELDS, DEFAULT_STOCK_INDICES,
+};
+use crate::service_islands::layer2_external_services::external_apis_island::price_vwap::{PriceMode, DEFAULT_VWAP_MAX_DEVIATION_PERCENT};
+use crate::service_islands::layer3_communication::websocket_service::transforms::OutputCase;
+
+/// Default `MAINTENANCE_MESSAGE`
+pub const DEFAULT_MAINTENANCE_MESSAGE: &str = "Market data is paused for scheduled maintenance; showing the last known values";
+
+/// Refresh interval per slow-moving data category (FNG/RSI/INDICES_FETCH_INTERVAL_SECONDS)
+#[derive(Debug, Clone, Copy)]
+pub struct CategoryIntervals {
+    pub fng: Duration,
+    pub rsi: Duration,
+    pub indices: Duration,
+}
+
+impl Default for CategoryIntervals {
+    /// Same as the categories' cache TTLs
+    fn default() -> Self {
+        Self {
+            fng: Duration::from_secs(300),
+            rsi: Duration::from_secs(3600),
+            indices: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Service configuration loaded from the environment
+#[derive(Debug, Clone)]
+pub struct Config {
+    // Server
+    pub host: String,
+    pub port: u16,
+    // Overall time allowed for graceful shutdown before the process force-exits
+    pub shutdown_timeout_seconds: u64,
+
+    // Market data fetching
+    pub fetch_interval_seconds: u64,
+    // TTL of realtime cache entries: crypto prices and latest_market_data (REALTIME_CACHE_TTL_SECONDS)
+    pub realtime_cache_ttl_seconds: u64,
+    // Slow-moving data refreshed on its own interval (reused from memory in between)
+    pub fng_fetch_interval_seconds: u64,
+    pub rsi_fetch_interval_seconds: u64,
+    pub indices_fetch_interval_seconds: u64,
+    pub binance_ws_enabled: bool,
+
+    // Redis
+    pub redis_url: String,
+    pub redis_replica_url: Option<String>,
+    // L2 cache tier: Redis, or process memory for running without Redis (CACHE_L2)
+    pub cache_l2: CacheL2,
+    // Namespace for every Redis key, stream and the leader lock (CACHE_KEY_PREFIX / REDIS_NAMESPACE)
+    pub cache_key_prefix: String,
+
+    // Leader election: unique per instance
+    pub node_id: String,
+    // Shadow mode: run election and fetching but only log broadcasts/stream publishes
+    pub shadow_mode: bool,
+    // Maintenance mode at startup: no fetching, clients get a notice plus the last good dashboard
+    pub maintenance_mode: bool,
+    pub maintenance_message: String,
+    // Confirm the lock in Redis before each leader fetch instead of trusting the cached flag
+    pub verify_leadership_before_fetch: bool,
+    // Followers consume market_data_stream (replaying gaps) instead of polling latest_market_data
+    pub follower_stream_consumer: bool,
+    // Cold-cache ticks after which one follower fetches from the APIs itself (0 = never)
+    pub follower_cold_fetch_ticks: u32,
+    // Minimum gap between dashboard broadcasts; faster updates are coalesced (0 = no limit)
+    pub min_broadcast_interval_ms: u64,
+    // Still broadcast dashboards missing crypto prices or global data (flagged `critical_failure`)
+    pub broadcast_on_critical_failure: bool,
+
+    // External API keys
+    pub taapi_secret: String,
+    pub cmc_api_key: Option<String>,
+    pub finnhub_api_key: Option<String>,
+    // (symbol, name) of the US indices / ETF proxies fetched from Finnhub (STOCK_INDICES)
+    pub stock_indices: Vec<(String, String)>,
+    // Fewest dashboard coins a Binance price response may contain before it counts as failed
+    pub binance_min_symbols: usize,
+    // Single-source Binance prices or a VWAP across exchanges (PRICE_MODE / VWAP_MAX_DEVIATION_PERCENT)
+    pub price_mode: PriceMode,
+    // Try the global data source with the better success rate first
+    pub adaptive_source_ordering: bool,
+    // Data groups fetched at once per aggregation (1 = one after another)
+    pub max_concurrent_fetches: usize,
+    // Retries all providers may spend together per aggregation (0 = unlimited)
+    pub max_retries_per_tick: usize,
+    // Global data fields a provider must return, else it counts as failed (REQUIRED_GLOBAL_FIELDS)
+    pub required_global_fields: Vec<GlobalField>,
+    // Identity sent to every provider (HTTP_USER_AGENT, HTTP_DEFAULT_HEADERS)
+    pub http_user_agent: HeaderValue,
+    pub http_default_headers: HeaderMap,
+
+    // WebSocket
+    pub max_ws_connections: usize,
+    pub delta_full_snapshot_every: u32,
+    pub ws_slow_client_lag_limit: u64,
+    // Client messages accepted per connection per second (0 = unlimited)
+    pub ws_max_client_messages_per_second: u32,
+    // Largest inbound client message/frame accepted
+    pub ws_max_message_bytes: usize,
+    // Longest a single outbound frame may block before the connection is dropped
+    pub ws_send_timeout_ms: u64,
+    // Connections sending the same broadcast at once (0 = unlimited)
+    pub ws_broadcast_concurrency: usize,
+    pub allowed_origins: AllowedOrigins,
+    // How long a dropped connection's subscriptions can be resumed (0 disables resume)
+    pub resume_grace_seconds: u64,
+    // Serve on this Unix domain socket instead of HOST:PORT
+    pub bind_uds: Option<PathBuf>,
+    // Stream dashboards to the main report service over gRPC on HOST:GRPC_PORT (disabled when unset)
+    pub grpc_port: Option<u16>,
+    // Recent dashboards kept in memory for GetHistory
+    pub history_buffer_size: usize,
+    // Built-in broadcast transforms (see websocket_service::transforms)
+    pub broadcast_round_decimals: Option<u32>,
+    pub broadcast_field_allowlist: Option<Vec<String>>,
+    // Key casing of broadcast dashboard data (OUTPUT_CASE)
+    pub output_case: OutputCase,
+
+    // Admin API: bearer token for /admin endpoints (disabled when unset)
+    pub admin_token: Option<String>,
+    // Shared secret for signed admin requests (timestamp + HMAC, see admin_auth)
+    pub admin_hmac_secret: Option<String>,
+}
+
+impl Config {
+    /// Load configuration from process environment variables
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Load configuration from an arbitrary variable lookup (used by tests)
+    pub fn from_lookup<F>(lookup: F) -> Result<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let env = EnvReader { lookup };
+
+        let shutdown_timeout_seconds = env.parse("SHUTDOWN_TIMEOUT_SECONDS", 30u64)?;
+        if shutdown_timeout_seconds < 1 {
+            bail!("SHUTDOWN_TIMEOUT_SECONDS must be >= 1 (got {})", shutdown_timeout_seconds);
+        }
+        let fetch_interval_seconds = env.parse("FETCH_INTERVAL_SECONDS", 5u64)?;
+        if fetch_interval_seconds < 1 {
+            bail!("FETCH_INTERVAL_SECONDS must be >= 1 (got {})", fetch_interval_seconds);
+        }
+
+        let realtime_cache_ttl_seconds = env.parse("REALTIME_CACHE_TTL_SECONDS", DEFAULT_REALTIME_TTL_SECONDS)?;
+        if realtime_cache_ttl_seconds < 1 {
+            bail!("REALTIME_CACHE_TTL_SECONDS must be >= 1 (got {})", realtime_cache_ttl_seconds);
+        }
+
+        let fng_fetch_interval_seconds = env.parse("FNG_FETCH_INTERVAL_SECONDS", 300u64)?;
+        let rsi_fetch_interval_seconds = env.parse("RSI_FETCH_INTERVAL_SECONDS", 3600u64)?;
+        let indices_fetch_interval_seconds = env.parse("INDICES_FETCH_INTERVAL_SECONDS", 300u64)?;
+
+        let binance_min_symbols = env.parse("BINANCE_MIN_SYMBOLS", DASHBOARD_SYMBOLS.len())?;
+        if !(1..=DASHBOARD_SYMBOLS.len()).contains(&binance_min_symbols) {
+            bail!("BINANCE_MIN_SYMBOLS must be between 1 and {} (got {})", DASHBOARD_SYMBOLS.len(), binance_min_symbols);
+        }
+
+        let max_concurrent_fetches = env.parse("MAX_CONCURRENT_FETCHES", DEFAULT_MAX_CONCURRENT_FETCHES)?;
+        if max_concurrent_fetches < 1 {
+            bail!("MAX_CONCURRENT_FETCHES must be >= 1 (got {})", max_concurrent_fetches);
+        }
+
+        let price_mode = match env.optional("PRICE_MODE").as_deref() {
+            None | Some("single") => PriceMode::Single,
+            Some("vwap") => {
+                let max_deviation_percent = env.parse("VWAP_MAX_DEVIATION_PERCENT", DEFAULT_VWAP_MAX_DEVIATION_PERCENT)?;
+                if max_deviation_percent.is_nan() || max_deviation_percent <= 0.0 {
+                    bail!("VWAP_MAX_DEVIATION_PERCENT must be > 0 (got {})", max_deviation_percent);
+                }
+                PriceMode::Vwap { max_deviation_percent }
+            }
+            Some(other) => bail!("PRICE_MODE must be 'single' or 'vwap' (got '{}')", other),
+        };
+
+        let output_case = match env.optional("OUTPUT_CASE").as_deref() {
+            None | Some("camel") => OutputCase::Camel,
+            Some("snake") => OutputCase::Snake,
+            Some("both") => OutputCase::Both,
+            Some(other) => bail!("OUTPUT_CASE must be 'camel', 'snake' or 'both' (got '{}')", other),
+        };
+
+        let stock_indices = match env.optional("STOCK_INDICES") {
+            Some(raw) => parse_stock_indices(&raw)?,
+            None => DEFAULT_STOCK_INDICES.iter().map(|(symbol, name)| (symbol.to_string(), name.to_string())).collect(),
+        };
+
+        let required_global_fields = match env.optional("REQUIRED_GLOBAL_FIELDS") {
+            Some(raw) => raw
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| GlobalField::from_name(name).with_context(|| format!(
+                    "REQUIRED_GLOBAL_FIELDS: unknown field '{}' (expected market_cap, volume_24h, btc_dominance or eth_dominance)",
+                    name
+                )))
+                .collect::<Result<Vec<_>>>()?,
+            None => DEFAULT_REQUIRED_GLOBAL_FIELDS.to_vec(),
+        };
+
+        let http_user_agent = match env.optional("HTTP_USER_AGENT") {
+            Some(agent) => HeaderValue::from_str(&agent)
+                .with_context(|| format!("HTTP_USER_AGENT is not a valid header value (got '{}')", agent))?,
+            None => HeaderValue::from_static(DEFAULT_HTTP_USER_AGENT),
+        };
+        let http_default_headers = match env.optional("HTTP_DEFAULT_HEADERS") {
+            Some(raw) => parse_headers(&raw).context("HTTP_DEFAULT_HEADERS must be 'Name: value' pairs separated by ';'")?,
+            None => HeaderMap::new(),
+        };
+
+        let cache_l2 = match env.optional("CACHE_L2").as_deref() {
+            None | Some("redis") => CacheL2::Redis,
+            Some("none") => CacheL2::None,
+            Some(other) => bail!("CACHE_L2 must be 'redis' or 'none' (got '{}')", other),
+        };
+
+        let cache_key_prefix = env.optional("CACHE_KEY_PREFIX")
+            .or_else(|| env.optional("REDIS_NAMESPACE"))
+            .map(|prefix| prefix.trim_end_matches(':').to_string())
+            .unwrap_or_default();
+        if cache_key_prefix.chars().any(char::is_whitespace) {
+            bail!("CACHE_KEY_PREFIX must not contain whitespace (got '{}')", cache_key_prefix);
+        }
+
+        let max_ws_connections = env.parse("MAX_WS_CONNECTIONS", 10_000usize)?;
+        if max_ws_connections < 1 {
+            bail!("MAX_WS_CONNECTIONS must be >= 1 (got {})", max_ws_connections);
+        }
+
+        let delta_full_snapshot_every = env.parse("DELTA_FULL_SNAPSHOT_EVERY", 12u32)?;
+        if delta_full_snapshot_every < 1 {
+            bail!("DELTA_FULL_SNAPSHOT_EVERY must be >= 1 (got {})", delta_full_snapshot_every);
+        }
+
+        let admin_token = env.optional("ADMIN_TOKEN");
+        let admin_hmac_secret = env.optional("ADMIN_HMAC_SECRET");
+        if admin_hmac_secret.is_some() && admin_token.is_none() {
+            bail!("ADMIN_HMAC_SECRET requires ADMIN_TOKEN (signatures are checked in addition to the token)");
+        }
+
+        let ws_send_timeout_ms = env.parse("WS_SEND_TIMEOUT_MS", 5000u64)?;
+        if ws_send_timeout_ms < 1 {
+            bail!("WS_SEND_TIMEOUT_MS must be >= 1 (got {})", ws_send_timeout_ms);
+        }
+        let ws_max_message_bytes = env.parse("WS_MAX_MESSAGE_BYTES", 64 * 1024usize)?;
+        if ws_max_message_bytes < 1 {
+            bail!("WS_MAX_MESSAGE_BYTES must be >= 1 (got {})", ws_max_message_bytes);
+        }
+
+        let allowed_origins = match env.optional("ALLOWED_ORIGINS") {
+            Some(raw) => AllowedOrigins::parse(&raw)?,
+            None => AllowedOrigins::Any,
+        };
+
+        // Railway provides a stable replica/instance id; otherwise generate one
+        let node_id = env.optional("RAILWAY_REPLICA_ID")
+            .or_else(|| env.optional("RAILWAY_INSTANCE_ID"))
+            .unwrap_or_else(|| format!("ws-{}", uuid::Uuid::new_v4()));
+
+        Ok(Self {
+            host: env.optional("HOST").unwrap_or_else(|| "0.0.0.0".to_string()),
+            port: env.parse("PORT", 8081u16)?,
+            shutdown_timeout_seconds,
+            fetch_interval_seconds,
+            realtime_cache_ttl_seconds,
+            fng_fetch_interval_seconds,
+            rsi_fetch_interval_seconds,
+            indices_fetch_interval_seconds,
+            binance_ws_enabled: env.parse("BINANCE_WS_ENABLED", true)?,
+            redis_url: env.optional("REDIS_URL").unwrap_or_else(|| "redis://127.0.0.1:6379".to_string()),
+            redis_replica_url: env.optional("REDIS_REPLICA_URL"),
+            cache_l2,
+            cache_key_prefix,
+            node_id,
+            shadow_mode: env.parse("SHADOW_MODE", false)?,
+            maintenance_mode: env.parse("MAINTENANCE_MODE", false)?,
+            maintenance_message: env.optional("MAINTENANCE_MESSAGE")
+                .unwrap_or_else(|| DEFAULT_MAINTENANCE_MESSAGE.to_string()),
+            verify_leadership_before_fetch: env.parse("VERIFY_LEADERSHIP_BEFORE_FETCH", true)?,
+            follower_stream_consumer: env.parse("FOLLOWER_STREAM_CONSUMER", false)?,
+            follower_cold_fetch_ticks: env.parse("FOLLOWER_COLD_FETCH_TICKS", 0u32)?,
+            min_broadcast_interval_ms: env.parse("MIN_BROADCAST_INTERVAL_MS", 0u64)?,
+            broadcast_on_critical_failure: env.parse("BROADCAST_ON_CRITICAL_FAILURE", false)?,
+            taapi_secret: env.optional("TAAPI_SECRET").unwrap_or_else(|| "default_secret".to_string()),
+            cmc_api_key: env.optional("CMC_API_KEY"),
+            finnhub_api_key: env.optional("FINNHUB_API_KEY"),
+            stock_indices,
+            binance_min_symbols,
+            price_mode,
+            adaptive_source_ordering: env.parse("ADAPTIVE_SOURCE_ORDERING", false)?,
+            max_concurrent_fetches,
+            max_retries_per_tick: env.parse("MAX_RETRIES_PER_TICK", 0usize)?,
+            required_global_fields,
+            http_user_agent,
+            http_default_headers,
+            max_ws_connections,
+            delta_full_snapshot_every,
+            ws_slow_client_lag_limit: env.parse("WS_SLOW_CLIENT_LAG_LIMIT", 100u64)?,
+            ws_max_client_messages_per_second: env.parse("WS_MAX_CLIENT_MESSAGES_PER_SECOND", 20u32)?,
+            ws_max_message_bytes,
+            ws_send_timeout_ms,
+            ws_broadcast_concurrency: env.parse("WS_BROADCAST_CONCURRENCY", 256usize)?,
+            allowed_origins,
+            resume_grace_seconds: env.parse("RESUME_GRACE_SECONDS", 60u64)?,
+            bind_uds: env.optional("BIND_UDS").map(PathBuf::from),
+            grpc_port: env.optional("GRPC_PORT")
+                .map(|_| env.parse("GRPC_PORT", 0u16))
+                .transpose()?,
+            history_buffer_size: env.parse("HISTORY_BUFFER_SIZE", 60usize)?,
+            broadcast_round_decimals: env.optional("BROADCAST_ROUND_DECIMALS")
+                .map(|_| env.parse("BROADCAST_ROUND_DECIMALS", 0u32))
+                .transpose()?,
+            broadcast_field_allowlist: env.optional("BROADCAST_FIELD_ALLOWLIST").map(|raw| {
+                raw.split(',').map(str::trim).filter(|f| !f.is_empty()).map(str::to_string).collect()
+            }),
+            output_case,
+            admin_token,
+            admin_hmac_secret,
+        })
+    }
+
+    /// Refresh intervals for the slow-moving data categories
+    pub fn category_intervals(&self) -> CategoryIntervals {
+        CategoryIntervals {
+            fng: Duration::from_secs(self.fng_fetch_interval_seconds),
+            rsi: Duration::from_secs(self.rsi_fetch_interval_seconds),
+            indices: Duration::from_secs(self.indices_fetch_interval_seconds),
+        }
+    }
+
+    /// Headers for the shared outbound HTTP client
+    pub fn http_client_options(&self) -> HttpClientOptions {
+        HttpClientOptions {
+            user_agent: self.http_user_agent.clone(),
+            default_headers: self.http_default_headers.clone(),
+        }
+    }
+
+    /// Address the HTTP/WebSocket server binds to
+    pub fn socket_addr(&self) -> Result<SocketAddr> {
+        format!("{}:{}", self.host, self.port)
+            .parse()
+            .with_context(|| format!("HOST and PORT must form a valid address (got {}:{})", self.host, self.port))
+    }
+
+    /// Address of the gRPC dashboard stream (None when `GRPC_PORT` is unset)
+    pub fn grpc_addr(&self) -> Result<Option<SocketAddr>> {
+        self.grpc_port
+            .map(|port| {
+                format!("{}:{}", self.host, port)
+                    .parse()
+                    .with_context(|| format!("HOST and GRPC_PORT must form a valid address (got {}:{})", self.host, port))
+            })
+            .transpose()
+    }
+}
+
+/// Browser origins allowed to open WebSocket connections (`ALLOWED_ORIGINS`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AllowedOrigins {
+    /// `*`: any origin (development default)
+    Any,
+    /// Exact origins such as `https://example.com`, normalized to lowercase without a trailing slash
+    List(Vec<String>),
+}
+
+impl AllowedOrigins {
+    /// Parse a comma-separated origin list; `*` anywhere allows all origins
+    pub fn parse(raw: &str) -> Result<Self> {
+        let origins: Vec<String> = raw
+            .split(',')
+            .map(normalize_origin)
+            .filter(|o| !o.is_empty())
+            .collect();
+
+        if origins.iter().any(|o| o == "*") {
+            return Ok(Self::Any);
+        }
+        if origins.is_empty() {
+            bail!("ALLOWED_ORIGINS must list at least one origin or '*'");
+        }
+        Ok(Self::List(origins))
+    }
+
+    /// Whether a request with this `Origin` header may connect
+    ///
+    /// Requests without an `Origin` header come from non-browser clients, which
+    /// same-origin protections don't apply to anyway, so they are allowed.
+    pub fn allows(&self, origin: Option<&str>) -> bool {
+        match (self, origin) {
+            (Self::Any, _) | (_, None) => true,
+            (Self::List(origins), Some(origin)) => origins.contains(&normalize_origin(origin)),
+        }
+    }
+}
+
+fn normalize_origin(origin: &str) -> String {
+    origin.trim().trim_end_matches('/').to_ascii_lowercase()
+}
+
+/// Small helper over the variable lookup with consistent error messages
+struct EnvReader<F> {
+    lookup: F,
+}
+
+impl<F: Fn(&str) -> Option<String>> EnvReader<F> {
+    /// Trimmed, non-empty value of `name`
+    fn optional(&self, name: &str) -> Option<String> {
+        (self.lookup)(name)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Parse `name`, using `default` when unset and failing on an unparseable value
+    fn parse<T: FromStr>(&self, name: &str, default: T) -> Result<T> {
+        match self.optional(name) {
+            Some(raw) => raw
+                .parse()
+                .map_err(|_| anyhow::anyhow!("{} must be a valid {} (got '{}')", name, std::any::type_name::<T>(), raw)),
+            None => Ok(default),
+        }
+    }
+}
+
+/// Parse `STOCK_INDICES` (`SYMBOL:Name` pairs separated by commas)
+fn parse_stock_indices(raw: &str) -> Result<Vec<(String, String)>> {
+    let mut indices: Vec<(String, String)> = Vec::new();
+    for pair in raw.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+        let (symbol, name) = pair.split_once(':').unwrap_or((pair, pair));
+        let symbol = symbol.trim().to_uppercase();
+        if symbol.is_empty() || !symbol.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '^') {
+            bail!("STOCK_INDICES: invalid symbol in '{}'", pair);
+        }
+        if indices.iter().any(|(existing, _)| *existing == symbol) {
+            bail!("STOCK_INDICES: duplicate symbol '{}'", symbol);
+        }
+        indices.push((symbol, name.trim().to_string()));
+    }
+    if indices.is_empty() {
+        bail!("STOCK_INDICES must list at least one symbol");
+    }
+    Ok(indices)
+}
+
+/// Parse `Name: value; Name2: value2` into a header map
+fn parse_headers(raw: &str) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    for pair in raw.split(';').map(str::trim).filter(|pair| !pair.is_empty()) {
+        let (name, value) = pair
+            .split_once(':')
+            .with_context(|| format!("missing ':' in '{}'", pair))?;
+        let name = HeaderName::from_str(name.trim()).with_context(|| format!("invalid header name '{}'", name.trim()))?;
+        let value = HeaderValue::from_str(value.trim()).with_context(|| format!("invalid value for header '{}'", name))?;
+        headers.append(name, value);
+    }
+    Ok(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config_from(vars: &[(&str, &str)]) -> Result<Config> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Config::from_lookup(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = config_from(&[]).unwrap();
+        assert_eq!(config.port, 8081);
+        assert_eq!(config.fetch_interval_seconds, 5);
+        assert!(config.cmc_api_key.is_none());
+        assert!(config.node_id.starts_with("ws-"));
+        assert_eq!(config.socket_addr().unwrap().port(), 8081);
+    }
+
+    #[test]
+    fn test_validation_errors_name_the_variable() {
+        let err = config_from(&[("SHUTDOWN_TIMEOUT_SECONDS", "0")]).unwrap_err();
+        assert!(err.to_string().contains("SHUTDOWN_TIMEOUT_SECONDS must be >= 1"));
+
+        let err = config_from(&[("FETCH_INTERVAL_SECONDS", "0")]).unwrap_err();
+        assert!(err.to_string().contains("FETCH_INTERVAL_SECONDS must be >= 1"));
+
+        assert_eq!(config_from(&[("REDIS_NAMESPACE", "tenant-a:")]).unwrap().cache_key_prefix, "tenant-a");
+        let err = config_from(&[("CACHE_KEY_PREFIX", "tenant a")]).unwrap_err();
+        assert!(err.to_string().contains("CACHE_KEY_PREFIX"));
+
+        let err = config_from(&[("REALTIME_CACHE_TTL_SECONDS", "0")]).unwrap_err();
+        assert!(err.to_string().contains("REALTIME_CACHE_TTL_SECONDS must be >= 1"));
+
+        let err = config_from(&[("ADMIN_HMAC_SECRET", "s3cret")]).unwrap_err();
+        assert!(err.to_string().contains("ADMIN_HMAC_SECRET requires ADMIN_TOKEN"));
+
+        let err = config_from(&[("MAX_CONCURRENT_FETCHES", "0")]).unwrap_err();
+        assert!(err.to_string().contains("MAX_CONCURRENT_FETCHES must be >= 1"));
+
+        let err = config_from(&[("WS_SEND_TIMEOUT_MS", "0")]).unwrap_err();
+        assert!(err.to_string().contains("WS_SEND_TIMEOUT_MS must be >= 1"));
+
+        let err = config_from(&[("PORT", "eighty")]).unwrap_err();
+        assert!(err.to_string().contains("PORT"));
+        assert!(err.to_string().contains("eighty"));
+
+        let err = config_from(&[("REQUIRED_GLOBAL_FIELDS", "market_cap,eth")]).unwrap_err();
+        assert!(err.to_string().contains("REQUIRED_GLOBAL_FIELDS: unknown field 'eth'"));
+
+        let err = config_from(&[("HTTP_DEFAULT_HEADERS", "X-Team: dashboards; Accept")]).unwrap_err();
+        assert!(format!("{:#}", err).contains("missing ':' in 'Accept'"));
+        let config = config_from(&[("HTTP_DEFAULT_HEADERS", "X-Team: dashboards; Accept-Language: en")]).unwrap();
+        assert_eq!(config.http_default_headers["x-team"], "dashboards");
+        assert_eq!(config.http_default_headers.len(), 2);
+
+        let config = config_from(&[("STOCK_INDICES", "spy:S&P 500, IWM:Russell 2000,EWJ")]).unwrap();
+        assert_eq!(config.stock_indices, vec![
+            ("SPY".to_string(), "S&P 500".to_string()),
+            ("IWM".to_string(), "Russell 2000".to_string()),
+            ("EWJ".to_string(), "EWJ".to_string()),
+        ]);
+        let err = config_from(&[("STOCK_INDICES", "SPY:a,SPY:b")]).unwrap_err();
+        assert!(err.to_string().contains("duplicate symbol 'SPY'"));
+
+        let err = config_from(&[("OUTPUT_CASE", "kebab")]).unwrap_err();
+        assert!(err.to_string().contains("OUTPUT_CASE"));
+        assert_eq!(config_from(&[("OUTPUT_CASE", "both")]).unwrap().output_case, OutputCase::Both);
+
+        let err = config_from(&[("PRICE_MODE", "median")]).unwrap_err();
+        assert!(err.to_string().contains("PRICE_MODE"));
+        let config = config_from(&[("PRICE_MODE", "vwap"), ("VWAP_MAX_DEVIATION_PERCENT", "1.5")]).unwrap();
+        assert_eq!(config.price_mode, PriceMode::Vwap { max_deviation_percent: 1.5 });
+    }
+
+    #[test]
+    fn test_allowed_origins() {
+        let config = config_from(&[("ALLOWED_ORIGINS", "https://app.example.com, https://Admin.example.com/")]).unwrap();
+        let origins = &config.allowed_origins;
+        assert!(origins.allows(Some("https://app.example.com")));
+        assert!(origins.allows(Some("https://admin.example.com")));
+        assert!(!origins.allows(Some("https://evil.example.com")));
+        assert!(!origins.allows(Some("http://app.example.com")));
+        assert!(origins.allows(None));
+
+        let config = config_from(&[("ALLOWED_ORIGINS", "*")]).unwrap();
+        assert!(config.allowed_origins.allows(Some("https://anything.test")));
+
+        assert!(config_from(&[("ALLOWED_ORIGINS", " , ")]).is_err());
+    }
+
+    #[test]
+    fn test_empty_optional_keys_are_none() {
+        let config = config_from(&[("CMC_API_KEY", "  "), ("RAILWAY_REPLICA_ID", "replica-1")]).unwrap();
+        assert!(config.cmc_api_key.is_none());
+        assert_eq!(config.node_id, "replica-1");
+    }
+}
@@ -1,9 +1,10 @@
-/// Data Transfer Objects (DTOs) for WebSocket communication
-///
-/// This module defines the API contract between the client and server,
-/// providing type-safe message structures for bidirectional communication.
+//! Data Transfer Objects (DTOs) for WebSocket communication
+//!
+//! This module defines the API contract between the client and server,
+//! providing type-safe message structures for bidirectional communication.
 
+pub mod schema;
 pub mod websocket;
 
 // Re-export commonly used types
-pub use websocket::{ClientMessage, ServerMessage, DashboardData, DashboardUpdatePayload};
+pub use websocket::{ClientMessage, ServerMessage, DashboardData, MarketStatistics, UsStockIndices, DashboardUpdatePayload, DashboardDeltaPayload, MarketUpdatePayload};
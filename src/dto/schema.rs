@@ -0,0 +1,210 @@
+//! JSON Schema for the WebSocket protocol
+//!
+//! Derived from `ClientMessage` and `ServerMessage` (and every payload type) with
+//! `schemars`, so the schema follows the same serde attributes as the wire format.
+//! `cargo run --bin schema` writes it to `schema/` for client type generation; a test
+//! fails while the committed files are stale.
+//!
+//! Dashboard data is described in the default camelCase; `OUTPUT_CASE` or
+//! `Subscribe { case }` renames its keys, and `BROADCAST_FIELD_ALLOWLIST` can drop some.
+
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+
+use super::{ClientMessage, ServerMessage};
+
+/// Builds one schema file's contents
+pub type SchemaBuilder = fn() -> RootSchema;
+
+/// File name and builder of each schema `cargo run --bin schema` writes
+pub const SCHEMA_FILES: &[(&str, SchemaBuilder)] = &[
+    ("client_message.json", client_message_schema),
+    ("server_message.json", server_message_schema),
+];
+
+/// Schema of the messages clients send
+pub fn client_message_schema() -> RootSchema {
+    schema_for!(ClientMessage)
+}
+
+/// Schema of the messages the server sends
+pub fn server_message_schema() -> RootSchema {
+    schema_for!(ServerMessage)
+}
+
+/// Schema file contents as written: pretty JSON with a trailing newline
+pub fn render(schema: &RootSchema) -> String {
+    let mut text = serde_json::to_string_pretty(schema).expect("a schema is plain JSON");
+    text.push('\n');
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::websocket::*;
+    use crate::service_islands::layer3_communication::websocket_service::transforms::OutputCase;
+    use serde_json::{json, Value};
+
+    /// Minimal validator for the keywords `schemars` emits; unlike JSON Schema it also
+    /// rejects properties a schema doesn't list, so a sample exercising every field
+    /// proves the derived schema describes what serde actually writes
+    fn validate(root: &Value, schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+        if schema == &Value::Bool(true) {
+            return Ok(());
+        }
+        if let Some(target) = schema.get("$ref").and_then(Value::as_str) {
+            let resolved = match target.strip_prefix("#/definitions/") {
+                Some(name) => &root["definitions"][name],
+                None => root,
+            };
+            return validate(root, resolved, value, path);
+        }
+        for option in schema.get("allOf").and_then(Value::as_array).into_iter().flatten() {
+            validate(root, option, value, path)?;
+        }
+        if let Some(options) = schema.get("oneOf").and_then(Value::as_array) {
+            let matches = options.iter().filter(|option| validate(root, option, value, path).is_ok()).count();
+            if matches != 1 {
+                return Err(format!("{}: matches {} variants", path, matches));
+            }
+        }
+        if let Some(options) = schema.get("anyOf").and_then(Value::as_array) {
+            if !options.iter().any(|option| validate(root, option, value, path).is_ok()) {
+                return Err(format!("{}: matches no alternative", path));
+            }
+        }
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                return Err(format!("{}: {} not in {:?}", path, value, allowed));
+            }
+        }
+        let kinds: Vec<&str> = match &schema.get("type") {
+            Some(Value::String(kind)) => vec![kind.as_str()],
+            Some(Value::Array(kinds)) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        let kind_matches = |kind: &str| match kind {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            _ => true,
+        };
+        if !kinds.is_empty() && !kinds.iter().any(|kind| kind_matches(kind)) {
+            return Err(format!("{}: {} is not {}", path, value, schema["type"]));
+        }
+        if let (Some(minimum), Some(number)) = (schema.get("minimum").and_then(Value::as_f64), value.as_f64()) {
+            if number < minimum {
+                return Err(format!("{}: {} is below {}", path, value, minimum));
+            }
+        }
+        if let (Some(items), Some(values)) = (schema.get("items"), value.as_array()) {
+            for (index, item) in values.iter().enumerate() {
+                validate(root, items, item, &format!("{}/{}", path, index))?;
+            }
+        }
+        if let Some(fields) = value.as_object() {
+            for name in schema["required"].as_array().into_iter().flatten().filter_map(Value::as_str) {
+                if !fields.contains_key(name) {
+                    return Err(format!("{}: missing {}", path, name));
+                }
+            }
+            for (name, field) in fields {
+                let field_path = format!("{}/{}", path, name);
+                match (schema["properties"].get(name), schema.get("additionalProperties")) {
+                    (Some(property), _) => validate(root, property, field, &field_path)?,
+                    (None, Some(values)) => validate(root, values, field, &field_path)?,
+                    (None, None) if schema.get("properties").is_some() => return Err(format!("{}: not in the schema", field_path)),
+                    (None, None) => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn assert_valid(schema: &RootSchema, message: impl serde::Serialize) {
+        let schema = serde_json::to_value(schema).unwrap();
+        let value = serde_json::to_value(message).unwrap();
+        if let Err(e) = validate(&schema, &schema, &value, "") {
+            panic!("{} does not match the schema: {}", value, e);
+        }
+    }
+
+    /// A dashboard with every optional field present
+    fn full_dashboard() -> DashboardData {
+        let mut json = zeroed_dashboard_json();
+        json["btc_rsi_14"] = json!(55.0);
+        json["fng_value"] = json!(40);
+        json["fng_classification"] = json!("Fear");
+        json["us_stock_indices"] = json!({
+            "SPY": { "symbol": "SPY", "name": "S&P 500", "price": 500.0, "change": 1.0, "change_percent": 0.2, "status": "success" },
+        });
+        json["stale_fields"] = json!(["fng_value"]);
+        json["sources"] = json!({
+            "crypto": { "source": "binance_ws", "ok": true, "exchanges": { "BTC": { "binance": 1.0 } } },
+            "fng": { "source": null, "ok": false, "disabled": true },
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_committed_schema_files_are_current() {
+        let committed = [
+            include_str!("../../schema/client_message.json"),
+            include_str!("../../schema/server_message.json"),
+        ];
+        for ((name, build), committed) in SCHEMA_FILES.iter().zip(committed) {
+            assert_eq!(committed, render(&build()), "schema/{} is stale; run `cargo run --bin schema`", name);
+        }
+    }
+
+    #[test]
+    fn test_client_messages_match_the_derived_schema() {
+        let schema = client_message_schema();
+        let messages = [
+            ClientMessage::Subscribe(SubscribePayload {
+                topics: vec!["BTC".into()], delta: Some(true), batch: Some(false), case: Some(OutputCase::Snake),
+            }),
+            ClientMessage::Heartbeat,
+            ClientMessage::Hello(HelloPayload { client_version: Some("1.0".into()), requested_features: vec![FEATURE_DELTA.into()] }),
+            ClientMessage::GetHistory(GetHistoryPayload { limit: Some(10) }),
+        ];
+        for message in messages {
+            assert_valid(&schema, message);
+        }
+
+        let schema = serde_json::to_value(&schema).unwrap();
+        assert!(validate(&schema, &schema, &json!({ "type": "Subscribe", "payload": {} }), "").is_err());
+        assert!(validate(&schema, &schema, &json!({ "type": "Unknown" }), "").is_err());
+    }
+
+    #[test]
+    fn test_server_messages_match_the_derived_schema() {
+        let schema = server_message_schema();
+        let dashboard = full_dashboard();
+        let mut changed = full_dashboard();
+        changed.btc_price_usd = 1.0;
+        let messages = vec![
+            ServerMessage::new_welcome("conn".into(), "1.0", Some("token".into()), vec!["dashboard".into()]),
+            ServerMessage::MarketUpdate(MarketUpdatePayload {
+                symbol: "BTC".into(), price: 1.0, change_24h: 0.5, volume: Some(2.0), timestamp: 1,
+            }),
+            ServerMessage::DashboardUpdate(DashboardUpdatePayload::new(dashboard.clone(), "api")),
+            ServerMessage::DashboardDelta(DashboardDeltaPayload::new(changed.diff(&dashboard).unwrap())),
+            ServerMessage::SystemHealth(SystemHealthPayload {
+                status: HealthStatus::Degraded,
+                layer_health: Some(LayerHealth { infrastructure: true, external_apis: false, websocket: true }),
+                timestamp: 1,
+            }),
+            ServerMessage::new_connection_error("conn", ERROR_CODE_RATE_LIMITED, "slow down"),
+            ServerMessage::new_batch(vec![ServerMessage::Pong(PongPayload { client_time: 1, server_time: 2 })]),
+        ];
+        for message in messages {
+            assert_valid(&schema, message);
+        }
+    }
+}
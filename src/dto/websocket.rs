@@ -5,8 +5,65 @@
 //! for easy parsing.
 
 use chrono::Utc;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::service_islands::layer3_communication::websocket_service::transforms::OutputCase;
+
+// ============================================================================
+// Protocol Constants
+// ============================================================================
+
+/// WebSocket subprotocol advertised via `Sec-WebSocket-Protocol`
+///
+/// Bump the suffix when the message format changes incompatibly.
+pub const WS_SUBPROTOCOL: &str = "market-data-v1";
+
+/// Message schema version reported in `Welcome` and `Capabilities`
+///
+/// Bump when message variants or payloads change in a way clients must know about.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// `ServerMessage` variants this server can send
+pub const SUPPORTED_SERVER_MESSAGES: &[&str] = &[
+    "Welcome",
+    "Capabilities",
+    "MarketUpdate",
+    "DashboardUpdate",
+    "DashboardDelta",
+    "History",
+    "Subscriptions",
+    "Pong",
+    "SystemHealth",
+    "Notice",
+    "Error",
+    "Ack",
+    "Batch",
+    "Reconnect",
+];
+
+/// `ClientMessage` variants, by their `type` tag
+pub const CLIENT_MESSAGE_TYPES: &[&str] = &[
+    "Subscribe",
+    "Unsubscribe",
+    "Heartbeat",
+    "Hello",
+    "Resume",
+    "GetHistory",
+    "Ping",
+    "GetSubscriptions",
+];
+
+/// Optional feature: sparse `DashboardDelta` updates (opt in via `Subscribe { delta: true }`)
+pub const FEATURE_DELTA: &str = "delta";
+
+/// Optional feature: broadcasts sent as MessagePack binary frames instead of JSON text
+pub const FEATURE_MSGPACK: &str = "msgpack";
+
+/// Optional features a client may request in `Hello`
+pub const AVAILABLE_FEATURES: &[&str] = &[FEATURE_DELTA, FEATURE_MSGPACK];
 
 // ============================================================================
 // Error Code Constants
@@ -18,6 +75,8 @@ pub const ERROR_CODE_SUBSCRIPTION_FAILED: &str = "SUBSCRIPTION_FAILED";
 pub const ERROR_CODE_UNSUBSCRIBE_FAILED: &str = "UNSUBSCRIBE_FAILED";
 pub const ERROR_CODE_INTERNAL_ERROR: &str = "INTERNAL_ERROR";
 pub const ERROR_CODE_RATE_LIMITED: &str = "RATE_LIMITED";
+pub const ERROR_CODE_RESUME_FAILED: &str = "RESUME_FAILED";
+pub const ERROR_CODE_MESSAGE_TOO_BIG: &str = "MESSAGE_TOO_BIG";
 
 // ============================================================================
 // Client Messages (Client → Server)
@@ -32,7 +91,7 @@ pub const ERROR_CODE_RATE_LIMITED: &str = "RATE_LIMITED";
 ///   "payload": { "topics": ["BTC", "ETH"] }
 /// }
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "type", content = "payload")]
 pub enum ClientMessage {
     /// Subscribe to specific topics or symbols
@@ -43,6 +102,21 @@ pub enum ClientMessage {
 
     /// Heartbeat/ping to keep connection alive
     Heartbeat,
+
+    /// Optional handshake: client version and the optional features it understands
+    Hello(HelloPayload),
+
+    /// Restore a dropped connection's subscriptions using the token from its `Welcome`
+    Resume(ResumePayload),
+
+    /// Request the most recent dashboards (answered with `History`)
+    GetHistory(GetHistoryPayload),
+
+    /// Latency probe, answered with `Pong` (separate from the `Heartbeat` keep-alive)
+    Ping(PingPayload),
+
+    /// Ask for the connection's current topics (answered with `Subscriptions`)
+    GetSubscriptions,
 }
 
 impl ClientMessage {
@@ -50,11 +124,28 @@ impl ClientMessage {
     ///
     /// # Example
     /// ```
+    /// # use web_server_report_websocket::ClientMessage;
     /// let msg = ClientMessage::from_json_str(r#"{"type":"Heartbeat"}"#)?;
+    /// assert_eq!(msg.type_name(), "Heartbeat");
+    /// # Ok::<(), serde_json::Error>(())
     /// ```
     pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error> {
         serde_json::from_str(s)
     }
+
+    /// The message's `type` tag (one of `CLIENT_MESSAGE_TYPES`)
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ClientMessage::Subscribe(_) => "Subscribe",
+            ClientMessage::Unsubscribe(_) => "Unsubscribe",
+            ClientMessage::Heartbeat => "Heartbeat",
+            ClientMessage::Hello(_) => "Hello",
+            ClientMessage::Resume(_) => "Resume",
+            ClientMessage::GetHistory(_) => "GetHistory",
+            ClientMessage::Ping(_) => "Ping",
+            ClientMessage::GetSubscriptions => "GetSubscriptions",
+        }
+    }
 }
 
 // ============================================================================
@@ -64,55 +155,132 @@ impl ClientMessage {
 /// Messages sent FROM the server TO the client.
 ///
 /// Uses adjacently-tagged enum format for easy frontend parsing.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "type", content = "payload")]
+#[allow(clippy::large_enum_variant)] // DashboardUpdate dominates traffic; boxing it buys nothing
 pub enum ServerMessage {
     /// Welcome message sent immediately upon connection
     Welcome(WelcomePayload),
 
+    /// Reply to `Hello`: the features this connection will actually get
+    Capabilities(CapabilitiesPayload),
+
     /// Individual market data update for a specific symbol
     MarketUpdate(MarketUpdatePayload),
 
     /// Full dashboard update with all market data (current implementation)
     DashboardUpdate(DashboardUpdatePayload),
 
+    /// Only the dashboard fields that changed since the previous broadcast (delta mode)
+    DashboardDelta(DashboardDeltaPayload),
+
+    /// Recent dashboards, oldest first (reply to `GetHistory`)
+    History(HistoryPayload),
+
+    /// The connection's current topics (reply to `GetSubscriptions`)
+    Subscriptions(SubscriptionsPayload),
+
+    /// Reply to `Ping`
+    Pong(PongPayload),
+
     /// System health status update
     SystemHealth(SystemHealthPayload),
 
+    /// Operator notice broadcast to every client (e.g. maintenance announcements)
+    Notice(NoticePayload),
+
     /// Error message
     Error(ErrorPayload),
 
     /// Acknowledgment of subscription/unsubscription
     Ack(AckPayload),
+
+    /// Sent just before the server closes the connection: when to reconnect
+    Reconnect(ReconnectPayload),
+
+    /// Several messages in one frame (opt in via `Subscribe { batch: true }`)
+    ///
+    /// Never contains another `Batch`; build with `ServerMessage::new_batch`.
+    Batch(Vec<ServerMessage>),
 }
 
 impl ServerMessage {
+    /// The message's `type` tag (one of `SUPPORTED_SERVER_MESSAGES`)
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ServerMessage::Welcome(_) => "Welcome",
+            ServerMessage::Capabilities(_) => "Capabilities",
+            ServerMessage::MarketUpdate(_) => "MarketUpdate",
+            ServerMessage::DashboardUpdate(_) => "DashboardUpdate",
+            ServerMessage::DashboardDelta(_) => "DashboardDelta",
+            ServerMessage::History(_) => "History",
+            ServerMessage::Subscriptions(_) => "Subscriptions",
+            ServerMessage::Pong(_) => "Pong",
+            ServerMessage::SystemHealth(_) => "SystemHealth",
+            ServerMessage::Notice(_) => "Notice",
+            ServerMessage::Error(_) => "Error",
+            ServerMessage::Ack(_) => "Ack",
+            ServerMessage::Reconnect(_) => "Reconnect",
+            ServerMessage::Batch(_) => "Batch",
+        }
+    }
+
     /// Create a new error message
     ///
     /// # Example
     /// ```
+    /// # use web_server_report_websocket::ServerMessage;
+    /// # use web_server_report_websocket::dto::websocket::ERROR_CODE_INVALID_TOPIC;
     /// let error = ServerMessage::new_error(
     ///     ERROR_CODE_INVALID_TOPIC,
     ///     "Topic 'INVALID' does not exist"
     /// );
     /// ```
     pub fn new_error(code: &str, message: &str) -> Self {
-        ServerMessage::Error(ErrorPayload {
-            code: code.to_string(),
-            message: message.to_string(),
-            timestamp: Utc::now().timestamp(),
-        })
+        ServerMessage::Error(ErrorPayload::new(code, message))
     }
 
-    /// Create a welcome message
-    pub fn new_welcome(connection_id: String, server_version: &str) -> Self {
+    /// Create an error message for one connection, carrying its id for log correlation
+    pub fn new_connection_error(connection_id: impl ToString, code: &str, message: &str) -> Self {
+        ServerMessage::Error(ErrorPayload::new(code, message).for_connection(connection_id))
+    }
+
+    /// Create a reconnect hint (see `ReconnectPayload`)
+    pub fn new_reconnect(after_ms: u64, jitter_ms: u64) -> Self {
+        ServerMessage::Reconnect(ReconnectPayload { after_ms, jitter_ms })
+    }
+
+    /// Create a welcome message (an empty `available_topics` is left out)
+    pub fn new_welcome(
+        connection_id: String,
+        server_version: &str,
+        resume_token: Option<String>,
+        available_topics: Vec<String>,
+    ) -> Self {
         ServerMessage::Welcome(WelcomePayload {
             connection_id,
             server_version: server_version.to_string(),
+            protocol_version: PROTOCOL_VERSION,
+            supported_messages: SUPPORTED_SERVER_MESSAGES.iter().map(|m| m.to_string()).collect(),
+            resume_token,
+            available_topics,
             timestamp: Utc::now().to_rfc3339(),
         })
     }
 
+    /// Create a capabilities reply granting the requested features this server supports
+    pub fn new_capabilities(requested_features: &[String]) -> Self {
+        ServerMessage::Capabilities(CapabilitiesPayload {
+            protocol_version: PROTOCOL_VERSION,
+            supported_messages: SUPPORTED_SERVER_MESSAGES.iter().map(|m| m.to_string()).collect(),
+            features: requested_features
+                .iter()
+                .filter(|f| AVAILABLE_FEATURES.contains(&f.as_str()))
+                .cloned()
+                .collect(),
+        })
+    }
+
     /// Create an acknowledgment message
     pub fn new_ack(action: &str, topics: Vec<String>) -> Self {
         ServerMessage::Ack(AckPayload {
@@ -122,13 +290,33 @@ impl ServerMessage {
         })
     }
 
+    /// Create a batch, flattening any nested batches into it
+    pub fn new_batch(messages: Vec<ServerMessage>) -> Self {
+        let mut flat = Vec::with_capacity(messages.len());
+        for message in messages {
+            match message {
+                ServerMessage::Batch(inner) => match ServerMessage::new_batch(inner) {
+                    ServerMessage::Batch(inner) => flat.extend(inner),
+                    other => flat.push(other),
+                },
+                other => flat.push(other),
+            }
+        }
+        ServerMessage::Batch(flat)
+    }
+
     /// Serialize to JSON string for sending via WebSocket
     ///
     /// # Example
-    /// ```
+    /// ```no_run
+    /// # use axum::extract::ws::{Message, WebSocket};
+    /// # use web_server_report_websocket::ServerMessage;
+    /// # async fn send(mut socket: WebSocket) -> anyhow::Result<()> {
     /// let msg = ServerMessage::new_error("ERR001", "Something went wrong");
     /// let json_str = msg.to_json_string()?;
     /// socket.send(Message::Text(json_str)).await?;
+    /// # Ok(())
+    /// # }
     /// ```
     pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(self)
@@ -139,26 +327,96 @@ impl ServerMessage {
 // Client Message Payloads
 // ============================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SubscribePayload {
     /// List of topics/symbols to subscribe to
     /// Examples: ["BTC", "ETH", "MarketStats", "SystemHealth"]
+    ///
+    /// An empty list subscribes to `"dashboard"` (full dashboard updates, same as
+    /// never subscribing); a non-empty list narrows what the connection receives.
     pub topics: Vec<String>,
+
+    /// Opt in to (or out of) delta mode: receive `DashboardDelta` messages with only
+    /// the changed fields, plus a periodic full `dashboard_update` to resync.
+    /// Omitted: the connection keeps its current mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta: Option<bool>,
+
+    /// Opt in to (or out of) batch mode: each tick's `MarketUpdate`s for the subscribed
+    /// symbols arrive as one `Batch` frame instead of the full dashboard.
+    /// Omitted: the connection keeps its current mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch: Option<bool>,
+
+    /// Key casing of the dashboard data this connection receives (`camel`, `snake` or
+    /// `both`), overriding `OUTPUT_CASE`. Omitted: the connection keeps its current casing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub case: Option<OutputCase>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UnsubscribePayload {
     /// List of topics/symbols to unsubscribe from
     pub topics: Vec<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HelloPayload {
+    /// Client application version (for logging/diagnostics)
+    #[serde(default)]
+    pub client_version: Option<String>,
+
+    /// Optional features the client understands (see `AVAILABLE_FEATURES`)
+    #[serde(default)]
+    pub requested_features: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetHistoryPayload {
+    /// Maximum number of dashboards to return (default: everything buffered)
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PingPayload {
+    /// Client clock when the ping was sent (Unix ms), echoed back in `Pong`
+    pub client_time: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumePayload {
+    /// `resumeToken` from the previous connection's `Welcome`
+    pub token: String,
+}
+
 // ============================================================================
 // Server Message Payloads
 // ============================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionsPayload {
+    /// Subscribed topics, sorted (empty: default full dashboard updates)
+    pub topics: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PongPayload {
+    /// `clientTime` from the `Ping`; round trip = now - clientTime
+    pub client_time: i64,
+    /// Server clock when the ping was handled (Unix ms), for estimating clock skew
+    pub server_time: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct WelcomePayload {
     /// Unique connection identifier
@@ -167,11 +425,38 @@ pub struct WelcomePayload {
     /// Server version information
     pub server_version: String,
 
+    /// Message schema version (`PROTOCOL_VERSION`)
+    pub protocol_version: u32,
+
+    /// `ServerMessage` types this server may send
+    pub supported_messages: Vec<String>,
+
+    /// Token to send in `Resume` after a reconnect (absent when resume is disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_token: Option<String>,
+
+    /// Topics accepted by `Subscribe` (absent when the client connected with `?welcome_topics=false`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub available_topics: Vec<String>,
+
     /// Connection timestamp (RFC3339 format)
     pub timestamp: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilitiesPayload {
+    /// Message schema version (`PROTOCOL_VERSION`)
+    pub protocol_version: u32,
+
+    /// `ServerMessage` types this server may send
+    pub supported_messages: Vec<String>,
+
+    /// Requested features that are enabled for this connection
+    pub features: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct MarketUpdatePayload {
     /// Symbol/ticker (e.g., "BTC", "ETH", "SOL")
@@ -195,7 +480,7 @@ pub struct MarketUpdatePayload {
 ///
 /// Matches the exact structure from Redis stream and dashboard_aggregator.
 /// Accepts snake_case from Redis (via aliases) and outputs camelCase to frontend.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DashboardData {
     // BTC data
@@ -205,16 +490,18 @@ pub struct DashboardData {
     pub btc_change_24h: f64,
     #[serde(alias = "btc_market_cap_percentage")]
     pub btc_market_cap_percentage: f64,
-    #[serde(alias = "btc_rsi_14")]
-    pub btc_rsi_14: f64,
+    /// None when the RSI fetch failed (`partial_failure` is set)
+    #[serde(alias = "btc_rsi_14", default)]
+    pub btc_rsi_14: Option<f64>,
 
     // ETH data
     #[serde(alias = "eth_price_usd")]
     pub eth_price_usd: f64,
     #[serde(alias = "eth_change_24h")]
     pub eth_change_24h: f64,
-    #[serde(alias = "eth_market_cap_percentage")]
-    pub eth_market_cap_percentage: f64,
+    /// None when the global data provider omitted it
+    #[serde(alias = "eth_market_cap_percentage", default)]
+    pub eth_market_cap_percentage: Option<f64>,
 
     // SOL data
     #[serde(alias = "sol_price_usd")]
@@ -255,18 +542,42 @@ pub struct DashboardData {
     pub market_cap_change_percentage_24h_usd: f64,
 
     // Indicators
-    #[serde(alias = "fng_value")]
-    pub fng_value: u32,
+    /// None when the Fear & Greed fetch failed (`partial_failure` is set)
+    #[serde(alias = "fng_value", default)]
+    pub fng_value: Option<u32>,
+    /// Official Fear & Greed label (e.g. "Extreme Fear"); None if the API didn't provide one
+    #[serde(alias = "fng_classification", default)]
+    pub fng_classification: Option<String>,
 
-    // US Stock Indices (nested object)
-    #[serde(alias = "us_stock_indices")]
-    pub us_stock_indices: Value,
+    // US Stock Indices (empty when Finnhub is unavailable)
+    #[serde(alias = "us_stock_indices", default)]
+    pub us_stock_indices: UsStockIndices,
 
     // Metadata
     #[serde(alias = "fetch_duration_ms")]
     pub fetch_duration_ms: u64,
+    /// Some data group failed to fetch (see `sources`)
     #[serde(alias = "partial_failure")]
     pub partial_failure: bool,
+    /// A critical group (crypto prices or global data) failed; implies `partial_failure`
+    #[serde(alias = "critical_failure", default)]
+    pub critical_failure: bool,
+    /// Fields showing their last successful value because this fetch failed (e.g. `fng_value`)
+    #[serde(alias = "stale_fields", default)]
+    pub stale_fields: Vec<String>,
+    /// `live` (just fetched by the broadcasting node) or `cache` (relayed from Redis);
+    /// set at broadcast time, empty elsewhere
+    #[serde(default)]
+    pub origin: String,
+    /// Age of the data (since `timestamp`) when it was broadcast
+    #[serde(alias = "age_ms", default)]
+    pub age_ms: u64,
+    /// Source and fetch status per data group (`crypto`, `global`, `fng`, `rsi`, `indices`)
+    #[serde(default)]
+    pub sources: BTreeMap<String, SourceStatus>,
+    /// Time each data group took during the aggregation (diagnostics)
+    #[serde(default)]
+    pub timings: DashboardTimings,
     #[serde(alias = "last_updated")]
     pub last_updated: String,
     #[serde(alias = "timestamp")]
@@ -278,8 +589,20 @@ impl DashboardData {
     ///
     /// # Example
     /// ```
-    /// let json = r#"{"btc_price_usd": 96000.0, "fng_value": 10, ...}"#;
-    /// let data = DashboardData::from_json_str(json)?;
+    /// # use web_server_report_websocket::DashboardData;
+    /// # let mut json = serde_json::json!({
+    /// #     "btc_price_usd": 96000.0, "fng_value": 10, "partial_failure": false, "last_updated": "", "timestamp": "",
+    /// # });
+    /// # for field in ["btc_change_24h", "btc_market_cap_percentage", "eth_price_usd", "eth_change_24h",
+    /// #     "sol_price_usd", "sol_change_24h", "xrp_price_usd", "xrp_change_24h", "ada_price_usd", "ada_change_24h",
+    /// #     "link_price_usd", "link_change_24h", "bnb_price_usd", "bnb_change_24h", "market_cap_usd",
+    /// #     "volume_24h_usd", "market_cap_change_percentage_24h_usd", "fetch_duration_ms"] {
+    /// #     json[field] = 0.into();
+    /// # }
+    /// # let json = json.to_string();
+    /// let data = DashboardData::from_json_str(&json)?; // {"btc_price_usd": 96000.0, "fng_value": 10, ...}
+    /// assert_eq!(data.fng_value, Some(10));
+    /// # Ok::<(), serde_json::Error>(())
     /// ```
     pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error> {
         serde_json::from_str(s)
@@ -289,9 +612,214 @@ impl DashboardData {
     pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(self)
     }
+
+    /// Fields whose value differs from `previous`, keyed by their camelCase wire name
+    pub fn diff(&self, previous: &DashboardData) -> Result<serde_json::Map<String, Value>, serde_json::Error> {
+        let current = serde_json::to_value(self)?;
+        let previous = serde_json::to_value(previous)?;
+
+        let (Value::Object(current), Value::Object(previous)) = (current, previous) else {
+            return Ok(serde_json::Map::new());
+        };
+
+        Ok(current
+            .into_iter()
+            .filter(|(field, value)| previous.get(field) != Some(value))
+            .collect())
+    }
+
+    /// Implausible values, as `name=value` pairs (empty when the dashboard is fit to broadcast)
+    ///
+    /// Numbers must be finite, prices and totals non-negative and percentages within
+    /// 0-100. Failed groups are zero-filled, so BTC price and market cap only have to be
+    /// positive when `critical_failure` is not set.
+    pub fn invalid_fields(&self) -> Vec<String> {
+        let mut invalid = Vec::new();
+        let mut check = |name: &str, value: f64, valid: bool| {
+            if !(value.is_finite() && valid) {
+                invalid.push(format!("{}={}", name, value));
+            }
+        };
+
+        let prices = [
+            ("btc_price_usd", self.btc_price_usd),
+            ("eth_price_usd", self.eth_price_usd),
+            ("sol_price_usd", self.sol_price_usd),
+            ("xrp_price_usd", self.xrp_price_usd),
+            ("ada_price_usd", self.ada_price_usd),
+            ("link_price_usd", self.link_price_usd),
+            ("bnb_price_usd", self.bnb_price_usd),
+            ("market_cap_usd", self.market_cap_usd),
+            ("volume_24h_usd", self.volume_24h_usd),
+        ];
+        for (name, value) in prices {
+            let required = !self.critical_failure && matches!(name, "btc_price_usd" | "market_cap_usd");
+            check(name, value, if required { value > 0.0 } else { value >= 0.0 });
+        }
+        for (symbol, change) in self.coin_changes() {
+            check(&format!("{}_change_24h", symbol.to_lowercase()), change, true);
+        }
+        check("market_cap_change_percentage_24h_usd", self.market_cap_change_percentage_24h_usd, true);
+
+        let percentages = [
+            ("btc_market_cap_percentage", Some(self.btc_market_cap_percentage)),
+            ("eth_market_cap_percentage", self.eth_market_cap_percentage),
+            ("btc_rsi_14", self.btc_rsi_14),
+            ("fng_value", self.fng_value.map(f64::from)),
+        ];
+        for (name, value) in percentages {
+            if let Some(value) = value {
+                check(name, value, (0.0..=100.0).contains(&value));
+            }
+        }
+        invalid
+    }
+
+    /// 24h change of each dashboard coin, in `DASHBOARD_SYMBOLS` order
+    pub fn coin_changes(&self) -> [(&'static str, f64); 7] {
+        [
+            ("BTC", self.btc_change_24h),
+            ("ETH", self.eth_change_24h),
+            ("SOL", self.sol_change_24h),
+            ("XRP", self.xrp_change_24h),
+            ("ADA", self.ada_change_24h),
+            ("LINK", self.link_change_24h),
+            ("BNB", self.bnb_change_24h),
+        ]
+    }
+
+    /// Market cap (USD) of each dashboard coin whose dominance is known, in `DASHBOARD_SYMBOLS` order
+    ///
+    /// Derived from the total market cap and `*_market_cap_percentage`, which the global
+    /// data provider only reports for BTC and ETH.
+    pub fn coin_market_caps(&self) -> Vec<(&'static str, f64)> {
+        [("BTC", Some(self.btc_market_cap_percentage)), ("ETH", self.eth_market_cap_percentage)]
+            .into_iter()
+            .filter_map(|(symbol, percentage)| Some((symbol, percentage? / 100.0 * self.market_cap_usd)))
+            .filter(|(_, cap)| *cap > 0.0)
+            .collect()
+    }
+
+    /// Market-wide figures derived from this dashboard
+    pub fn statistics(&self) -> MarketStatistics {
+        let changes = self.coin_changes();
+        let count = |keep: fn(f64) -> bool| changes.iter().filter(|(_, change)| keep(*change)).count() as u32;
+        let rsi_values: Vec<f64> = self.btc_rsi_14.into_iter().collect();
+
+        let caps = self.coin_market_caps();
+        let weighted_cap: f64 = caps.iter().map(|(_, cap)| cap).sum();
+        let weighted_change = caps.iter()
+            .filter_map(|(symbol, cap)| Some(cap * changes.iter().find(|(coin, _)| coin == symbol)?.1))
+            .sum::<f64>();
+
+        MarketStatistics {
+            total_market_cap_usd: self.market_cap_usd,
+            total_market_cap_change_24h: self.market_cap_change_percentage_24h_usd,
+            market_cap_weighted_change_24h: (weighted_cap > 0.0).then(|| weighted_change / weighted_cap),
+            weighted_coins: caps.iter().map(|(symbol, _)| symbol.to_string()).collect(),
+            coins_up: count(|change| change > 0.0),
+            coins_down: count(|change| change < 0.0),
+            coins_unchanged: count(|change| change == 0.0),
+            average_rsi_14: (!rsi_values.is_empty()).then(|| rsi_values.iter().sum::<f64>() / rsi_values.len() as f64),
+            partial_failure: self.partial_failure,
+            timestamp: self.timestamp.clone(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Market-wide figures derived from one dashboard (see `DashboardData::statistics`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketStatistics {
+    /// Total crypto market capitalization (USD)
+    pub total_market_cap_usd: f64,
+    /// 24h change of the total market cap (%), as reported by the global data provider
+    pub total_market_cap_change_24h: f64,
+    /// 24h change (%) of the dashboard coins in `weighted_coins`, each weighted by its market cap;
+    /// None when no coin's market cap is known
+    pub market_cap_weighted_change_24h: Option<f64>,
+    /// Coins included in the weighting (those with a known market cap)
+    pub weighted_coins: Vec<String>,
+    /// Dashboard coins up, down and flat over 24h
+    pub coins_up: u32,
+    pub coins_down: u32,
+    pub coins_unchanged: u32,
+    /// Mean RSI(14) over the coins that have one (only BTC today); None when none does
+    pub average_rsi_14: Option<f64>,
+    /// The source dashboard had failed groups, so some figures may be stale or zero
+    pub partial_failure: bool,
+    /// `timestamp` of the source dashboard
+    pub timestamp: String,
+}
+
+/// US stock index quotes keyed by ETF symbol (e.g. "SPY")
+///
+/// Serialized as a plain object, so `{}` is an empty map rather than a missing field.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct UsStockIndices(pub BTreeMap<String, UsStockIndex>);
+
+impl UsStockIndices {
+    /// Whether no index data is available
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Quote for one ETF symbol
+    pub fn get(&self, symbol: &str) -> Option<&UsStockIndex> {
+        self.0.get(symbol)
+    }
+}
+
+/// One US stock index quote (keys stay snake_case, as sent by the aggregator)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct UsStockIndex {
+    #[serde(default)]
+    pub symbol: String,
+    #[serde(default)]
+    pub name: String,
+    pub price: f64,
+    pub change: f64,
+    pub change_percent: f64,
+    /// "success", or "failed" / "unavailable" (not on the Finnhub plan) for a placeholder quote
+    pub status: String,
+}
+
+/// Where one dashboard data group came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SourceStatus {
+    /// Provider that served the data (e.g. "binance_ws", "coinmarketcap"); None if the fetch failed
+    pub source: Option<String>,
+    /// Whether the group was fetched successfully
+    pub ok: bool,
+    /// Switched off by an operator (not fetched, and not counted as a failure)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+    /// `PRICE_MODE=vwap` only: each coin's price per exchange (`crypto` group)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchanges: Option<BTreeMap<String, BTreeMap<String, f64>>>,
+}
+
+/// Milliseconds each data group's fetch took in one aggregation
+///
+/// Groups are fetched concurrently, so these overlap; the slowest one bounds `fetch_duration_ms`.
+/// Cached or throttled groups take close to 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardTimings {
+    #[serde(alias = "crypto_ms", default)]
+    pub crypto_ms: u64,
+    #[serde(alias = "global_ms", default)]
+    pub global_ms: u64,
+    #[serde(alias = "fng_ms", default)]
+    pub fng_ms: u64,
+    #[serde(alias = "rsi_ms", default)]
+    pub rsi_ms: u64,
+    #[serde(alias = "indices_ms", default)]
+    pub indices_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DashboardUpdatePayload {
     /// Complete dashboard data (strongly-typed structure)
@@ -319,8 +847,20 @@ impl DashboardUpdatePayload {
     ///
     /// # Example
     /// ```
-    /// let redis_value = r#"{"btc_price_usd": 96000.0, "fng_value": 10, ...}"#;
-    /// let payload = DashboardUpdatePayload::from_json_str(redis_value, "external_apis")?;
+    /// # use web_server_report_websocket::DashboardUpdatePayload;
+    /// # let mut json = serde_json::json!({
+    /// #     "btc_price_usd": 96000.0, "fng_value": 10, "partial_failure": false, "last_updated": "", "timestamp": "",
+    /// # });
+    /// # for field in ["btc_change_24h", "btc_market_cap_percentage", "eth_price_usd", "eth_change_24h",
+    /// #     "sol_price_usd", "sol_change_24h", "xrp_price_usd", "xrp_change_24h", "ada_price_usd", "ada_change_24h",
+    /// #     "link_price_usd", "link_change_24h", "bnb_price_usd", "bnb_change_24h", "market_cap_usd",
+    /// #     "volume_24h_usd", "market_cap_change_percentage_24h_usd", "fetch_duration_ms"] {
+    /// #     json[field] = 0.into();
+    /// # }
+    /// # let redis_value = json.to_string();
+    /// let payload = DashboardUpdatePayload::from_json_str(&redis_value, "external_apis")?;
+    /// assert_eq!(payload.data.btc_price_usd, 96000.0);
+    /// # Ok::<(), serde_json::Error>(())
     /// ```
     pub fn from_json_str(json: &str, source: &str) -> Result<Self, serde_json::Error> {
         let data = DashboardData::from_json_str(json)?;
@@ -328,7 +868,34 @@ impl DashboardUpdatePayload {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryPayload {
+    /// Recent dashboards, oldest first
+    pub entries: Vec<DashboardData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardDeltaPayload {
+    /// Changed fields only, keyed by camelCase `DashboardData` field name
+    pub changes: serde_json::Map<String, Value>,
+
+    /// Timestamp (RFC3339 format)
+    pub timestamp: String,
+}
+
+impl DashboardDeltaPayload {
+    /// Create a delta payload from a set of changed fields
+    pub fn new(changes: serde_json::Map<String, Value>) -> Self {
+        Self {
+            changes,
+            timestamp: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SystemHealthPayload {
     /// Overall system status
@@ -342,7 +909,40 @@ pub struct SystemHealthPayload {
     pub timestamp: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NoticePayload {
+    /// Severity, used by clients to pick how to display the notice
+    pub level: NoticeLevel,
+
+    /// Text shown to users
+    pub message: String,
+
+    /// Unix timestamp
+    #[serde(default = "now_timestamp")]
+    #[schemars(skip_serializing_if = "omit_schema_default")]
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NoticeLevel {
+    Info,
+    Warning,
+    Critical,
+}
+
+fn now_timestamp() -> i64 {
+    Utc::now().timestamp()
+}
+
+/// Keeps a generated default (e.g. `now_timestamp`) out of the JSON Schema, which would
+/// otherwise record the time the schema was written
+fn omit_schema_default<T>(_: &T) -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum HealthStatus {
     Healthy,
@@ -350,7 +950,7 @@ pub enum HealthStatus {
     Unhealthy,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LayerHealth {
     /// Layer 1: Infrastructure (cache, coordination)
@@ -363,7 +963,7 @@ pub struct LayerHealth {
     pub websocket: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ErrorPayload {
     /// Error code (use ERROR_CODE_* constants)
@@ -374,9 +974,40 @@ pub struct ErrorPayload {
 
     /// Unix timestamp
     pub timestamp: i64,
+
+    /// Id of the connection the error was sent to (the `conn_id` in server logs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_id: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl ErrorPayload {
+    /// Create an error payload timestamped now
+    pub fn new(code: &str, message: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.to_string(),
+            timestamp: Utc::now().timestamp(),
+            connection_id: None,
+        }
+    }
+
+    /// Tag the error with the connection it is sent to
+    pub fn for_connection(mut self, connection_id: impl ToString) -> Self {
+        self.connection_id = Some(connection_id.to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconnectPayload {
+    /// Wait at least this long before reconnecting (grows with server load)
+    pub after_ms: u64,
+    /// Add a random delay between 0 and this, so clients don't reconnect in lockstep
+    pub jitter_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AckPayload {
     /// Action that was acknowledged ("subscribe" or "unsubscribe")
@@ -389,6 +1020,24 @@ pub struct AckPayload {
     pub timestamp: i64,
 }
 
+/// Dashboard JSON (aggregator snake_case) with every required field zeroed, for tests
+#[cfg(test)]
+pub(crate) fn zeroed_dashboard_json() -> Value {
+    let mut json = serde_json::json!({
+        "us_stock_indices": {}, "partial_failure": false, "last_updated": "", "timestamp": "",
+    });
+    for field in [
+        "btc_price_usd", "btc_change_24h", "btc_market_cap_percentage", "eth_price_usd", "eth_change_24h",
+        "eth_market_cap_percentage", "sol_price_usd", "sol_change_24h", "xrp_price_usd", "xrp_change_24h",
+        "ada_price_usd", "ada_change_24h", "link_price_usd", "link_change_24h", "bnb_price_usd",
+        "bnb_change_24h", "market_cap_usd", "volume_24h_usd", "market_cap_change_percentage_24h_usd",
+        "fetch_duration_ms",
+    ] {
+        json[field] = serde_json::json!(0);
+    }
+    json
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -401,6 +1050,9 @@ mod tests {
     fn test_client_message_subscribe_serialization() {
         let msg = ClientMessage::Subscribe(SubscribePayload {
             topics: vec!["BTC".to_string(), "ETH".to_string()],
+            delta: None,
+            batch: None,
+            case: None,
         });
 
         let json = serde_json::to_string(&msg).unwrap();
@@ -436,16 +1088,40 @@ mod tests {
         assert!(json.contains(r#""type":"Error"#));
         assert!(json.contains(ERROR_CODE_INVALID_TOPIC));
         assert!(json.contains("Invalid topic"));
+        assert!(!json.contains("connectionId"));
+
+        let json = ServerMessage::new_connection_error(42, ERROR_CODE_RATE_LIMITED, "Slow down").to_json_string().unwrap();
+        assert!(json.contains(r#""connectionId":"42""#));
     }
 
     #[test]
     fn test_server_message_welcome() {
-        let msg = ServerMessage::new_welcome("conn-123".to_string(), "1.0.0");
+        let msg = ServerMessage::new_welcome("conn-123".to_string(), "1.0.0", Some("tok".to_string()), vec!["dashboard".to_string(), "BTC".to_string()]);
         let json = msg.to_json_string().unwrap();
 
         assert!(json.contains(r#""type":"Welcome"#));
         assert!(json.contains("conn-123"));
         assert!(json.contains("1.0.0"));
+        assert!(json.contains(r#""protocolVersion":1"#));
+        assert!(json.contains(r#""supportedMessages":["Welcome""#));
+        assert!(json.contains(r#""resumeToken":"tok""#));
+        assert!(json.contains(r#""availableTopics":["dashboard","BTC"]"#));
+
+        let compact = ServerMessage::new_welcome("conn-123".to_string(), "1.0.0", None, Vec::new());
+        assert!(!compact.to_json_string().unwrap().contains("availableTopics"));
+    }
+
+    #[test]
+    fn test_hello_grants_only_available_features() {
+        let json = r#"{"type":"Hello","payload":{"clientVersion":"2.1.0","requestedFeatures":["delta","msgpack","zstd"]}}"#;
+        let ClientMessage::Hello(hello) = ClientMessage::from_json_str(json).unwrap() else {
+            panic!("Expected Hello variant");
+        };
+
+        match ServerMessage::new_capabilities(&hello.requested_features) {
+            ServerMessage::Capabilities(caps) => assert_eq!(caps.features, vec![FEATURE_DELTA, FEATURE_MSGPACK]),
+            _ => panic!("Expected Capabilities variant"),
+        }
     }
 
     #[test]
@@ -463,6 +1139,32 @@ mod tests {
         assert!(json.contains("50000"));
     }
 
+    #[test]
+    fn test_batch_round_trip_is_flat() {
+        let update = |symbol: &str| ServerMessage::MarketUpdate(MarketUpdatePayload {
+            symbol: symbol.to_string(),
+            price: 1.5,
+            change_24h: -0.5,
+            volume: None,
+            timestamp: 1234567890,
+        });
+        let batch = ServerMessage::new_batch(vec![
+            update("BTC"),
+            ServerMessage::Batch(vec![update("ETH"), ServerMessage::Batch(vec![update("SOL")])]),
+        ]);
+
+        let json = batch.to_json_string().unwrap();
+        assert!(json.starts_with(r#"{"type":"Batch","payload":[{"type":"MarketUpdate""#));
+        let ServerMessage::Batch(messages) = serde_json::from_str(&json).unwrap() else {
+            panic!("Expected Batch variant");
+        };
+        let symbols: Vec<_> = messages.iter().map(|message| match message {
+            ServerMessage::MarketUpdate(update) => update.symbol.as_str(),
+            other => panic!("Unexpected message in batch: {:?}", other),
+        }).collect();
+        assert_eq!(symbols, vec!["BTC", "ETH", "SOL"]);
+    }
+
     #[test]
     fn test_dashboard_data_from_redis_json() {
         // This is the actual JSON structure from Redis stream
@@ -488,9 +1190,11 @@ mod tests {
             "volume_24h_usd": 208615359377.3596,
             "market_cap_change_percentage_24h_usd": 0.8706429089114247,
             "fng_value": 10,
+            "fng_classification": "Extreme Fear",
             "us_stock_indices": {},
             "fetch_duration_ms": 114,
             "partial_failure": false,
+            "sources": {"crypto": {"source": "binance_ws", "ok": true}, "rsi": {"source": null, "ok": false}},
             "last_updated": "2025-11-15T13:45:35.496238881+00:00",
             "timestamp": "2025-11-15T13:45:35.496253484+00:00"
         }"#;
@@ -500,13 +1204,103 @@ mod tests {
 
         // Verify key fields
         assert_eq!(dashboard_data.btc_price_usd, 96062.47);
-        assert_eq!(dashboard_data.fng_value, 10);
+        assert_eq!(dashboard_data.fng_value, Some(10));
+        assert_eq!(dashboard_data.fng_classification.as_deref(), Some("Extreme Fear"));
         assert_eq!(dashboard_data.eth_price_usd, 3177.25);
 
         // Serialize back to JSON (should be camelCase for frontend)
         let json = dashboard_data.to_json_string().unwrap();
         assert!(json.contains("btcPriceUsd")); // camelCase
         assert!(json.contains("96062.47"));
+        assert!(json.contains(r#""fngClassification":"Extreme Fear""#));
+        assert!(json.contains(r#""sources":{"crypto":{"source":"binance_ws","ok":true},"rsi":{"source":null,"ok":false}}"#));
+    }
+
+    const SAMPLE_DASHBOARD_JSON: &str = r#"{
+        "btc_price_usd": 96062.47, "btc_change_24h": 1.475, "btc_rsi_14": 33.5,
+        "btc_market_cap_percentage": 57.2,
+        "eth_price_usd": 3177.25, "eth_change_24h": -2.95, "eth_market_cap_percentage": 11.4,
+        "sol_price_usd": 141.15, "sol_change_24h": 3.24,
+        "xrp_price_usd": 2.2593, "xrp_change_24h": 0.0,
+        "ada_price_usd": 0.5071, "ada_change_24h": -0.795,
+        "link_price_usd": 14.2, "link_change_24h": 1.646,
+        "bnb_price_usd": 935.51, "bnb_change_24h": 4.13,
+        "market_cap_usd": 3334519158862.68, "volume_24h_usd": 208615359377.36,
+        "market_cap_change_percentage_24h_usd": 0.87,
+        "us_stock_indices": {}, "fetch_duration_ms": 114, "partial_failure": false,
+        "last_updated": "2025-11-15T13:45:35+00:00", "timestamp": "2025-11-15T13:45:35+00:00"
+    }"#;
+
+    #[test]
+    fn test_market_statistics() {
+        let mut dashboard = DashboardData::from_json_str(SAMPLE_DASHBOARD_JSON).unwrap();
+
+        let stats = dashboard.statistics();
+        assert_eq!(stats.total_market_cap_usd, 3334519158862.68);
+        assert_eq!(stats.total_market_cap_change_24h, 0.87);
+        // BTC (57.2% of the market, +1.475%) and ETH (11.4%, -2.95%)
+        let expected = (57.2 * 1.475 + 11.4 * -2.95) / (57.2 + 11.4);
+        assert!((stats.market_cap_weighted_change_24h.unwrap() - expected).abs() < 1e-9);
+        assert_eq!(stats.weighted_coins, vec!["BTC", "ETH"]);
+        assert_eq!((stats.coins_up, stats.coins_down, stats.coins_unchanged), (4, 2, 1));
+        assert_eq!(stats.average_rsi_14, Some(33.5));
+        assert!(serde_json::to_string(&stats).unwrap().contains(r#""coinsUp":4"#));
+
+        dashboard.btc_rsi_14 = None;
+        assert_eq!(dashboard.statistics().average_rsi_14, None);
+
+        dashboard.eth_market_cap_percentage = None;
+        assert_eq!(dashboard.statistics().market_cap_weighted_change_24h, Some(1.475));
+        dashboard.market_cap_usd = 0.0;
+        assert_eq!(dashboard.statistics().market_cap_weighted_change_24h, None);
+    }
+
+    #[test]
+    fn test_invalid_fields() {
+        let mut dashboard = DashboardData::from_json_str(SAMPLE_DASHBOARD_JSON).unwrap();
+        assert!(dashboard.invalid_fields().is_empty());
+
+        dashboard.eth_price_usd = f64::NAN;
+        dashboard.btc_rsi_14 = Some(140.0);
+        dashboard.btc_price_usd = 0.0;
+        assert_eq!(dashboard.invalid_fields(), ["btc_price_usd=0", "eth_price_usd=NaN", "btc_rsi_14=140"]);
+
+        // Zero-filled prices are expected once the crypto group has failed
+        dashboard.critical_failure = true;
+        assert_eq!(dashboard.invalid_fields(), ["eth_price_usd=NaN", "btc_rsi_14=140"]);
+    }
+
+    #[test]
+    fn test_us_stock_indices_typed() {
+        let empty: UsStockIndices = serde_json::from_str("{}").unwrap();
+        assert!(empty.is_empty());
+
+        let indices: UsStockIndices = serde_json::from_value(serde_json::json!({
+            "SPY": {
+                "symbol": "SPY", "name": "SPDR S&P 500 ETF Trust",
+                "price": 512.3, "change": -1.2, "change_percent": -0.23, "status": "success",
+            },
+        })).unwrap();
+        let spy = indices.get("SPY").unwrap();
+        assert_eq!(spy.price, 512.3);
+        assert_eq!(spy.status, "success");
+        assert_eq!(serde_json::to_value(&indices).unwrap()["SPY"]["change_percent"], serde_json::json!(-0.23));
+    }
+
+    #[test]
+    fn test_dashboard_data_missing_indicators_are_null() {
+        let mut json = zeroed_dashboard_json();
+        json["partial_failure"] = serde_json::json!(true);
+        json["fng_value"] = Value::Null;
+
+        // null and absent both mean the fetch failed
+        let dashboard_data: DashboardData = serde_json::from_value(json).unwrap();
+        assert_eq!(dashboard_data.fng_value, None);
+        assert_eq!(dashboard_data.btc_rsi_14, None);
+
+        let json = dashboard_data.to_json_string().unwrap();
+        assert!(json.contains(r#""fngValue":null"#));
+        assert!(json.contains(r#""btcRsi14":null"#));
     }
 
     #[test]
@@ -536,6 +1330,9 @@ mod tests {
             "us_stock_indices": {},
             "fetch_duration_ms": 114,
             "partial_failure": false,
+            "origin": "cache",
+            "age_ms": 1500,
+            "timings": { "crypto_ms": 40, "global_ms": 95, "fng_ms": 0, "rsi_ms": 0, "indices_ms": 110 },
             "last_updated": "2025-11-15T13:45:35.496238881+00:00",
             "timestamp": "2025-11-15T13:45:35.496253484+00:00"
         }"#;
@@ -547,6 +1344,11 @@ mod tests {
         assert_eq!(payload.source, "external_apis");
         assert_eq!(payload.data.btc_price_usd, 96062.47);
 
+        // Freshness survives the typed round trip
+        let round_trip = DashboardData::from_json_str(&payload.data.to_json_string().unwrap()).unwrap();
+        assert_eq!((round_trip.origin.as_str(), round_trip.age_ms), ("cache", 1500));
+        assert_eq!((round_trip.timings.global_ms, round_trip.timings.indices_ms), (95, 110));
+
         // Wrap in ServerMessage and serialize
         let msg = ServerMessage::DashboardUpdate(payload);
         let json = msg.to_json_string().unwrap();
@@ -555,4 +1357,44 @@ mod tests {
         assert!(json.contains("btcPriceUsd"));
         assert!(json.contains("external_apis"));
     }
+
+    #[test]
+    fn test_dashboard_data_diff_only_changed_fields() {
+        let json = r#"{
+            "btc_price_usd": 96062.47, "btc_change_24h": 1.475, "btc_market_cap_percentage": 57.2,
+            "btc_rsi_14": 33.4, "eth_price_usd": 3177.25, "eth_change_24h": 2.95,
+            "eth_market_cap_percentage": 11.4, "sol_price_usd": 141.15, "sol_change_24h": 3.24,
+            "xrp_price_usd": 2.2593, "xrp_change_24h": 0.071, "ada_price_usd": 0.5071,
+            "ada_change_24h": 0.795, "link_price_usd": 14.2, "link_change_24h": 1.646,
+            "bnb_price_usd": 935.51, "bnb_change_24h": 4.13, "market_cap_usd": 3334519158862.682,
+            "volume_24h_usd": 208615359377.3596, "market_cap_change_percentage_24h_usd": 0.87,
+            "fng_value": 10, "us_stock_indices": {}, "fetch_duration_ms": 114,
+            "partial_failure": false, "last_updated": "2025-11-15T13:45:35+00:00",
+            "timestamp": "2025-11-15T13:45:35+00:00"
+        }"#;
+        let previous = DashboardData::from_json_str(json).unwrap();
+        let mut current = previous.clone();
+        current.btc_price_usd = 96100.0;
+
+        let changes = current.diff(&previous).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes["btcPriceUsd"], serde_json::json!(96100.0));
+
+        let msg = ServerMessage::DashboardDelta(DashboardDeltaPayload::new(changes));
+        let json = msg.to_json_string().unwrap();
+        assert!(json.contains(r#""type":"DashboardDelta"#));
+        assert!(!json.contains("ethPriceUsd"));
+    }
+
+    #[test]
+    fn test_notice_body_parsing() {
+        let notice: NoticePayload = serde_json::from_str(r#"{"level":"warning","message":"Market data paused for 30s"}"#).unwrap();
+        assert_eq!(notice.level, NoticeLevel::Warning);
+        assert!(notice.timestamp > 0);
+
+        let json = ServerMessage::Notice(notice).to_json_string().unwrap();
+        assert!(json.contains(r#""type":"Notice","payload":{"level":"warning""#));
+
+        assert!(serde_json::from_str::<NoticePayload>(r#"{"level":"urgent","message":"x"}"#).is_err());
+    }
 }
@@ -1,6 +1,9 @@
 pub mod service_islands;
 pub mod performance;
 pub mod dto;
+pub mod config;
+pub mod admin_auth;
 
 pub use service_islands::ServiceIslands;
+pub use config::Config;
 pub use dto::{ClientMessage, ServerMessage, DashboardData, DashboardUpdatePayload};
@@ -1,88 +1,159 @@
 use dotenvy::dotenv;
-use std::{env, net::SocketAddr, sync::Arc, time::Duration};
+use std::{sync::Arc, time::{Duration, Instant}};
 use axum::{
     Router,
-    routing::get,
-    extract::{ws::{WebSocket, WebSocketUpgrade, Message}, State},
-    response::IntoResponse,
+    routing::{get, post},
+    body::Body,
+    extract::{ws::{rejection::WebSocketUpgradeRejection, WebSocket, WebSocketUpgrade, Message}, Extension, FromRequest, Path, Query, State},
+    response::{IntoResponse, Response},
+    http::{header, HeaderMap, HeaderValue, Method, Request, StatusCode},
 };
-use tokio::{signal, time::interval};
-use tracing::{info, error, warn};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tokio::{signal, sync::broadcast, time::interval};
+use tracing::{info, info_span, error, warn, Instrument};
+use tracing_subscriber::{layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry};
 use anyhow::Context;
+use serde::Deserialize;
 
-mod service_islands;
-mod performance;
+use web_server_report_websocket::{admin_auth, Config, ServerMessage, ServiceIslands};
+use web_server_report_websocket::service_islands::layer1_infrastructure::cache_system_island::stream_consumer::parse_stream_id;
+use web_server_report_websocket::config::AllowedOrigins;
+use web_server_report_websocket::performance;
+use web_server_report_websocket::dto::DashboardData;
+use web_server_report_websocket::dto::websocket::{NoticeLevel, NoticePayload, SubscribePayload, ERROR_CODE_MESSAGE_TOO_BIG, ERROR_CODE_RATE_LIMITED, WS_SUBPROTOCOL};
+use web_server_report_websocket::dto::ClientMessage;
+use web_server_report_websocket::service_islands::layer2_external_services::external_apis_island::api_self_test::check_configured_apis;
+use web_server_report_websocket::service_islands::layer2_external_services::external_apis_island::market_data_api::DASHBOARD_SYMBOLS;
+use web_server_report_websocket::service_islands::layer2_external_services::external_apis_island::api_aggregator::source_switches::DATA_GROUPS;
+use web_server_report_websocket::service_islands::layer3_communication::websocket_service::broadcast_service::{BroadcastMessage, TopicReceivers};
+use web_server_report_websocket::service_islands::layer3_communication::websocket_service::serialization_pool::PreparedMessage;
+use web_server_report_websocket::service_islands::layer3_communication::websocket_service::WebSocketServiceIsland;
+use web_server_report_websocket::service_islands::layer3_communication::grpc_push;
+use web_server_report_websocket::service_islands::layer3_communication::websocket_service::outbound::{send_with_timeout, SendOutcome};
+use web_server_report_websocket::service_islands::layer3_communication::websocket_service::metrics::{frame_len, ConnectionTraffic};
+use web_server_report_websocket::service_islands::layer3_communication::websocket_service::connection_manager::{available_topics, CloseReason, ConnectionSlot, ConnectionState, ReconnectAdvice};
 
-use service_islands::ServiceIslands;
+/// Filter used when `RUST_LOG` is unset or invalid
+const DEFAULT_LOG_FILTER: &str = "web_server_report_websocket=info,tower_http=debug";
+
+/// Swaps the active log filter at runtime (SIGHUP, `/admin/log-level`)
+type LogReloadHandle = reload::Handle<EnvFilter, Registry>;
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     // Initialize environment variables
     dotenv().ok();
 
-    // Initialize tracing
+    // Initialize tracing behind a reload handle so the filter can change without a restart
+    let (filter_layer, log_reload) = reload::Layer::new(env_log_filter());
     tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "web_server_report_websocket=info,tower_http=debug".into()),
-        )
+        .with(filter_layer)
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    #[cfg(unix)]
+    tokio::spawn(reload_log_filter_on_sighup(log_reload.clone()));
+
     info!("🚀 Starting WebSocket Service with Service Islands Architecture...");
 
+    // Load and validate configuration once, failing fast on bad values
+    let config = Config::from_env().context("Invalid configuration")?;
+    let addr = config.socket_addr()?;
+    let grpc_addr = config.grpc_addr()?;
+    performance::configure_http_client(config.http_client_options());
+
+    // `--check-apis`: verify the configured API keys and exit instead of serving
+    if std::env::args().any(|arg| arg == "--check-apis") {
+        return check_apis(&config).await;
+    }
+
     // Initialize Service Islands Architecture
     info!("🏝️ Initializing Service Islands Architecture...");
-    let service_islands = Arc::new(ServiceIslands::initialize().await?);
+    let service_islands = Arc::new(ServiceIslands::initialize(config).await?);
+    service_islands.init_report.log();
+    if service_islands.config.admin_token.is_none() {
+        warn!("⚠️ ADMIN_TOKEN is not set: /stats and /debug/connections are open to anyone who can reach this port");
+    }
+
+    // MAINTENANCE_MODE puts the whole cluster in maintenance, like the admin toggle
+    if service_islands.maintenance_mode() {
+        if let Err(e) = service_islands.set_maintenance_mode(true).await {
+            warn!("⚠️ Failed to share MAINTENANCE_MODE with other instances: {:#}", e);
+        }
+    }
 
     // Perform initial health check
     info!("🔍 Performing initial health check...");
-    let (is_healthy, health_details) = service_islands.health_check_detailed().await;
-    if is_healthy {
+    let health_report = service_islands.health_check_detailed().await;
+    if health_report.healthy {
         info!("✅ Service Islands Architecture is healthy!");
     } else {
         warn!("⚠️ Some Service Islands may have issues - continuing with startup...");
-        warn!("Health details: {:?}", health_details);
+        warn!("Health details: {:?}", health_report);
     }
 
-    // Spawn background task for periodic market data fetching
-    let islands_clone = service_islands.clone();
-    tokio::spawn(async move {
-        spawn_market_data_fetcher(islands_clone).await;
-    });
+    // Spawn background task for periodic market data fetching (stops when shutdown begins)
+    let fetcher = tokio::spawn(spawn_market_data_fetcher(service_islands.clone()));
+
+    // Optional gRPC push of the same dashboards to the main report service (stops when shutdown begins)
+    if let Some(grpc_addr) = grpc_addr {
+        let broadcast_service = Arc::clone(&service_islands.websocket_service.broadcast_service);
+        let mut shutdown = service_islands.subscribe_shutdown();
+        tokio::spawn(async move {
+            let stopped = async move {
+                let _ = shutdown.changed().await;
+            };
+            if let Err(e) = grpc_push::serve(grpc_addr, broadcast_service, stopped).await {
+                error!("❌ gRPC dashboard stream failed: {:#}", e);
+            }
+        });
+    }
 
     // Create router with WebSocket endpoint
-    let app = create_router(service_islands.clone());
+    let app = create_router(service_islands.clone(), log_reload);
 
-    // Start server
-    let host = env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
-    let port: u16 = env::var("PORT")
-        .unwrap_or_else(|_| "8081".to_string())
-        .parse()
-        .context("PORT must be a valid number")?;
+    // Start server (Unix domain socket when BIND_UDS is set, TCP otherwise)
+    match service_islands.config.bind_uds.clone() {
+        Some(path) => serve_unix_socket(&path, app, service_islands.clone()).await?,
+        None => {
+            info!("🌐 WebSocket Service listening on ws://{}", addr);
+            info!("📡 WebSocket endpoint: ws://{}/ws", addr);
 
-    let addr: SocketAddr = format!("{}:{}", host, port)
-        .parse()
-        .context("HOST and PORT must form a valid address")?;
+            // Run server with graceful shutdown
+            axum::Server::bind(&addr)
+                .serve(app.into_make_service())
+                .with_graceful_shutdown(shutdown_signal(service_islands.clone()))
+                .await?;
+        }
+    }
 
-    info!("🌐 WebSocket Service listening on ws://{}", addr);
-    info!("📡 WebSocket endpoint: ws://{}/ws", addr);
+    // Shutdown began with the signal (the server only returns after it); the whole
+    // sequence below shares its SHUTDOWN_TIMEOUT_SECONDS deadline, with the end of it
+    // kept for releasing leadership
+    let deadline = service_islands.begin_shutdown();
+    let drain_deadline = service_islands.shutdown_drain_deadline(deadline);
 
-    // Run server with graceful shutdown
-    let server = axum::Server::bind(&addr)
-        .serve(app.into_make_service())
-        .with_graceful_shutdown(shutdown_signal());
+    // 1. Background tasks were told to stop; let an in-flight fetch/publish finish
+    match tokio::time::timeout_at(drain_deadline, fetcher).await {
+        Ok(Ok(())) => info!("✅ Market data fetcher stopped"),
+        Ok(Err(e)) => warn!("⚠️ Market data fetcher task failed: {}", e),
+        Err(_) => warn!("⚠️ Market data fetcher still busy at the shutdown deadline, shutting down anyway"),
+    }
 
-    // Wait for server to finish
-    server.await?;
+    // 2. Upgraded WebSocket connections outlive the HTTP server: ask them to close (1001)
+    //    and give them until the drain deadline to send their close frames
+    service_islands.websocket_service.connection_manager.begin_drain();
+    while service_islands.active_connections() > 0 && tokio::time::Instant::now() < drain_deadline {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
 
-    // Gracefully release leadership on shutdown
+    // 3. Release leadership once nothing can act as leader anymore; renewals stop first,
+    //    and the lock is released even if the leader flag is stale (ownership is checked in Redis)
     info!("🔓 Releasing leadership before shutdown...");
-    if let Err(e) = service_islands.leader_election.release_leadership().await {
-        warn!("⚠️ Failed to release leadership: {}", e);
-    } else {
-        info!("✅ Leadership released successfully");
+    match tokio::time::timeout_at(deadline, service_islands.release_leadership()).await {
+        Ok(Ok(())) => info!("✅ Leadership released successfully"),
+        Ok(Err(e)) => warn!("⚠️ Failed to release leadership: {}", e),
+        Err(_) => warn!("⚠️ Releasing leadership timed out at the shutdown deadline"),
     }
 
     info!("👋 WebSocket service shutdown complete");
@@ -90,79 +161,558 @@ async fn main() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// Exit the process if graceful shutdown hasn't finished by `deadline`
+///
+/// Backstop for anything the shutdown sequence can't bound itself (e.g. an HTTP
+/// request the server is still waiting on), so a hung task can't block exit forever.
+async fn force_exit_at(deadline: tokio::time::Instant) {
+    tokio::time::sleep_until(deadline).await;
+    error!("❌ Graceful shutdown exceeded SHUTDOWN_TIMEOUT_SECONDS, forcing exit");
+    std::process::exit(1);
+}
+
+/// Serve `app` on a Unix domain socket at `path` until shutdown
+///
+/// A stale socket file left by a previous run is replaced (anything else at `path` is
+/// refused, never deleted); the file is removed on exit.
+#[cfg(unix)]
+async fn serve_unix_socket(path: &std::path::Path, app: Router, service_islands: Arc<ServiceIslands>) -> anyhow::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            std::fs::remove_file(path)
+                .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+            info!("Removed stale socket file {}", path.display());
+        }
+        Ok(_) => anyhow::bail!("BIND_UDS path {} exists and is not a socket; refusing to replace it", path.display()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e).with_context(|| format!("Failed to inspect {}", path.display())),
+    }
+    let listener = tokio::net::UnixListener::bind(path)
+        .with_context(|| format!("Failed to bind Unix socket {}", path.display()))?;
+
+    info!("🌐 WebSocket Service listening on unix:{}", path.display());
+    info!("📡 WebSocket endpoint: /ws over unix:{}", path.display());
+
+    let result = axum::Server::builder(UnixAcceptor { listener })
+        .serve(app.into_make_service())
+        .with_graceful_shutdown(shutdown_signal(service_islands))
+        .await;
+
+    let _ = std::fs::remove_file(path);
+    Ok(result?)
+}
+
+#[cfg(not(unix))]
+async fn serve_unix_socket(_path: &std::path::Path, _app: Router, _service_islands: Arc<ServiceIslands>) -> anyhow::Result<()> {
+    anyhow::bail!("BIND_UDS is only supported on Unix platforms")
+}
+
+/// Adapts a `UnixListener` to hyper's connection acceptor
+#[cfg(unix)]
+struct UnixAcceptor {
+    listener: tokio::net::UnixListener,
+}
+
+#[cfg(unix)]
+impl hyper::server::accept::Accept for UnixAcceptor {
+    type Conn = tokio::net::UnixStream;
+    type Error = std::io::Error;
+
+    fn poll_accept(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Result<Self::Conn, Self::Error>>> {
+        self.listener
+            .poll_accept(cx)
+            .map(|accepted| Some(accepted.map(|(stream, _addr)| stream)))
+    }
+}
+
 /// Create the router with WebSocket endpoint
-fn create_router(service_islands: Arc<ServiceIslands>) -> Router {
+fn create_router(service_islands: Arc<ServiceIslands>, log_reload: LogReloadHandle) -> Router {
+    let cors = cors_layer(&service_islands.config.allowed_origins);
+
     Router::new()
         .route("/ws", get(websocket_handler))
+        .route("/ws/:symbol", get(symbol_websocket_handler))
         .route("/health", get(health_handler))
+        .route("/metrics", get(metrics_handler))
+        .route("/stats", get(stats_handler))
+        .route("/debug/connections", get(debug_connections_handler))
+        .route("/dashboard", get(dashboard_handler))
+        .route("/dashboard/history/:id", get(dashboard_history_handler))
+        .route("/dashboard/statistics", get(dashboard_statistics_handler))
+        .route("/admin/broadcast", post(admin_broadcast_handler))
+        .route("/admin/broadcast/pause", post(admin_broadcast_pause_handler))
+        .route("/admin/broadcast/resume", post(admin_broadcast_resume_handler))
+        .route("/admin/maintenance/enable", post(admin_maintenance_enable_handler))
+        .route("/admin/maintenance/disable", post(admin_maintenance_disable_handler))
+        .route("/admin/log-level", post(admin_log_level_handler))
+        .route("/admin/source/:name/disable", post(admin_source_disable_handler))
+        .route("/admin/source/:name/enable", post(admin_source_enable_handler))
+        .layer(Extension(log_reload))
+        .layer(cors)
         .with_state(service_islands)
 }
 
+/// CORS for the HTTP endpoints, mirroring the WebSocket origin allowlist
+fn cors_layer(allowed_origins: &AllowedOrigins) -> CorsLayer {
+    let allow_origin = match allowed_origins {
+        AllowedOrigins::Any => AllowOrigin::any(),
+        AllowedOrigins::List(origins) => AllowOrigin::list(
+            origins.iter().filter_map(|origin| HeaderValue::from_str(origin).ok()),
+        ),
+    };
+
+    CorsLayer::new()
+        .allow_methods([Method::GET])
+        .allow_origin(allow_origin)
+}
+
+/// Run one request per provider, print a pass/fail table and fail if a required provider failed
+async fn check_apis(config: &Config) -> Result<(), anyhow::Error> {
+    let checks = check_configured_apis(config).await?;
+
+    println!("{:<16} {:<10} {:<6} {:>10}  DETAIL", "PROVIDER", "REQUIRED", "RESULT", "LATENCY");
+    for check in &checks {
+        println!(
+            "{:<16} {:<10} {:<6} {:>8}ms  {}",
+            check.provider,
+            if check.required { "yes" } else { "no" },
+            if check.passed() { "PASS" } else { "FAIL" },
+            check.latency.as_millis(),
+            check.error.as_deref().unwrap_or(""),
+        );
+    }
+
+    let failed: Vec<&str> = checks.iter()
+        .filter(|check| check.required && !check.passed())
+        .map(|check| check.provider)
+        .collect();
+    if !failed.is_empty() {
+        anyhow::bail!("Required API checks failed: {}", failed.join(", "));
+    }
+    Ok(())
+}
+
+/// Query parameters of the WebSocket routes
+#[derive(Debug, Default, Deserialize)]
+struct WebSocketParams {
+    /// `false` leaves `availableTopics` out of `Welcome` (size-sensitive clients)
+    #[serde(default)]
+    welcome_topics: Option<bool>,
+}
+
 /// WebSocket upgrade handler
+///
+/// Rejects browsers whose `Origin` is not in `ALLOWED_ORIGINS` with 403.
+/// Reserves a connection slot before upgrading; at capacity the upgrade is
+/// rejected with 503 + Retry-After instead of accepting and closing.
+/// Confirms the `market-data-v1` subprotocol when the client asks for it;
+/// clients requesting only other protocols still connect (without one).
 async fn websocket_handler(
-    ws: WebSocketUpgrade,
+    ws: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
+    Query(params): Query<WebSocketParams>,
+    headers: HeaderMap,
     State(service_islands): State<Arc<ServiceIslands>>,
-) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_websocket(socket, service_islands))
+) -> Response {
+    upgrade_websocket(ws, &headers, service_islands, None, params)
 }
 
-/// Handle individual WebSocket connection
-async fn handle_websocket(mut socket: WebSocket, service_islands: Arc<ServiceIslands>) {
-    use std::sync::atomic::Ordering;
+/// Single-symbol WebSocket for embeds, e.g. `/ws/BTC`
+///
+/// Same as `/ws`, but the connection starts subscribed to the symbol and so receives
+/// its `MarketUpdate`s instead of full dashboards. Unknown symbols get 404.
+async fn symbol_websocket_handler(
+    ws: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
+    Path(symbol): Path<String>,
+    Query(params): Query<WebSocketParams>,
+    headers: HeaderMap,
+    State(service_islands): State<Arc<ServiceIslands>>,
+) -> Response {
+    let symbol = symbol.to_uppercase();
+    if !DASHBOARD_SYMBOLS.contains(&symbol.as_str()) {
+        return (StatusCode::NOT_FOUND, format!("Unknown symbol '{}'", symbol)).into_response();
+    }
+
+    upgrade_websocket(ws, &headers, service_islands, Some(symbol), params)
+}
+
+/// Header, origin, subprotocol and capacity checks shared by the WebSocket routes
+///
+/// Every error response counts as a rejected upgrade on `/metrics`; handshakes that
+/// fail after the 101 count as failed, completed ones as accepted.
+fn upgrade_websocket(
+    ws: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
+    headers: &HeaderMap,
+    service_islands: Arc<ServiceIslands>,
+    initial_topic: Option<String>,
+    params: WebSocketParams,
+) -> Response {
+    let metrics = service_islands.websocket_service.metrics.clone();
+    let ws = match ws {
+        Ok(ws) => ws,
+        Err(rejection) => {
+            metrics.record_upgrade_rejected();
+            return upgrade_rejection_response(headers, rejection);
+        }
+    };
+
+    let origin = headers.get(header::ORIGIN).and_then(|v| v.to_str().ok());
+    if !service_islands.config.allowed_origins.allows(origin) {
+        warn!("🚫 Rejecting WebSocket upgrade from disallowed origin {:?}", origin);
+        metrics.record_upgrade_rejected();
+        return (StatusCode::FORBIDDEN, "Origin not allowed").into_response();
+    }
+
+    let requested_protocols: Vec<&str> = headers
+        .get(header::SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(',').map(str::trim).filter(|p| !p.is_empty()).collect())
+        .unwrap_or_default();
+    if !requested_protocols.is_empty() && !requested_protocols.contains(&WS_SUBPROTOCOL) {
+        info!("Client requested unsupported WebSocket subprotocol(s) {:?}, connecting without one",
+              requested_protocols);
+    }
+
+    let Some(slot) = service_islands.try_reserve_connection() else {
+        warn!("🚫 Rejecting WebSocket upgrade: connection limit ({}) reached",
+              service_islands.max_ws_connections);
+        metrics.record_upgrade_rejected();
+        let advice = ReconnectAdvice::for_load(service_islands.active_connections(), service_islands.max_ws_connections);
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, advice.retry_after_secs().to_string())],
+            "Too many WebSocket connections, retry later",
+        ).into_response();
+    };
+
+    let max_message_bytes = service_islands.config.ws_max_message_bytes;
+    ws.protocols([WS_SUBPROTOCOL])
+        .max_message_size(max_message_bytes)
+        .max_frame_size(max_message_bytes)
+        .on_failed_upgrade({
+            let metrics = metrics.clone();
+            move |error| {
+                warn!("⚠️ WebSocket handshake failed after upgrade response: {}", error);
+                metrics.record_upgrade_failed();
+            }
+        })
+        .on_upgrade(move |socket| {
+            metrics.record_upgrade_accepted();
+            handle_websocket(socket, service_islands, slot, initial_topic, params)
+                .instrument(info_span!("ws", conn_id = tracing::field::Empty))
+        })
+        .into_response()
+}
+
+/// 400 explaining why a request to a WebSocket route couldn't be upgraded
+///
+/// Usually a reverse proxy that doesn't forward `Upgrade`/`Connection`, so the
+/// headers that matter are logged alongside the reason.
+fn upgrade_rejection_response(headers: &HeaderMap, rejection: WebSocketUpgradeRejection) -> Response {
+    let header_value = |name: header::HeaderName| headers.get(name).and_then(|v| v.to_str().ok()).unwrap_or("<missing>");
+    let reason = rejection.body_text();
+    warn!("🚫 Rejecting WebSocket upgrade: {} (Upgrade: {}, Connection: {}, Sec-WebSocket-Version: {})",
+          reason, header_value(header::UPGRADE), header_value(header::CONNECTION),
+          header_value(header::SEC_WEBSOCKET_VERSION));
+
+    (
+        StatusCode::BAD_REQUEST,
+        format!("WebSocket upgrade failed: {}. If a reverse proxy sits in front of this server, \
+                 make sure it forwards the `Upgrade` and `Connection` headers.", reason),
+    ).into_response()
+}
 
-    // Increment connection counter
-    service_islands.active_ws_connections.fetch_add(1, Ordering::SeqCst);
+/// Handle individual WebSocket connection
+///
+/// `slot` keeps this connection counted until the function returns. With an
+/// `initial_topic` the connection is subscribed to it right after `Welcome`.
+async fn handle_websocket(
+    mut socket: WebSocket,
+    service_islands: Arc<ServiceIslands>,
+    slot: ConnectionSlot,
+    initial_topic: Option<String>,
+    params: WebSocketParams,
+) {
     let current_connections = service_islands.active_connections();
     info!("➕ New WebSocket connection (total: {})", current_connections);
 
-    // Subscribe to broadcast channel
-    let mut rx = service_islands.websocket_service.broadcast_service.subscribe();
+    let websocket_service = &service_islands.websocket_service;
+    let conn_id = websocket_service.connection_manager.register().await;
+    // Every log line of this connection carries `conn_id`, matching `connectionId` in its errors
+    tracing::Span::current().record("conn_id", conn_id);
+    let traffic = websocket_service.connection_manager
+        .state(conn_id)
+        .await
+        .map(|state| state.traffic)
+        .unwrap_or_default();
+    let message_handler = &websocket_service.message_handler;
+
+    // Topic channels for the client's subscriptions (none if its topics don't need
+    // dashboards) plus the system channel every connection listens on
+    let mut rx = message_handler.receivers_for(conn_id).await;
+    let mut system_rx = message_handler.system_receiver();
+    // Local copy of this connection's subscription state, refreshed after each client message
+    let mut conn_state = ConnectionState::default();
+    let mut drain_rx = websocket_service.connection_manager.subscribe_drain();
+    let mut slow_client = websocket_service.slow_client_detector();
+    let mut rate_limiter = websocket_service.client_rate_limiter();
 
-    // Send initial message
-    if socket.send(Message::Text("Connected to WebSocket service".to_string())).await.is_err() {
+    // Send initial message (protocol version and supported messages; clients may follow up with Hello)
+    let resume_token = websocket_service.connection_manager.resume_token(conn_id).await;
+    let topics = if params.welcome_topics.unwrap_or(true) { available_topics() } else { Vec::new() };
+    let welcome = ServerMessage::new_welcome(conn_id.to_string(), env!("CARGO_PKG_VERSION"), resume_token, topics);
+    if !send_all(&mut socket, websocket_service, &traffic, &conn_state, vec![welcome]).await {
         info!("Failed to send initial message");
+        websocket_service.connection_manager.unregister(conn_id).await;
         return;
     }
 
-    // Handle incoming messages and broadcasts
-    loop {
+    // During maintenance nothing is broadcast: show the notice and the last good dashboard now
+    if service_islands.maintenance_mode() {
+        let greeting = service_islands.maintenance_greeting().await;
+        if !send_all(&mut socket, websocket_service, &traffic, &conn_state, greeting).await {
+            websocket_service.connection_manager.unregister(conn_id).await;
+            return;
+        }
+    }
+
+    // Unchanged dashboards aren't rebroadcast: start the client off with the latest one
+    if !service_islands.maintenance_mode() && initial_topic.is_none() {
+        if let Some(latest) = websocket_service.broadcast_service.latest_dashboard().await {
+            if !send_prepared(&mut socket, websocket_service, &traffic, &conn_state, &latest).await {
+                websocket_service.connection_manager.unregister(conn_id).await;
+                return;
+            }
+        }
+    }
+
+    if let Some(topic) = initial_topic {
+        let subscribe = ClientMessage::Subscribe(SubscribePayload { topics: vec![topic], delta: None, batch: None, case: None });
+        let responses = message_handler.handle(conn_id, subscribe).await;
+        conn_state = websocket_service.connection_manager.state(conn_id).await.unwrap_or_default();
+        rx = message_handler.receivers_for(conn_id).await;
+        if !send_all(&mut socket, websocket_service, &traffic, &conn_state, responses).await {
+            websocket_service.connection_manager.unregister(conn_id).await;
+            return;
+        }
+    }
+
+    // Handle incoming messages and broadcasts; Some(reason) when the server ends the connection
+    let close_reason = loop {
         tokio::select! {
             // Receive broadcast messages
-            msg = rx.recv() => {
+            msg = next_broadcast(&mut rx, &mut system_rx) => {
                 match msg {
-                    Ok(text) => {
-                        if socket.send(Message::Text(text)).await.is_err() {
-                            break;
+                    Ok(prepared) => {
+                        let socket = &mut socket;
+                        let (sent, took) = websocket_service.send_broadcast(&conn_state, &prepared, |framed| async {
+                            let started = Instant::now();
+                            (send_framed(socket, websocket_service, &traffic, framed).await, started.elapsed())
+                        }).await;
+                        if !sent {
+                            break None;
+                        }
+                        if slow_client.record_send(took) {
+                            warn!(strikes = slow_client.strikes(), "Disconnecting slow WebSocket client (sends blocking)");
+                            break Some(CloseReason::PolicyViolation);
                         }
                     }
-                    Err(_) => break,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        if slow_client.record_lag(skipped) {
+                            warn!(strikes = slow_client.strikes(), "Disconnecting slow WebSocket client ({} messages dropped)", skipped);
+                            break Some(CloseReason::PolicyViolation);
+                        }
+                        warn!("WebSocket client lagging, {} messages dropped", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break Some(CloseReason::Normal),
                 }
             }
-            // Receive client messages (we just ignore them for now)
-            Some(msg) = socket.recv() => {
-                if msg.is_err() {
-                    break;
+            // Server is draining connections
+            _ = drain_rx.changed() => break Some(CloseReason::GoingAway),
+            // Receive client messages
+            msg = socket.recv() => {
+                if let Some(Ok(frame)) = &msg {
+                    websocket_service.metrics.record_received(&traffic, frame_len(frame));
+                }
+                match msg {
+                    Some(Ok(Message::Text(text))) => {
+                        if !rate_limiter.allow() {
+                            let error = ServerMessage::new_connection_error(conn_id, ERROR_CODE_RATE_LIMITED, "Too many messages, slow down");
+                            if !send_all(&mut socket, websocket_service, &traffic, &conn_state, vec![error]).await {
+                                break None;
+                            }
+                            continue;
+                        }
+
+                        let responses = message_handler.handle_text(conn_id, &text).await;
+                        if !send_all(&mut socket, websocket_service, &traffic, &conn_state, responses).await {
+                            break None;
+                        }
+
+                        // Switch topic channels if the client's subscriptions or modes changed them
+                        let state = websocket_service.connection_manager
+                            .state(conn_id)
+                            .await
+                            .unwrap_or_default();
+                        let channels_changed = state.broadcast_topics() != conn_state.broadcast_topics();
+                        let delta_enabled = state.delta && !conn_state.delta;
+                        conn_state = state;
+                        if channels_changed {
+                            rx = message_handler.receivers_for(conn_id).await;
+                        }
+
+                        // Deltas only apply on top of a full dashboard: send the one they are diffed against
+                        if delta_enabled {
+                            if let Some(baseline) = websocket_service.broadcast_service.latest_dashboard().await {
+                                if !send_prepared(&mut socket, websocket_service, &traffic, &conn_state, &baseline).await {
+                                    break None;
+                                }
+                            }
+                        }
+                    }
+                    Some(Err(e)) => {
+                        if !is_message_too_big(e) {
+                            break None;
+                        }
+                        warn!("Closing WebSocket: client message exceeds {} bytes",
+                              service_islands.config.ws_max_message_bytes);
+                        // The oversized frame left the stream unusable; explain, then close with 1009
+                        let error = ServerMessage::new_connection_error(
+                            conn_id,
+                            ERROR_CODE_MESSAGE_TOO_BIG,
+                            &format!("Message exceeds {} bytes", service_islands.config.ws_max_message_bytes),
+                        );
+                        let _ = send_all(&mut socket, websocket_service, &traffic, &conn_state, vec![error]).await;
+                        break Some(CloseReason::MessageTooBig);
+                    }
+                    Some(Ok(Message::Close(_))) | None => break None,
+                    Some(Ok(_)) => {}
                 }
             }
         }
+    };
+
+    if let Some(reason) = close_reason {
+        if reason.suggests_reconnect() {
+            let advice = ReconnectAdvice::for_load(service_islands.active_connections(), service_islands.max_ws_connections);
+            let reconnect = ServerMessage::new_reconnect(advice.after_ms, advice.jitter_ms);
+            let _ = send_all(&mut socket, websocket_service, &traffic, &conn_state, vec![reconnect]).await;
+        }
+        let _ = send_frame(&mut socket, websocket_service, &traffic, Message::Close(Some(reason.close_frame()))).await;
     }
 
-    // Decrement connection counter
-    service_islands.active_ws_connections.fetch_sub(1, Ordering::SeqCst);
+    // Release the connection slot
+    websocket_service.connection_manager.unregister(conn_id).await;
+    drop(slot);
     let current_connections = service_islands.active_connections();
     info!("➖ WebSocket connection closed (total: {})", current_connections);
 }
 
+/// Whether a read failed because the client exceeded `WS_MAX_MESSAGE_BYTES`
+fn is_message_too_big(error: axum::Error) -> bool {
+    error
+        .into_inner()
+        .downcast::<tokio_tungstenite::tungstenite::Error>()
+        .is_ok_and(|e| matches!(*e, tokio_tungstenite::tungstenite::Error::Capacity(_)))
+}
+
+/// Next message from the connection's topic channels or the system channel
+async fn next_broadcast(
+    topics: &mut TopicReceivers,
+    system: &mut broadcast::Receiver<BroadcastMessage>,
+) -> Result<BroadcastMessage, broadcast::error::RecvError> {
+    tokio::select! {
+        msg = topics.recv() => msg,
+        msg = system.recv() => msg,
+    }
+}
+
+/// Send one frame within `WS_SEND_TIMEOUT_MS`; false once the socket is gone or stuck
+async fn send_frame(
+    socket: &mut WebSocket,
+    websocket_service: &WebSocketServiceIsland,
+    traffic: &ConnectionTraffic,
+    message: Message,
+) -> bool {
+    let len = frame_len(&message);
+    match send_with_timeout(socket, message, websocket_service.send_timeout).await {
+        SendOutcome::Sent => {
+            websocket_service.metrics.record_sent(traffic, len);
+            true
+        }
+        SendOutcome::Closed => false,
+        SendOutcome::TimedOut => {
+            websocket_service.metrics.record_send_timeout();
+            warn!("Dropping WebSocket client: send blocked for over {:?}", websocket_service.send_timeout);
+            false
+        }
+    }
+}
+
+/// Send a broadcast message in the connection's format; false once the socket is gone or stuck
+async fn send_prepared(
+    socket: &mut WebSocket,
+    websocket_service: &WebSocketServiceIsland,
+    traffic: &ConnectionTraffic,
+    conn_state: &ConnectionState,
+    prepared: &PreparedMessage,
+) -> bool {
+    send_framed(socket, websocket_service, traffic, conn_state.frame_for(prepared)).await
+}
+
+/// Send a frame from `ConnectionState::frame_for`, counting its message type; false once the socket is gone or stuck
+async fn send_framed(
+    socket: &mut WebSocket,
+    websocket_service: &WebSocketServiceIsland,
+    traffic: &ConnectionTraffic,
+    (frame, message_type): (Message, Option<&'static str>),
+) -> bool {
+    if !send_frame(socket, websocket_service, traffic, frame).await {
+        return false;
+    }
+    if let Some(message_type) = message_type {
+        websocket_service.message_stats.record_sent(message_type);
+    }
+    true
+}
+
+/// Send handler responses in order, in the connection's key casing; false once the socket is gone or stuck
+async fn send_all(
+    socket: &mut WebSocket,
+    websocket_service: &WebSocketServiceIsland,
+    traffic: &ConnectionTraffic,
+    conn_state: &ConnectionState,
+    responses: Vec<ServerMessage>,
+) -> bool {
+    for response in responses {
+        match websocket_service.encode_for(conn_state, &response) {
+            Ok(text) => {
+                if !send_frame(socket, websocket_service, traffic, Message::Text(text)).await {
+                    return false;
+                }
+                websocket_service.message_stats.record_sent(response.type_name());
+            }
+            Err(e) => warn!("Failed to serialize server message: {}", e),
+        }
+    }
+    true
+}
+
 /// Health check endpoint
 /// Returns OK (200) if core services are healthy (cache, websocket)
 /// External APIs being down won't fail the health check
 async fn health_handler(
     State(service_islands): State<Arc<ServiceIslands>>,
 ) -> impl IntoResponse {
-    let (is_healthy, health_details) = service_islands.health_check_detailed().await;
+    let health_report = service_islands.health_check_detailed().await;
+    let connection_ages = service_islands.websocket_service.connection_manager.connection_ages().await;
 
-    let status = if is_healthy { "healthy" } else { "unhealthy" };
-    let status_code = if is_healthy {
+    let status = if health_report.healthy { "healthy" } else { "unhealthy" };
+    let status_code = if health_report.healthy {
         axum::http::StatusCode::OK
     } else {
         axum::http::StatusCode::SERVICE_UNAVAILABLE
@@ -174,91 +724,560 @@ async fn health_handler(
             "status": status,
             "service": "web-server-report-websocket",
             "active_connections": service_islands.active_connections(),
-            "details": health_details,
+            "uptime_seconds": service_islands.uptime().as_secs(),
+            "oldest_connection_age_seconds": connection_ages.map(|(oldest, _)| oldest.as_secs()),
+            "newest_connection_age_seconds": connection_ages.map(|(_, newest)| newest.as_secs()),
+            "features": service_islands.init_report,
+            "details": health_report,
         }))
     )
 }
 
+/// Snapshot endpoint: the latest dashboard (camelCase, like WebSocket updates)
+///
+/// Concurrent requests on a cold cache share one refresh; 503 while no data exists yet.
+async fn dashboard_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+) -> Response {
+    match service_islands.dashboard_snapshot().await {
+        Ok(Some(data)) => match DashboardData::deserialize(&data) {
+            Ok(dashboard) => axum::Json(dashboard).into_response(),
+            Err(e) => {
+                error!("❌ Cached dashboard has an unexpected shape: {}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, error_body("Invalid dashboard data")).into_response()
+            }
+        },
+        Ok(None) => (StatusCode::SERVICE_UNAVAILABLE, error_body("No market data available yet")).into_response(),
+        Err(e) => {
+            error!("❌ Failed to load dashboard snapshot: {}", e);
+            (StatusCode::SERVICE_UNAVAILABLE, error_body("Market data unavailable")).into_response()
+        }
+    }
+}
+
+/// Market-wide statistics derived from the latest dashboard (no aggregation)
+async fn dashboard_statistics_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+) -> Response {
+    match service_islands.market_statistics().await {
+        Some(statistics) => axum::Json(statistics).into_response(),
+        None => (StatusCode::SERVICE_UNAVAILABLE, error_body("No market data available yet")).into_response(),
+    }
+}
+
+/// One past dashboard by its `market_data_stream` entry id (e.g. `1700000000000-0`)
+async fn dashboard_history_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+    Path(id): Path<String>,
+) -> Response {
+    if let Err(e) = parse_stream_id(&id) {
+        return (StatusCode::BAD_REQUEST, error_body(&e.to_string())).into_response();
+    }
+
+    match service_islands.dashboard_at(&id).await {
+        Ok(Some(data)) => match DashboardData::deserialize(&data) {
+            Ok(dashboard) => axum::Json(dashboard).into_response(),
+            Err(e) => {
+                error!("❌ Stream entry {} has an unexpected shape: {}", id, e);
+                (StatusCode::INTERNAL_SERVER_ERROR, error_body("Invalid dashboard data")).into_response()
+            }
+        },
+        Ok(None) => (StatusCode::NOT_FOUND, error_body("No such entry (it may be older than the stream's retention)")).into_response(),
+        Err(e) => {
+            error!("❌ Failed to read stream entry {}: {:#}", id, e);
+            (StatusCode::SERVICE_UNAVAILABLE, error_body("Market data history unavailable")).into_response()
+        }
+    }
+}
+
+/// Metrics endpoint: WebSocket counters as JSON
+async fn metrics_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+) -> impl IntoResponse {
+    axum::Json(serde_json::json!({
+        "active_connections": service_islands.active_connections(),
+        "broadcasting_enabled": service_islands.broadcasting_enabled(),
+        "maintenance_mode": service_islands.maintenance_mode(),
+        "websocket": service_islands.websocket_service.metrics.snapshot(),
+        "messages": service_islands.websocket_service.message_stats.snapshot(),
+    }))
+}
+
+/// Stats endpoint: external API counters, provider health and aggregation counts
+///
+/// Open when no `ADMIN_TOKEN` is configured; otherwise requires it like `/admin`.
+async fn stats_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+    request: AdminRequest,
+) -> Response {
+    if service_islands.config.admin_token.is_some() {
+        if let Some(rejection) = admin_rejection(&service_islands, &request).await {
+            return rejection;
+        }
+    }
+
+    axum::Json(service_islands.external_apis.stats().await).into_response()
+}
+
+/// Connections listed by `/debug/connections` unless `?limit=` says otherwise
+const DEBUG_CONNECTIONS_DEFAULT_LIMIT: usize = 20;
+
+/// Query parameters of `/debug/connections`
+#[derive(Debug, Deserialize)]
+struct DebugConnectionsParams {
+    limit: Option<usize>,
+}
+
+/// Debug endpoint: live connections by bytes exchanged, heaviest first
+///
+/// Open when no `ADMIN_TOKEN` is configured; otherwise requires it like `/admin`.
+async fn debug_connections_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+    Query(params): Query<DebugConnectionsParams>,
+    request: AdminRequest,
+) -> Response {
+    if service_islands.config.admin_token.is_some() {
+        if let Some(rejection) = admin_rejection(&service_islands, &request).await {
+            return rejection;
+        }
+    }
+
+    let limit = params.limit.unwrap_or(DEBUG_CONNECTIONS_DEFAULT_LIMIT);
+    let connections: Vec<_> = service_islands.websocket_service.connection_manager
+        .heaviest(limit)
+        .await
+        .into_iter()
+        .map(|(id, state)| {
+            let mut topics: Vec<_> = state.topics.into_iter().collect();
+            topics.sort();
+            serde_json::json!({
+                "id": id,
+                "connected_secs": state.connected_at.elapsed().as_secs(),
+                "bytes_sent": state.traffic.bytes_sent(),
+                "bytes_received": state.traffic.bytes_received(),
+                "topics": topics,
+                "client_version": state.client_version,
+            })
+        })
+        .collect();
+
+    axum::Json(serde_json::json!({
+        "active_connections": service_islands.active_connections(),
+        "connections": connections,
+    }))
+    .into_response()
+}
+
+/// Admin endpoint: broadcast a `Notice` to every connected client
+///
+/// Requires `Authorization: Bearer <ADMIN_TOKEN>`; returns 404 when no token is configured.
+async fn admin_broadcast_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+    request: AdminRequest,
+) -> Response {
+    if let Some(rejection) = admin_rejection(&service_islands, &request).await {
+        return rejection;
+    }
+
+    let notice: NoticePayload = match serde_json::from_str(&request.body) {
+        Ok(notice) => notice,
+        Err(e) => return (StatusCode::BAD_REQUEST, error_body(&format!("Invalid notice body: {}", e))).into_response(),
+    };
+    if notice.message.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, error_body("Notice message must not be empty")).into_response();
+    }
+
+    match service_islands.broadcast_notice(notice).await {
+        Ok(receivers) => (
+            StatusCode::ACCEPTED,
+            axum::Json(serde_json::json!({ "status": "sent", "receivers": receivers })),
+        ).into_response(),
+        Err(e) => {
+            error!("❌ Failed to broadcast admin notice: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, error_body("Failed to broadcast notice")).into_response()
+        }
+    }
+}
+
+/// Admin endpoint: stop sending dashboard updates to clients (cache refresh continues)
+///
+/// Same auth as `/admin/broadcast`. An optional `{"message":"…"}` body is sent to
+/// clients once as a warning `Notice` when broadcasting actually gets paused.
+async fn admin_broadcast_pause_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+    request: AdminRequest,
+) -> Response {
+    set_broadcasting(&service_islands, &request, false).await
+}
+
+/// Admin endpoint: resume dashboard updates paused by `/admin/broadcast/pause`
+///
+/// Same body and auth as the pause endpoint; the optional notice is sent at `info` level.
+async fn admin_broadcast_resume_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+    request: AdminRequest,
+) -> Response {
+    set_broadcasting(&service_islands, &request, true).await
+}
+
+/// Admin endpoint: enter maintenance mode (no fetching; clients keep the last good dashboard)
+///
+/// Same auth as `/admin/broadcast`. Connected clients get the `MAINTENANCE_MESSAGE`
+/// notice once (or the body's `{"message":"…"}` instead); new clients get it on connect.
+async fn admin_maintenance_enable_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+    request: AdminRequest,
+) -> Response {
+    set_maintenance(&service_islands, &request, true).await
+}
+
+/// Admin endpoint: leave maintenance mode; fetching resumes at the next tick
+///
+/// Same body and auth as the enable endpoint; the optional notice is sent at `info` level.
+async fn admin_maintenance_disable_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+    request: AdminRequest,
+) -> Response {
+    set_maintenance(&service_islands, &request, false).await
+}
+
+/// Shared body of the maintenance enable/disable endpoints
+async fn set_maintenance(service_islands: &ServiceIslands, request: &AdminRequest, enabled: bool) -> Response {
+    if let Some(rejection) = admin_rejection(service_islands, request).await {
+        return rejection;
+    }
+
+    let message = match optional_message(&request.body) {
+        Ok(message) => message,
+        Err(e) => return (StatusCode::BAD_REQUEST, error_body(&format!("Invalid body: {}", e))).into_response(),
+    };
+
+    let changed = match service_islands.set_maintenance_mode(enabled).await {
+        Ok(changed) => changed,
+        Err(e) => {
+            error!("❌ Failed to switch maintenance mode: {:#}", e);
+            return (StatusCode::SERVICE_UNAVAILABLE, error_body("Failed to store the maintenance switch")).into_response();
+        }
+    };
+    let notice = match (enabled, message) {
+        (true, message) => Some(NoticePayload {
+            message: message.unwrap_or_else(|| service_islands.config.maintenance_message.clone()),
+            ..service_islands.maintenance_notice()
+        }),
+        (false, Some(message)) => Some(NoticePayload { level: NoticeLevel::Info, message, timestamp: chrono::Utc::now().timestamp() }),
+        (false, None) => None,
+    };
+    if let Some(notice) = notice.filter(|_| changed) {
+        if let Err(e) = service_islands.broadcast_notice(notice).await {
+            warn!("⚠️ Failed to send maintenance {} notice: {}", if enabled { "start" } else { "end" }, e);
+        }
+    }
+
+    axum::Json(serde_json::json!({
+        "status": "ok",
+        "maintenance_mode": enabled,
+        "changed": changed,
+    })).into_response()
+}
+
+/// Optional `{"message":"…"}` admin body (None when empty or blank)
+fn optional_message(body: &str) -> Result<Option<String>, serde_json::Error> {
+    #[derive(serde::Deserialize)]
+    struct MessageRequest {
+        message: Option<String>,
+    }
+
+    if body.trim().is_empty() {
+        return Ok(None);
+    }
+    let request: MessageRequest = serde_json::from_str(body)?;
+    Ok(request.message.filter(|message| !message.trim().is_empty()))
+}
+
+/// Admin endpoint: stop fetching one data group (`crypto`, `global`, `fng`, `rsi`, `indices`)
+///
+/// Same auth as `/admin/broadcast`. The group is skipped from the next aggregation on
+/// (cluster-wide), reported as `disabled` in `sources` rather than as a failure.
+/// Unknown groups get 404, a failure to store the switch in Redis 503.
+async fn admin_source_disable_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+    Path(name): Path<String>,
+    request: AdminRequest,
+) -> Response {
+    set_source_enabled(&service_islands, &request, &name, false).await
+}
+
+/// Admin endpoint: resume fetching a data group disabled by `/admin/source/:name/disable`
+async fn admin_source_enable_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+    Path(name): Path<String>,
+    request: AdminRequest,
+) -> Response {
+    set_source_enabled(&service_islands, &request, &name, true).await
+}
+
+/// Shared body of the source disable/enable endpoints
+async fn set_source_enabled(service_islands: &ServiceIslands, request: &AdminRequest, name: &str, enabled: bool) -> Response {
+    if let Some(rejection) = admin_rejection(service_islands, request).await {
+        return rejection;
+    }
+
+    if !DATA_GROUPS.contains(&name) {
+        let error = format!("Unknown data source '{}' (expected one of: {})", name, DATA_GROUPS.join(", "));
+        return (StatusCode::NOT_FOUND, error_body(&error)).into_response();
+    }
+
+    match service_islands.set_source_enabled(name, enabled).await {
+        Ok(changed) => {
+            if changed {
+                warn!(source = name, enabled, "Data source switched by admin request");
+            }
+            axum::Json(serde_json::json!({
+                "status": "ok",
+                "source": name,
+                "enabled": enabled,
+                "changed": changed,
+                "disabled_sources": service_islands.external_apis.disabled_sources(),
+            })).into_response()
+        }
+        Err(e) => {
+            error!("❌ Failed to switch data source {}: {:#}", name, e);
+            (StatusCode::SERVICE_UNAVAILABLE, error_body("Failed to store the data source switch")).into_response()
+        }
+    }
+}
+
+/// Shared body of the pause/resume endpoints
+async fn set_broadcasting(service_islands: &ServiceIslands, request: &AdminRequest, enabled: bool) -> Response {
+    if let Some(rejection) = admin_rejection(service_islands, request).await {
+        return rejection;
+    }
+
+    let message = match optional_message(&request.body) {
+        Ok(message) => message,
+        Err(e) => return (StatusCode::BAD_REQUEST, error_body(&format!("Invalid body: {}", e))).into_response(),
+    };
+
+    let changed = match service_islands.set_broadcasting_enabled(enabled).await {
+        Ok(changed) => changed,
+        Err(e) => {
+            error!("❌ Failed to switch broadcasting: {:#}", e);
+            return (StatusCode::SERVICE_UNAVAILABLE, error_body("Failed to store the broadcasting switch")).into_response();
+        }
+    };
+    if let Some(message) = message.filter(|_| changed) {
+        let level = if enabled { NoticeLevel::Info } else { NoticeLevel::Warning };
+        let notice = NoticePayload { level, message, timestamp: chrono::Utc::now().timestamp() };
+        if let Err(e) = service_islands.broadcast_notice(notice).await {
+            warn!("⚠️ Failed to send broadcasting {} notice: {}", if enabled { "resumed" } else { "paused" }, e);
+        }
+    }
+
+    axum::Json(serde_json::json!({
+        "status": "ok",
+        "broadcasting_enabled": enabled,
+        "changed": changed,
+    })).into_response()
+}
+
+/// Admin endpoint: replace the log filter, e.g. `{"filter":"debug"}`
+///
+/// Same auth as `/admin/broadcast`. The change lasts until the next SIGHUP or restart.
+async fn admin_log_level_handler(
+    State(service_islands): State<Arc<ServiceIslands>>,
+    Extension(log_reload): Extension<LogReloadHandle>,
+    request: AdminRequest,
+) -> Response {
+    if let Some(rejection) = admin_rejection(&service_islands, &request).await {
+        return rejection;
+    }
+
+    #[derive(serde::Deserialize)]
+    struct LogLevelRequest {
+        filter: String,
+    }
+
+    let request: LogLevelRequest = match serde_json::from_str(&request.body) {
+        Ok(request) => request,
+        Err(e) => return (StatusCode::BAD_REQUEST, error_body(&format!("Invalid log level body: {}", e))).into_response(),
+    };
+    let filter = match EnvFilter::try_new(&request.filter) {
+        Ok(filter) => filter,
+        Err(e) => return (StatusCode::BAD_REQUEST, error_body(&format!("Invalid log filter: {}", e))).into_response(),
+    };
+
+    match log_reload.reload(filter) {
+        Ok(()) => {
+            info!("🔧 Log filter changed via admin API to '{}'", request.filter);
+            axum::Json(serde_json::json!({ "status": "ok", "filter": request.filter })).into_response()
+        }
+        Err(e) => {
+            error!("❌ Failed to reload log filter: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, error_body("Failed to reload log filter")).into_response()
+        }
+    }
+}
+
+/// An admin request as `admin_rejection` checks it: the parts a signature covers, plus headers
+struct AdminRequest {
+    method: Method,
+    path: String,
+    headers: HeaderMap,
+    body: String,
+}
+
+#[axum::async_trait]
+impl<S: Send + Sync> FromRequest<S, Body> for AdminRequest {
+    type Rejection = Response;
+
+    async fn from_request(request: Request<Body>, state: &S) -> Result<Self, Self::Rejection> {
+        let method = request.method().clone();
+        let path = request.uri().path().to_string();
+        let headers = request.headers().clone();
+        let body = String::from_request(request, state).await.map_err(IntoResponse::into_response)?;
+        Ok(Self { method, path, headers, body })
+    }
+}
+
+/// Rejection for a request without a valid `Authorization: Bearer <ADMIN_TOKEN>`
+///
+/// With `ADMIN_HMAC_SECRET`, the request must also be signed over its method, path and
+/// body (see `admin_auth`), with a signature no instance has accepted before.
+/// 404 when no token is configured, 401 on a missing or wrong token or signature,
+/// 503 when the signature can't be claimed in Redis, `None` when authorized.
+async fn admin_rejection(service_islands: &ServiceIslands, request: &AdminRequest) -> Option<Response> {
+    let headers = &request.headers;
+    let Some(admin_token) = service_islands.config.admin_token.as_deref() else {
+        return Some((StatusCode::NOT_FOUND, error_body("Admin API disabled")).into_response());
+    };
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if !provided.is_some_and(|token| admin_auth::tokens_match(token, admin_token)) {
+        return Some((StatusCode::UNAUTHORIZED, error_body("Missing or invalid admin token")).into_response());
+    }
+
+    if let Some(verifier) = &service_islands.admin_verifier {
+        let header_str = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let verified = verifier.verify(
+            header_str(admin_auth::TIMESTAMP_HEADER),
+            header_str(admin_auth::SIGNATURE_HEADER),
+            request.method.as_str(),
+            &request.path,
+            &request.body,
+            chrono::Utc::now().timestamp(),
+        );
+        let signature = match verified {
+            Ok(signature) => signature,
+            Err(e) => {
+                warn!("Rejected admin request: {}", e);
+                return Some((StatusCode::UNAUTHORIZED, error_body(&e.to_string())).into_response());
+            }
+        };
+
+        // Another instance may have accepted the same signature within the window
+        let claim_ttl = Duration::from_secs(2 * admin_auth::MAX_CLOCK_SKEW_SECONDS as u64);
+        match service_islands.leader_election.try_claim_admin_signature(&signature, claim_ttl).await {
+            Ok(true) => {}
+            Ok(false) => {
+                warn!("Rejected admin request: signature already used on another instance");
+                return Some((StatusCode::UNAUTHORIZED, error_body("Request already used")).into_response());
+            }
+            Err(e) => {
+                error!("❌ Failed to check admin request for replay: {:#}", e);
+                return Some((StatusCode::SERVICE_UNAVAILABLE, error_body("Could not check the request for replay")).into_response());
+            }
+        }
+    }
+    None
+}
+
+/// `{"status":"error","error":...}` body for HTTP error responses
+fn error_body(message: &str) -> axum::Json<serde_json::Value> {
+    axum::Json(serde_json::json!({ "status": "error", "error": message }))
+}
+
 /// Background task to fetch market data periodically
 ///
 /// With leader election enabled:
 /// - Only the LEADER instance fetches from external APIs
-/// - Follower instances read from Redis cache
+/// - Follower instances read from Redis cache (or `market_data_stream` with
+///   `FOLLOWER_STREAM_CONSUMER`)
 /// - This reduces API calls and prevents rate limiting
+///
+/// Returns once shutdown begins (`ServiceIslands::begin_shutdown`, called by `shutdown_signal`);
+/// a tick already in progress is finished first, never abandoned mid-publish.
 async fn spawn_market_data_fetcher(service_islands: Arc<ServiceIslands>) {
-    use std::sync::atomic::Ordering;
-
     info!("🔄 Starting periodic market data fetcher with leader election...");
 
-    // Read interval from environment variable (default: 5 seconds for real-time updates)
-    let fetch_interval = env::var("FETCH_INTERVAL_SECONDS")
-        .unwrap_or_else(|_| "5".to_string())
-        .parse::<u64>()
-        .unwrap_or(5);
+    // Interval from FETCH_INTERVAL_SECONDS (default: 5 seconds for real-time updates)
+    let fetch_interval = service_islands.config.fetch_interval_seconds;
 
     info!("⏱️ Market data fetch interval: {} seconds", fetch_interval);
 
     let mut interval_timer = interval(Duration::from_secs(fetch_interval));
+    let mut shutdown = service_islands.subscribe_shutdown();
 
     loop {
-        interval_timer.tick().await;
+        tokio::select! {
+            _ = interval_timer.tick() => {}
+            _ = shutdown.changed() => {
+                info!("🛑 Market data fetcher stopping for shutdown");
+                break;
+            }
+        }
 
-        // Check if this instance is the leader
-        let is_leader = service_islands.is_leader.load(Ordering::Relaxed);
+        service_islands.market_data_tick().await;
+    }
+}
 
-        if is_leader {
-            // LEADER MODE: Fetch from API and cache
-            info!("🎖️ [LEADER] Fetching market data from APIs...");
+/// Log filter from `RUST_LOG`, falling back to `DEFAULT_LOG_FILTER`
+fn env_log_filter() -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER))
+}
 
-            match service_islands.fetch_and_publish_market_data(true).await {
-                Ok(data) => {
-                    info!("✅ [LEADER] Market data fetched successfully from APIs");
+/// Re-read `RUST_LOG` on every SIGHUP and apply it live
+///
+/// A running process cannot see changes to its own environment, so `RUST_LOG` in
+/// `.env` takes precedence over the value the process was started with.
+#[cfg(unix)]
+async fn reload_log_filter_on_sighup(log_reload: LogReloadHandle) {
+    let mut hangup = match signal::unix::signal(signal::unix::SignalKind::hangup()) {
+        Ok(sig) => sig,
+        Err(e) => {
+            error!("Failed to install SIGHUP handler: {}", e);
+            return;
+        }
+    };
 
-                    // Broadcast to all WebSocket clients
-                    if let Err(e) = service_islands.broadcast_to_websocket_clients(data).await {
-                        error!("❌ [LEADER] Failed to broadcast to WebSocket clients: {}", e);
-                    } else {
-                        info!("📡 [LEADER] Broadcasted to {} WebSocket clients",
-                              service_islands.active_connections());
-                    }
-                }
-                Err(e) => {
-                    error!("❌ [LEADER] Failed to fetch market data: {}", e);
-                }
-            }
-        } else {
-            // FOLLOWER MODE: Read from cache only
-            info!("👥 [FOLLOWER] Reading market data from cache...");
-
-            // Try to get latest data from cache
-            match service_islands.cache_system.cache_manager()
-                .get("latest_market_data")
-                .await
-            {
-                Ok(Some(data)) => {
-                    info!("✅ [FOLLOWER] Market data loaded from cache");
-
-                    // Broadcast to all WebSocket clients
-                    if let Err(e) = service_islands.broadcast_to_websocket_clients(data).await {
-                        error!("❌ [FOLLOWER] Failed to broadcast to WebSocket clients: {}", e);
-                    } else {
-                        info!("📡 [FOLLOWER] Broadcasted cached data to {} WebSocket clients",
-                              service_islands.active_connections());
-                    }
-                }
-                Ok(None) => {
-                    warn!("⚠️ [FOLLOWER] No cached data available yet (leader may still be fetching)");
-                }
-                Err(e) => {
-                    error!("❌ [FOLLOWER] Failed to read from cache: {}", e);
-                }
-            }
+    while hangup.recv().await.is_some() {
+        let from_dotenv = dotenvy::dotenv_iter().ok().and_then(|vars| {
+            vars.filter_map(Result::ok)
+                .find_map(|(key, value)| (key == "RUST_LOG").then_some(value))
+        });
+        let filter = match from_dotenv {
+            Some(directives) => EnvFilter::try_new(&directives).unwrap_or_else(|e| {
+                warn!("⚠️ Invalid RUST_LOG in .env ({}), using process environment", e);
+                env_log_filter()
+            }),
+            None => env_log_filter(),
+        };
+
+        let description = filter.to_string();
+        match log_reload.reload(filter) {
+            Ok(()) => info!("🔧 SIGHUP: log filter reloaded to '{}'", description),
+            Err(e) => error!("❌ SIGHUP: failed to reload log filter: {}", e),
         }
     }
 }
 
 /// Graceful shutdown signal handler
-async fn shutdown_signal() {
+///
+/// Once a signal arrives, background tasks are told to stop and the force-exit
+/// deadline starts; `main` then stops the fetcher, drains WebSocket connections
+/// (1001, going away) and releases leadership, in that order.
+async fn shutdown_signal(service_islands: Arc<ServiceIslands>) {
     let ctrl_c = async {
         if let Err(e) = signal::ctrl_c().await {
             error!("Failed to install Ctrl+C handler: {}", e);
@@ -288,4 +1307,7 @@ async fn shutdown_signal() {
             info!("🛑 Received SIGTERM, shutting down gracefully...");
         },
     }
+
+    let deadline = service_islands.begin_shutdown();
+    tokio::spawn(force_exit_at(deadline));
 }
@@ -3,17 +3,53 @@
 //! Provides optimized HTTP clients and performance utilities.
 
 use std::time::Duration;
-use std::sync::LazyLock;
+use std::sync::{LazyLock, OnceLock};
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
 use reqwest::Client;
 
+/// Default `HTTP_USER_AGENT`: identifies this service instead of reqwest's empty default
+pub const DEFAULT_HTTP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Headers sent with every outbound API request (`HTTP_USER_AGENT`, `HTTP_DEFAULT_HEADERS`)
+#[derive(Debug, Clone)]
+pub struct HttpClientOptions {
+    pub user_agent: HeaderValue,
+    pub default_headers: HeaderMap,
+}
+
+impl Default for HttpClientOptions {
+    fn default() -> Self {
+        Self {
+            user_agent: HeaderValue::from_static(DEFAULT_HTTP_USER_AGENT),
+            default_headers: HeaderMap::new(),
+        }
+    }
+}
+
+static HTTP_CLIENT_OPTIONS: OnceLock<HttpClientOptions> = OnceLock::new();
+
+/// Set the headers `OPTIMIZED_HTTP_CLIENT` is built with
+///
+/// Must run before the client's first use (the API islands clone it on creation);
+/// returns false if options were already set.
+pub fn configure_http_client(options: HttpClientOptions) -> bool {
+    HTTP_CLIENT_OPTIONS.set(options).is_ok()
+}
+
 /// Optimized HTTP client with connection pooling and timeouts
 ///
+/// Sends the configured user agent and default headers (see `configure_http_client`).
 /// Falls back to a default client if the optimized configuration fails to build.
 pub static OPTIMIZED_HTTP_CLIENT: LazyLock<Client> = LazyLock::new(|| {
+    let options = HTTP_CLIENT_OPTIONS.get_or_init(HttpClientOptions::default);
+    let mut headers = options.default_headers.clone();
+    headers.insert(USER_AGENT, options.user_agent.clone());
+
     Client::builder()
         .pool_max_idle_per_host(10)
         .timeout(Duration::from_secs(30))
         .connect_timeout(Duration::from_secs(10))
+        .default_headers(headers)
         .build()
         .unwrap_or_else(|e| {
             eprintln!("⚠️ Failed to create optimized HTTP client: {}, using default", e);
@@ -3,14 +3,20 @@
 //! This module now re-exports from the multi-tier-cache library.
 //! All cache management functionality is provided by the external library.
 
+use std::time::Duration;
+
 // Re-export from the library
 #[allow(unused_imports)]
 pub use multi_tier_cache::{CacheManager, CacheStrategy, CacheManagerStats};
 
-/// Helper: return a realtime cache strategy with a 5 second TTL.
+/// Default TTL of realtime entries (crypto prices, `latest_market_data`)
+pub const DEFAULT_REALTIME_TTL_SECONDS: u64 = 5;
+
+/// Helper: return the realtime cache strategy for `ttl` (at least 1s).
 ///
 /// Use this for real-time market data that updates frequently.
-/// The 5-second TTL balances freshness with API rate limiting.
-pub fn realtime_strategy() -> CacheStrategy {
-	CacheStrategy::Custom(std::time::Duration::from_secs(5))
+/// The short TTL balances freshness with API rate limiting. Callers with a cache
+/// system use `CacheSystemIsland::realtime_strategy` (`REALTIME_CACHE_TTL_SECONDS`).
+pub fn realtime_strategy(ttl: Duration) -> CacheStrategy {
+	CacheStrategy::Custom(ttl.max(Duration::from_secs(1)))
 }
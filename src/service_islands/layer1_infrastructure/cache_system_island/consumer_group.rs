@@ -0,0 +1,299 @@
+//! Stream Consumer Groups
+//!
+//! Two ways to read a stream such as `market_data_stream`:
+//!
+//! - **Broadcast** (`MarketDataStreamConsumer`, plain `XREAD`): every reader sees
+//!   every entry. This is what followers use to relay dashboards.
+//! - **Work sharing** (this module, `XREADGROUP`): readers in one consumer group
+//!   each get a disjoint share of the entries. An entry stays in the group's
+//!   pending list until its consumer `XACK`s it; entries left pending longer than
+//!   `min_idle` (the consumer crashed or its handler failed) are taken over by the
+//!   next consumer that reads, with `XAUTOCLAIM`.
+//!
+//! Nothing uses work sharing yet; it is here for features where followers split
+//! work (e.g. each trimming a shard of history).
+
+use std::future::Future;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use multi_tier_cache::async_trait;
+use redis::aio::ConnectionManager;
+use redis::streams::{StreamAutoClaimReply, StreamId, StreamReadReply};
+use redis::{Client, FromRedisValue};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// Stream entry id with its fields
+pub type StreamEntry = (String, Vec<(String, String)>);
+
+/// What `create_consumer_group` does when the group already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExistingGroup {
+    /// Keep the group and its position (another instance created it first)
+    #[default]
+    Reuse,
+    /// Move the group's position to the stream's latest entry, skipping the backlog
+    ResetToLatest,
+    /// Treat an existing group as an error (one owner per group)
+    Fail,
+}
+
+/// Consumer group commands (`XGROUP`, `XREADGROUP`, `XACK`, `XAUTOCLAIM`)
+#[async_trait]
+pub trait StreamGroupBackend: Send + Sync {
+    /// Create `group` positioned after `start_id` (`$`: only new entries), creating
+    /// the stream if needed; `Ok(false)` when the group already exists
+    async fn group_create(&self, stream_key: &str, group: &str, start_id: &str) -> Result<bool>;
+
+    /// Move `group`'s position to after `id` (`$`: the latest entry)
+    async fn group_set_id(&self, stream_key: &str, group: &str, id: &str) -> Result<()>;
+
+    /// Up to `count` entries never delivered to the group, now pending on `consumer`
+    async fn group_read(&self, stream_key: &str, group: &str, consumer: &str, count: usize) -> Result<Vec<StreamEntry>>;
+
+    /// Acknowledge entries, removing them from the pending list; returns how many were pending
+    async fn group_ack(&self, stream_key: &str, group: &str, ids: &[String]) -> Result<usize>;
+
+    /// Take over up to `count` entries pending (on any consumer) for at least `min_idle`
+    async fn group_claim_idle(
+        &self,
+        stream_key: &str,
+        group: &str,
+        consumer: &str,
+        min_idle: Duration,
+        count: usize,
+    ) -> Result<Vec<StreamEntry>>;
+}
+
+/// Create `group` on `stream_key` for new entries, handling an existing group per `on_existing`
+pub async fn create_consumer_group(
+    backend: &dyn StreamGroupBackend,
+    stream_key: &str,
+    group: &str,
+    on_existing: ExistingGroup,
+) -> Result<()> {
+    if backend.group_create(stream_key, group, "$").await? {
+        return Ok(());
+    }
+    match on_existing {
+        ExistingGroup::Reuse => Ok(()),
+        ExistingGroup::ResetToLatest => backend.group_set_id(stream_key, group, "$").await,
+        ExistingGroup::Fail => bail!("Consumer group '{}' already exists on '{}'", group, stream_key),
+    }
+}
+
+/// Entries handled by one `StreamGroupConsumer::consume` call
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ConsumeSummary {
+    /// Entries whose handler succeeded (and were acknowledged)
+    pub acked: usize,
+    /// Entries whose handler failed (left pending for a later claim)
+    pub failed: usize,
+    /// Entries taken over from idle consumers before reading new ones
+    pub recovered: usize,
+}
+
+/// One named consumer in a group
+#[derive(Debug, Clone)]
+pub struct StreamGroupConsumer {
+    pub stream_key: String,
+    pub group: String,
+    pub consumer: String,
+    /// How long an entry must sit unacknowledged before another consumer takes it over
+    pub min_idle: Duration,
+    /// Most entries claimed, and most read, per `consume`
+    pub batch_size: usize,
+}
+
+impl StreamGroupConsumer {
+    /// Consumer `consumer` of `group` on `stream_key` (30s claim idle time, 50-entry batches)
+    pub fn new(stream_key: &str, group: &str, consumer: &str) -> Self {
+        Self {
+            stream_key: stream_key.to_string(),
+            group: group.to_string(),
+            consumer: consumer.to_string(),
+            min_idle: Duration::from_secs(30),
+            batch_size: 50,
+        }
+    }
+
+    /// Recover idle pending entries, then read new ones, running `handler` on each
+    ///
+    /// Only entries whose handler returns `Ok` are acknowledged; the rest stay
+    /// pending and are claimed again once idle for `min_idle`.
+    pub async fn consume<F, Fut>(&self, backend: &dyn StreamGroupBackend, mut handler: F) -> Result<ConsumeSummary>
+    where
+        F: FnMut(StreamEntry) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let recovered = backend
+            .group_claim_idle(&self.stream_key, &self.group, &self.consumer, self.min_idle, self.batch_size)
+            .await
+            .context("Failed to claim idle pending entries")?;
+        let new_entries = backend
+            .group_read(&self.stream_key, &self.group, &self.consumer, self.batch_size)
+            .await
+            .context("Failed to read from consumer group")?;
+
+        let mut summary = ConsumeSummary { recovered: recovered.len(), ..Default::default() };
+        let mut handled = Vec::new();
+        for entry in recovered.into_iter().chain(new_entries) {
+            let id = entry.0.clone();
+            match handler(entry).await {
+                Ok(()) => handled.push(id),
+                Err(e) => {
+                    warn!(%id, group = %self.group, error = format!("{:#}", e), "Stream entry handler failed, leaving it pending");
+                    summary.failed += 1;
+                }
+            }
+        }
+
+        if !handled.is_empty() {
+            backend.group_ack(&self.stream_key, &self.group, &handled).await?;
+        }
+        summary.acked = handled.len();
+        Ok(summary)
+    }
+}
+
+/// Consumer group commands on Redis (connects on first use)
+pub struct RedisStreamGroups {
+    client: Client,
+    connection: Mutex<Option<ConnectionManager>>,
+}
+
+impl RedisStreamGroups {
+    /// Groups on the Redis at `redis_url`
+    pub fn new(redis_url: &str) -> Result<Self> {
+        Ok(Self {
+            client: Client::open(redis_url).context("Invalid Redis URL for stream consumer groups")?,
+            connection: Mutex::new(None),
+        })
+    }
+
+    /// Run a command on the shared connection (reconnects on its own once established)
+    async fn query<T: FromRedisValue>(&self, cmd: &redis::Cmd) -> Result<T> {
+        let mut connection = {
+            let mut connection = self.connection.lock().await;
+            match &*connection {
+                Some(connection) => connection.clone(),
+                None => connection.insert(ConnectionManager::new(self.client.clone()).await?).clone(),
+            }
+        };
+        Ok(cmd.query_async(&mut connection).await?)
+    }
+}
+
+/// `StreamId` fields as strings (non-string values are skipped)
+fn entry_from(stream_id: StreamId) -> StreamEntry {
+    let fields = stream_id
+        .map
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, String::from_redis_value(&value).ok()?)))
+        .collect();
+    (stream_id.id, fields)
+}
+
+#[async_trait]
+impl StreamGroupBackend for RedisStreamGroups {
+    async fn group_create(&self, stream_key: &str, group: &str, start_id: &str) -> Result<bool> {
+        let mut cmd = redis::cmd("XGROUP");
+        cmd.arg("CREATE").arg(stream_key).arg(group).arg(start_id).arg("MKSTREAM");
+        match self.query::<()>(&cmd).await {
+            Ok(()) => Ok(true),
+            Err(e) if e.downcast_ref::<redis::RedisError>().and_then(|e| e.code()) == Some("BUSYGROUP") => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn group_set_id(&self, stream_key: &str, group: &str, id: &str) -> Result<()> {
+        let mut cmd = redis::cmd("XGROUP");
+        cmd.arg("SETID").arg(stream_key).arg(group).arg(id);
+        self.query(&cmd).await
+    }
+
+    async fn group_read(&self, stream_key: &str, group: &str, consumer: &str, count: usize) -> Result<Vec<StreamEntry>> {
+        let mut cmd = redis::cmd("XREADGROUP");
+        cmd.arg("GROUP").arg(group).arg(consumer).arg("COUNT").arg(count).arg("STREAMS").arg(stream_key).arg(">");
+        let reply: Option<StreamReadReply> = self.query(&cmd).await?;
+        Ok(reply
+            .into_iter()
+            .flat_map(|reply| reply.keys)
+            .flat_map(|key| key.ids)
+            .map(entry_from)
+            .collect())
+    }
+
+    async fn group_ack(&self, stream_key: &str, group: &str, ids: &[String]) -> Result<usize> {
+        let mut cmd = redis::cmd("XACK");
+        cmd.arg(stream_key).arg(group).arg(ids);
+        self.query(&cmd).await
+    }
+
+    async fn group_claim_idle(
+        &self,
+        stream_key: &str,
+        group: &str,
+        consumer: &str,
+        min_idle: Duration,
+        count: usize,
+    ) -> Result<Vec<StreamEntry>> {
+        let mut cmd = redis::cmd("XAUTOCLAIM");
+        cmd.arg(stream_key).arg(group).arg(consumer).arg(min_idle.as_millis() as u64).arg("0-0").arg("COUNT").arg(count);
+        let reply: StreamAutoClaimReply = self.query(&cmd).await?;
+        Ok(reply.claimed.into_iter().map(entry_from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use multi_tier_cache::StreamingBackend;
+
+    use super::super::memory_l2::MemoryL2Cache;
+
+    #[tokio::test]
+    async fn test_group_members_share_entries_and_recover_failures() {
+        let backend = Arc::new(MemoryL2Cache::new());
+        let publish = |n: u32| backend.stream_add("s", vec![("n".into(), n.to_string())], None);
+        publish(0).await.unwrap();
+
+        create_consumer_group(backend.as_ref(), "s", "trimmers", ExistingGroup::Fail).await.unwrap();
+        create_consumer_group(backend.as_ref(), "s", "trimmers", ExistingGroup::Reuse).await.unwrap();
+        assert!(create_consumer_group(backend.as_ref(), "s", "trimmers", ExistingGroup::Fail).await.is_err());
+
+        for n in 1..=4 {
+            publish(n).await.unwrap();
+        }
+        let mut a = StreamGroupConsumer::new("s", "trimmers", "a");
+        a.batch_size = 2;
+        let mut b = StreamGroupConsumer::new("s", "trimmers", "b");
+        b.min_idle = Duration::ZERO;
+
+        // Entry 0 predates the group; `a` and `b` split the rest, and `b` fails on 4
+        let mut seen_by_a = Vec::new();
+        let summary = a.consume(backend.as_ref(), |(_, fields)| {
+            seen_by_a.push(fields[0].1.clone());
+            async { Ok(()) }
+        }).await.unwrap();
+        assert_eq!((summary, seen_by_a), (ConsumeSummary { acked: 2, failed: 0, recovered: 0 }, vec!["1".to_string(), "2".to_string()]));
+
+        let summary = b.consume(backend.as_ref(), |(_, fields)| async move {
+            if fields[0].1 == "4" { bail!("trim failed") } else { Ok(()) }
+        }).await.unwrap();
+        assert_eq!(summary, ConsumeSummary { acked: 1, failed: 1, recovered: 0 });
+
+        // The failed entry is still pending and is recovered on the next consume
+        let summary = b.consume(backend.as_ref(), |_| async { Ok(()) }).await.unwrap();
+        assert_eq!(summary, ConsumeSummary { acked: 1, failed: 0, recovered: 1 });
+        assert_eq!(b.consume(backend.as_ref(), |_| async { Ok(()) }).await.unwrap(), ConsumeSummary::default());
+
+        // Resetting skips the backlog
+        publish(5).await.unwrap();
+        create_consumer_group(backend.as_ref(), "s", "trimmers", ExistingGroup::ResetToLatest).await.unwrap();
+        assert_eq!(a.consume(backend.as_ref(), |_| async { Ok(()) }).await.unwrap(), ConsumeSummary::default());
+    }
+}
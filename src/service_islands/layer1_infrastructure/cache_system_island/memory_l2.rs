@@ -0,0 +1,329 @@
+//! In-Memory L2 Cache
+//!
+//! Process-local stand-in for the Redis L2 tier, selected with `CACHE_L2=none`.
+//! Implements the key-value and stream traits of `multi_tier_cache`, so the
+//! aggregation, stream publish and broadcast paths run unchanged without Redis.
+//! Consumer groups are emulated too (see `consumer_group`).
+//! Nothing is shared between processes: every instance is its own leader.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use multi_tier_cache::{async_trait, CacheBackend, L2CacheBackend, StreamingBackend};
+use parking_lot::Mutex;
+use tokio::sync::Notify;
+
+use super::consumer_group::{StreamEntry, StreamGroupBackend};
+
+/// Consumer group state: last delivered id, plus pending id -> (consumer, delivered at)
+#[derive(Default)]
+struct MemoryGroup {
+    last_delivered: (u64, u64),
+    pending: HashMap<String, (String, Instant)>,
+}
+
+/// Key-value entries with optional expiry, plus append-only streams
+#[derive(Default)]
+pub struct MemoryL2Cache {
+    entries: Mutex<HashMap<String, (serde_json::Value, Option<Instant>)>>,
+    streams: Mutex<HashMap<String, VecDeque<StreamEntry>>>,
+    last_stream_id: Mutex<(u64, u64)>,
+    stream_added: Notify,
+    /// (stream, group) -> group state
+    groups: Mutex<HashMap<(String, String), MemoryGroup>>,
+}
+
+impl MemoryL2Cache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Next Redis-style `<ms>-<seq>` id, strictly increasing within the process
+    fn next_stream_id(&self) -> String {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+
+        let mut last = self.last_stream_id.lock();
+        *last = if now_ms > last.0 { (now_ms, 0) } else { (last.0, last.1 + 1) };
+        format!("{}-{}", last.0, last.1)
+    }
+
+    /// Entries with an id after `last_id` (`$` means only entries added from now on)
+    fn entries_after(&self, stream_key: &str, last_id: &str, count: usize) -> Vec<StreamEntry> {
+        let streams = self.streams.lock();
+        let Some(stream) = streams.get(stream_key) else {
+            return Vec::new();
+        };
+        if last_id == "$" {
+            return Vec::new();
+        }
+
+        let after = parse_stream_id(last_id);
+        stream
+            .iter()
+            .filter(|(id, _)| parse_stream_id(id) > after)
+            .take(count)
+            .cloned()
+            .collect()
+    }
+
+    /// Id of the newest entry in the stream (`0-0` when empty)
+    fn latest_id(&self, stream_key: &str) -> (u64, u64) {
+        self.streams
+            .lock()
+            .get(stream_key)
+            .and_then(|stream| stream.back())
+            .map(|(id, _)| parse_stream_id(id))
+            .unwrap_or_default()
+    }
+
+    /// `$` as the newest entry id, anything else parsed
+    fn resolve_id(&self, stream_key: &str, id: &str) -> (u64, u64) {
+        match id {
+            "$" => self.latest_id(stream_key),
+            id => parse_stream_id(id),
+        }
+    }
+}
+
+/// `<ms>-<seq>` as a comparable pair (a bare `<ms>` counts as sequence 0)
+fn parse_stream_id(id: &str) -> (u64, u64) {
+    let (ms, seq) = id.split_once('-').unwrap_or((id, "0"));
+    (ms.parse().unwrap_or_default(), seq.parse().unwrap_or_default())
+}
+
+#[async_trait]
+impl CacheBackend for MemoryL2Cache {
+    async fn get(&self, key: &str) -> Option<serde_json::Value> {
+        self.get_with_ttl(key).await.map(|(value, _)| value)
+    }
+
+    async fn set_with_ttl(&self, key: &str, value: serde_json::Value, ttl: Duration) -> Result<()> {
+        let expires_at = Instant::now().checked_add(ttl);
+        self.entries.lock().insert(key.to_string(), (value, expires_at));
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<()> {
+        self.entries.lock().remove(key);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "Memory"
+    }
+}
+
+#[async_trait]
+impl L2CacheBackend for MemoryL2Cache {
+    async fn get_with_ttl(&self, key: &str) -> Option<(serde_json::Value, Option<Duration>)> {
+        let mut entries = self.entries.lock();
+        let (value, expires_at) = entries.get(key)?;
+
+        let remaining = match expires_at {
+            Some(expires_at) => match expires_at.checked_duration_since(Instant::now()) {
+                Some(remaining) if !remaining.is_zero() => Some(remaining),
+                _ => {
+                    entries.remove(key);
+                    return None;
+                }
+            },
+            None => None,
+        };
+        Some((value.clone(), remaining))
+    }
+}
+
+#[async_trait]
+impl StreamingBackend for MemoryL2Cache {
+    async fn stream_add(
+        &self,
+        stream_key: &str,
+        fields: Vec<(String, String)>,
+        maxlen: Option<usize>,
+    ) -> Result<String> {
+        let id = self.next_stream_id();
+        {
+            let mut streams = self.streams.lock();
+            let stream = streams.entry(stream_key.to_string()).or_default();
+            stream.push_back((id.clone(), fields));
+            if let Some(maxlen) = maxlen {
+                while stream.len() > maxlen {
+                    stream.pop_front();
+                }
+            }
+        }
+        self.stream_added.notify_waiters();
+        Ok(id)
+    }
+
+    async fn stream_read_latest(&self, stream_key: &str, count: usize) -> Result<Vec<StreamEntry>> {
+        Ok(self
+            .streams
+            .lock()
+            .get(stream_key)
+            .map(|stream| stream.iter().rev().take(count).cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn stream_read(
+        &self,
+        stream_key: &str,
+        last_id: &str,
+        count: usize,
+        block_ms: Option<usize>,
+    ) -> Result<Vec<StreamEntry>> {
+        // `$` resolves to the newest id at call time, like XREAD
+        let last_id = match last_id {
+            "$" => self
+                .stream_read_latest(stream_key, 1)
+                .await?
+                .pop()
+                .map(|(id, _)| id)
+                .unwrap_or_else(|| "0-0".to_string()),
+            id => id.to_string(),
+        };
+
+        let entries = self.entries_after(stream_key, &last_id, count);
+        let Some(block_ms) = block_ms.filter(|_| entries.is_empty()) else {
+            return Ok(entries);
+        };
+
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(block_ms as u64);
+        loop {
+            let added = self.stream_added.notified();
+            let entries = self.entries_after(stream_key, &last_id, count);
+            if !entries.is_empty() {
+                return Ok(entries);
+            }
+            if tokio::time::timeout_at(deadline, added).await.is_err() {
+                return Ok(Vec::new());
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamGroupBackend for MemoryL2Cache {
+    async fn group_create(&self, stream_key: &str, group: &str, start_id: &str) -> Result<bool> {
+        let last_delivered = self.resolve_id(stream_key, start_id);
+        self.streams.lock().entry(stream_key.to_string()).or_default();
+        let mut groups = self.groups.lock();
+        let key = (stream_key.to_string(), group.to_string());
+        if groups.contains_key(&key) {
+            return Ok(false);
+        }
+        groups.insert(key, MemoryGroup { last_delivered, pending: HashMap::new() });
+        Ok(true)
+    }
+
+    async fn group_set_id(&self, stream_key: &str, group: &str, id: &str) -> Result<()> {
+        let last_delivered = self.resolve_id(stream_key, id);
+        match self.groups.lock().get_mut(&(stream_key.to_string(), group.to_string())) {
+            Some(state) => {
+                state.last_delivered = last_delivered;
+                Ok(())
+            }
+            None => anyhow::bail!("NOGROUP no consumer group '{}' on '{}'", group, stream_key),
+        }
+    }
+
+    async fn group_read(&self, stream_key: &str, group: &str, consumer: &str, count: usize) -> Result<Vec<StreamEntry>> {
+        let mut groups = self.groups.lock();
+        let Some(state) = groups.get_mut(&(stream_key.to_string(), group.to_string())) else {
+            anyhow::bail!("NOGROUP no consumer group '{}' on '{}'", group, stream_key);
+        };
+
+        let (ms, seq) = state.last_delivered;
+        let entries = self.entries_after(stream_key, &format!("{}-{}", ms, seq), count);
+        let now = Instant::now();
+        for (id, _) in &entries {
+            state.pending.insert(id.clone(), (consumer.to_string(), now));
+        }
+        if let Some((id, _)) = entries.last() {
+            state.last_delivered = parse_stream_id(id);
+        }
+        Ok(entries)
+    }
+
+    async fn group_ack(&self, stream_key: &str, group: &str, ids: &[String]) -> Result<usize> {
+        let mut groups = self.groups.lock();
+        let Some(state) = groups.get_mut(&(stream_key.to_string(), group.to_string())) else {
+            return Ok(0);
+        };
+        Ok(ids.iter().filter(|id| state.pending.remove(*id).is_some()).count())
+    }
+
+    async fn group_claim_idle(
+        &self,
+        stream_key: &str,
+        group: &str,
+        consumer: &str,
+        min_idle: Duration,
+        count: usize,
+    ) -> Result<Vec<StreamEntry>> {
+        let mut groups = self.groups.lock();
+        let Some(state) = groups.get_mut(&(stream_key.to_string(), group.to_string())) else {
+            anyhow::bail!("NOGROUP no consumer group '{}' on '{}'", group, stream_key);
+        };
+
+        let now = Instant::now();
+        let mut idle: Vec<String> = state.pending
+            .iter()
+            .filter(|(_, (_, delivered_at))| now.duration_since(*delivered_at) >= min_idle)
+            .map(|(id, _)| id.clone())
+            .collect();
+        idle.sort_by_key(|id| parse_stream_id(id));
+        idle.truncate(count);
+
+        let streams = self.streams.lock();
+        let stream = streams.get(stream_key);
+        let mut claimed = Vec::new();
+        for id in idle {
+            // Like XAUTOCLAIM, entries trimmed from the stream are dropped from the pending list
+            match stream.and_then(|stream| stream.iter().find(|(entry_id, _)| *entry_id == id)) {
+                Some(entry) => {
+                    state.pending.insert(id, (consumer.to_string(), now));
+                    claimed.push(entry.clone());
+                }
+                None => {
+                    state.pending.remove(&id);
+                }
+            }
+        }
+        Ok(claimed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_values_expire_and_streams_trim() {
+        let cache = MemoryL2Cache::new();
+        cache.set_with_ttl("fresh", serde_json::json!(1), Duration::from_secs(60)).await.unwrap();
+        cache.set_with_ttl("stale", serde_json::json!(2), Duration::ZERO).await.unwrap();
+        assert_eq!(cache.get("fresh").await, Some(serde_json::json!(1)));
+        assert_eq!(cache.get("stale").await, None);
+
+        let mut ids = Vec::new();
+        for n in 0..3 {
+            ids.push(cache.stream_add("s", vec![("n".into(), n.to_string())], Some(2)).await.unwrap());
+        }
+        let latest = cache.stream_read_latest("s", 10).await.unwrap();
+        assert_eq!(latest.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>(), vec![ids[2].clone(), ids[1].clone()]);
+
+        let after = cache.stream_read("s", &ids[1], 10, Some(10)).await.unwrap();
+        assert_eq!(after.len(), 1);
+        assert!(cache.stream_read("s", "$", 10, Some(10)).await.unwrap().is_empty());
+    }
+}
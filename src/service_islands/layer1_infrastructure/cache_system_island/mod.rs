@@ -5,12 +5,16 @@
 
 use std::sync::Arc;
 use std::ops::Deref;
-use anyhow::Result;
+use std::time::Duration;
+use anyhow::{Context, Result};
+use tracing::warn;
 
 // Import and re-export from multi-tier-cache library
 pub use multi_tier_cache::{
     CacheSystem as LibraryCacheSystem,
+    CacheSystemBuilder,
     CacheManager,
+    CacheBackend,
 };
 
 // Re-export stats struct for backward compatibility if needed
@@ -21,17 +25,52 @@ pub use multi_tier_cache::CacheManagerStats;
 pub mod l1_cache;
 pub mod l2_cache;
 pub mod cache_manager;
+pub mod consumer_group;
+pub mod memory_l2;
+pub mod namespaced;
+pub mod stream_consumer;
+
+use consumer_group::{ConsumeSummary, ExistingGroup, RedisStreamGroups, StreamEntry, StreamGroupBackend, StreamGroupConsumer};
+use memory_l2::MemoryL2Cache;
+use namespaced::{namespaced_key, NamespacedBackend};
+
+/// Key written by the Redis health probe (expires on its own)
+const HEALTH_PROBE_KEY: &str = "health_check_probe";
+const HEALTH_PROBE_TTL: Duration = Duration::from_secs(10);
+
+const DEFAULT_REALTIME_TTL: Duration = Duration::from_secs(cache_manager::DEFAULT_REALTIME_TTL_SECONDS);
+
+/// L2 tier backing the cache (`CACHE_L2`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CacheL2 {
+    /// Shared Redis (required for multi-instance deployments)
+    #[default]
+    Redis,
+    /// Process-local memory, for tests and local development without Redis
+    None,
+}
 
 /// Cache System Island - Two-tier caching system
 ///
 /// Wraps the multi-tier-cache library using Deref for zero-cost access.
-pub struct CacheSystemIsland(LibraryCacheSystem);
+/// Optionally holds a second cache system pointed at a Redis read replica,
+/// used only for reads (see `read_cache_manager`).
+pub struct CacheSystemIsland {
+    primary: LibraryCacheSystem,
+    replica: Option<LibraryCacheSystem>,
+    /// `CACHE_KEY_PREFIX` applied to Redis keys and streams (empty: bare keys)
+    key_prefix: String,
+    /// Consumer group commands on the primary's streams (None: Redis URL unusable)
+    stream_groups: Option<Arc<dyn StreamGroupBackend>>,
+    /// TTL of realtime entries (`REALTIME_CACHE_TTL_SECONDS`)
+    realtime_ttl: Duration,
+}
 
 impl Deref for CacheSystemIsland {
     type Target = LibraryCacheSystem;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.primary
     }
 }
 
@@ -39,24 +78,208 @@ impl CacheSystemIsland {
     /// Initialize the Cache System Island
     ///
     /// Now uses the multi-tier-cache library internally.
-    pub async fn new() -> Result<Self> {
+    pub async fn new(redis_url: &str) -> Result<Self> {
+        Self::with_read_replica(redis_url, None).await
+    }
+
+    /// Initialize the Cache System Island with an optional Redis read replica
+    ///
+    /// When `replica_url` is set, a read-only replica connection is also created;
+    /// a replica that fails to connect is logged and reads fall back to the primary.
+    pub async fn with_read_replica(redis_url: &str, replica_url: Option<&str>) -> Result<Self> {
         println!("🏗️ Initializing Cache System Island (using multi-tier-cache library)...");
 
-        // Initialize from library
-        let inner = LibraryCacheSystem::new().await?;
+        let primary = Self::build_redis(redis_url, "").await?;
+        let replica = match replica_url {
+            Some(replica_url) => Self::connect_replica(replica_url, "").await,
+            None => None,
+        };
 
         println!("✅ Cache System Island initialized successfully (library-backed)");
 
-        Ok(Self(inner))
+        Ok(Self { primary, replica, key_prefix: String::new(), stream_groups: Self::redis_stream_groups(redis_url, ""), realtime_ttl: DEFAULT_REALTIME_TTL })
+    }
+
+    /// Initialize with every Redis key and stream name under `<key_prefix>:`
+    ///
+    /// Same Redis connections as `with_read_replica`, with the L2 and streaming
+    /// backends wrapped in `NamespacedBackend`.
+    pub async fn with_key_prefix(redis_url: &str, replica_url: Option<&str>, key_prefix: &str) -> Result<Self> {
+        println!("🏗️ Initializing Cache System Island (Redis keys under '{}:')...", key_prefix);
+
+        let primary = Self::build_redis(redis_url, key_prefix).await?;
+        let replica = match replica_url {
+            Some(replica_url) => Self::connect_replica(replica_url, key_prefix).await,
+            None => None,
+        };
+
+        println!("✅ Cache System Island initialized successfully (namespaced)");
+
+        Ok(Self { primary, replica, key_prefix: key_prefix.to_string(), stream_groups: Self::redis_stream_groups(redis_url, key_prefix), realtime_ttl: DEFAULT_REALTIME_TTL })
+    }
+
+    /// Moka L1 plus Redis L2 and streams, both on `redis_url` and namespaced by `key_prefix`
+    ///
+    /// Built by hand because the library's own constructors always stream through the
+    /// `REDIS_URL` environment variable, whatever URL the L2 was given.
+    async fn build_redis(redis_url: &str, key_prefix: &str) -> Result<LibraryCacheSystem> {
+        let l1 = Arc::new(multi_tier_cache::L1Cache::new().await?);
+        let l2 = Arc::new(multi_tier_cache::L2Cache::with_url(redis_url).await?);
+        let streams = Arc::new(multi_tier_cache::RedisStreams::new(redis_url).await?);
+
+        let cache_manager = CacheManager::new_with_backends(
+            l1.clone(),
+            Arc::new(NamespacedBackend::new(key_prefix, l2.clone())),
+            Some(Arc::new(NamespacedBackend::new(key_prefix, streams))),
+        ).await?;
+
+        Ok(LibraryCacheSystem {
+            cache_manager: Arc::new(cache_manager),
+            l1_cache: Some(l1),
+            l2_cache: Some(l2),
+        })
+    }
+
+    /// Initialize with the in-memory L2 instead of Redis (`CACHE_L2=none`)
+    ///
+    /// L1 stays Moka; L2 values and the market data stream live in this process only.
+    pub async fn in_memory() -> Result<Self> {
+        println!("🏗️ Initializing Cache System Island (L1 + in-memory L2, no Redis)...");
+
+        let l2 = Arc::new(MemoryL2Cache::new());
+        let primary = CacheSystemBuilder::new()
+            .with_l1(Arc::new(multi_tier_cache::L1Cache::new().await?))
+            .with_l2(l2.clone())
+            .with_streams(l2.clone())
+            .build()
+            .await?;
+
+        println!("✅ Cache System Island initialized (in-memory L2)");
+
+        Ok(Self { primary, replica: None, key_prefix: String::new(), stream_groups: Some(l2), realtime_ttl: DEFAULT_REALTIME_TTL })
+    }
+
+    /// Initialize the L2 tier selected by `CACHE_L2`
+    ///
+    /// A replica and `key_prefix` only apply to Redis; the in-memory L2 is private
+    /// to the process and needs no namespace.
+    pub async fn with_l2(l2: CacheL2, redis_url: &str, replica_url: Option<&str>, key_prefix: &str) -> Result<Self> {
+        match l2 {
+            CacheL2::Redis if key_prefix.is_empty() => Self::with_read_replica(redis_url, replica_url).await,
+            CacheL2::Redis => Self::with_key_prefix(redis_url, replica_url, key_prefix).await,
+            CacheL2::None => {
+                if replica_url.is_some() {
+                    warn!("REDIS_REPLICA_URL is ignored with CACHE_L2=none");
+                }
+                Self::in_memory().await
+            }
+        }
+    }
+
+    /// Use `ttl` for realtime entries (crypto prices, `latest_market_data`)
+    pub fn with_realtime_ttl(mut self, ttl: Duration) -> Self {
+        self.realtime_ttl = ttl;
+        self
+    }
+
+    /// Cache strategy for realtime entries, with this cache's `REALTIME_CACHE_TTL_SECONDS`
+    pub fn realtime_strategy(&self) -> cache_manager::CacheStrategy {
+        cache_manager::realtime_strategy(self.realtime_ttl)
+    }
+
+    /// Consumer groups on `redis_url` (the streams' Redis), namespaced like the streams
+    ///
+    /// Connects lazily, on the first group command.
+    fn redis_stream_groups(redis_url: &str, key_prefix: &str) -> Option<Arc<dyn StreamGroupBackend>> {
+        match RedisStreamGroups::new(redis_url) {
+            Ok(groups) => Some(Arc::new(NamespacedBackend::new(key_prefix, Arc::new(groups)))),
+            Err(e) => {
+                warn!(error = %e, "Stream consumer groups unavailable");
+                None
+            }
+        }
+    }
+
+    /// Connect the read replica, returning None (primary fallback) on failure
+    async fn connect_replica(replica_url: &str, key_prefix: &str) -> Option<LibraryCacheSystem> {
+        match Self::build_redis(replica_url, key_prefix).await {
+            Ok(replica) => {
+                println!("📖 Redis read replica enabled for cache reads");
+                Some(replica)
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to connect Redis read replica - reads will use the primary");
+                None
+            }
+        }
     }
 
     /// Health check for cache system
-    pub async fn health_check(&self) -> bool {
-        self.0.health_check().await
+    ///
+    /// Only the primary is required; an unhealthy replica is logged but not fatal.
+    /// Redis is probed with a short-lived write so the actual error (e.g. connection
+    /// refused) is returned instead of a bare `false`.
+    pub async fn health_check(&self) -> Result<()> {
+        if let Some(replica) = &self.replica {
+            if !replica.health_check().await {
+                warn!("Redis read replica health check failed");
+            }
+        }
+
+        if let Some(l1) = &self.primary.l1_cache {
+            if !l1.health_check().await {
+                anyhow::bail!("L1 in-memory cache health check failed");
+            }
+        }
+        if let Some(l2) = &self.primary.l2_cache {
+            l2.set_with_ttl(&namespaced_key(&self.key_prefix, HEALTH_PROBE_KEY), serde_json::json!(chrono::Utc::now().timestamp()), HEALTH_PROBE_TTL)
+                .await
+                .context("Redis health probe failed")?;
+        }
+        Ok(())
     }
 
     /// Direct access to cache manager (idiomatic accessor)
+    ///
+    /// Always the primary - use this for writes, streams and anything leader-side.
     pub fn cache_manager(&self) -> &Arc<CacheManager> {
-        &self.0.cache_manager
+        &self.primary.cache_manager
+    }
+
+    /// Cache manager for read-only lookups (e.g. follower cache reads)
+    ///
+    /// Returns the replica when configured, otherwise the primary.
+    pub fn read_cache_manager(&self) -> &Arc<CacheManager> {
+        match &self.replica {
+            Some(replica) => &replica.cache_manager,
+            None => &self.primary.cache_manager,
+        }
+    }
+
+    /// Create a consumer group on `stream_key` for entries added from now on
+    ///
+    /// `on_existing` decides what happens when the group is already there (e.g.
+    /// another follower created it first). See `consumer_group` for the two read modes.
+    pub async fn create_consumer_group(&self, stream_key: &str, group: &str, on_existing: ExistingGroup) -> Result<()> {
+        consumer_group::create_consumer_group(self.stream_groups()?, stream_key, group, on_existing).await
+    }
+
+    /// Run `handler` on `consumer`'s share of the group's entries (`XREADGROUP`),
+    /// acknowledging those it handled and first taking over entries left idle by others
+    pub async fn consume_group<F, Fut>(&self, consumer: &StreamGroupConsumer, handler: F) -> Result<ConsumeSummary>
+    where
+        F: FnMut(StreamEntry) -> Fut,
+        Fut: std::future::Future<Output = Result<()>>,
+    {
+        consumer.consume(self.stream_groups()?, handler).await
+    }
+
+    fn stream_groups(&self) -> Result<&dyn StreamGroupBackend> {
+        self.stream_groups.as_deref().context("Stream consumer groups are unavailable")
+    }
+
+    /// Whether reads are being served by a read replica
+    pub fn has_read_replica(&self) -> bool {
+        self.replica.is_some()
     }
 }
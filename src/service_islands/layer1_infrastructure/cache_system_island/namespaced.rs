@@ -0,0 +1,153 @@
+//! Namespaced Cache Backend
+//!
+//! Prepends `CACHE_KEY_PREFIX` to every key and stream name on its way to Redis,
+//! so independent deployments can share one Redis without clobbering each other.
+//! Wrapping the L2 and streaming backends keeps call sites on bare names like
+//! `latest_market_data`; L1 is process-local and needs no prefix.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use multi_tier_cache::{async_trait, CacheBackend, L2CacheBackend, StreamingBackend};
+
+use super::consumer_group::{StreamEntry, StreamGroupBackend};
+
+/// `<prefix>:<key>`, or `key` unchanged when no prefix is configured
+pub fn namespaced_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}:{}", prefix, key)
+    }
+}
+
+/// Backend wrapper that namespaces keys and stream names
+pub struct NamespacedBackend<B: ?Sized> {
+    prefix: String,
+    inner: Arc<B>,
+}
+
+impl<B: ?Sized> NamespacedBackend<B> {
+    /// Wrap `inner`, prefixing everything with `<prefix>:`
+    pub fn new(prefix: &str, inner: Arc<B>) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            inner,
+        }
+    }
+
+    fn key(&self, key: &str) -> String {
+        namespaced_key(&self.prefix, key)
+    }
+}
+
+#[async_trait]
+impl<B: CacheBackend + ?Sized> CacheBackend for NamespacedBackend<B> {
+    async fn get(&self, key: &str) -> Option<serde_json::Value> {
+        self.inner.get(&self.key(key)).await
+    }
+
+    async fn set_with_ttl(&self, key: &str, value: serde_json::Value, ttl: Duration) -> Result<()> {
+        self.inner.set_with_ttl(&self.key(key), value, ttl).await
+    }
+
+    async fn remove(&self, key: &str) -> Result<()> {
+        self.inner.remove(&self.key(key)).await
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+}
+
+#[async_trait]
+impl<B: L2CacheBackend + ?Sized> L2CacheBackend for NamespacedBackend<B> {
+    async fn get_with_ttl(&self, key: &str) -> Option<(serde_json::Value, Option<Duration>)> {
+        self.inner.get_with_ttl(&self.key(key)).await
+    }
+}
+
+#[async_trait]
+impl<B: StreamingBackend + ?Sized> StreamingBackend for NamespacedBackend<B> {
+    async fn stream_add(
+        &self,
+        stream_key: &str,
+        fields: Vec<(String, String)>,
+        maxlen: Option<usize>,
+    ) -> Result<String> {
+        self.inner.stream_add(&self.key(stream_key), fields, maxlen).await
+    }
+
+    async fn stream_read_latest(&self, stream_key: &str, count: usize) -> Result<Vec<(String, Vec<(String, String)>)>> {
+        self.inner.stream_read_latest(&self.key(stream_key), count).await
+    }
+
+    async fn stream_read(
+        &self,
+        stream_key: &str,
+        last_id: &str,
+        count: usize,
+        block_ms: Option<usize>,
+    ) -> Result<Vec<(String, Vec<(String, String)>)>> {
+        self.inner.stream_read(&self.key(stream_key), last_id, count, block_ms).await
+    }
+}
+
+#[async_trait]
+impl<B: StreamGroupBackend + ?Sized> StreamGroupBackend for NamespacedBackend<B> {
+    async fn group_create(&self, stream_key: &str, group: &str, start_id: &str) -> Result<bool> {
+        self.inner.group_create(&self.key(stream_key), group, start_id).await
+    }
+
+    async fn group_set_id(&self, stream_key: &str, group: &str, id: &str) -> Result<()> {
+        self.inner.group_set_id(&self.key(stream_key), group, id).await
+    }
+
+    async fn group_read(&self, stream_key: &str, group: &str, consumer: &str, count: usize) -> Result<Vec<StreamEntry>> {
+        self.inner.group_read(&self.key(stream_key), group, consumer, count).await
+    }
+
+    async fn group_ack(&self, stream_key: &str, group: &str, ids: &[String]) -> Result<usize> {
+        self.inner.group_ack(&self.key(stream_key), group, ids).await
+    }
+
+    async fn group_claim_idle(
+        &self,
+        stream_key: &str,
+        group: &str,
+        consumer: &str,
+        min_idle: Duration,
+        count: usize,
+    ) -> Result<Vec<StreamEntry>> {
+        self.inner.group_claim_idle(&self.key(stream_key), group, consumer, min_idle, count).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::memory_l2::MemoryL2Cache;
+
+    #[tokio::test]
+    async fn test_keys_and_streams_are_prefixed() {
+        let shared = Arc::new(MemoryL2Cache::new());
+        let tenant_a = NamespacedBackend::new("a", shared.clone());
+        let tenant_b = NamespacedBackend::new("b", shared.clone());
+
+        tenant_a.set_with_ttl("latest_market_data", serde_json::json!(1), Duration::from_secs(60)).await.unwrap();
+        tenant_b.set_with_ttl("latest_market_data", serde_json::json!(2), Duration::from_secs(60)).await.unwrap();
+        assert_eq!(tenant_a.get("latest_market_data").await, Some(serde_json::json!(1)));
+        assert_eq!(shared.get("b:latest_market_data").await, Some(serde_json::json!(2)));
+        assert_eq!(shared.get("latest_market_data").await, None);
+
+        tenant_a.stream_add("market_data_stream", vec![("data".into(), "{}".into())], None).await.unwrap();
+        assert_eq!(shared.stream_read_latest("a:market_data_stream", 10).await.unwrap().len(), 1);
+        assert!(tenant_b.stream_read_latest("market_data_stream", 10).await.unwrap().is_empty());
+        assert_eq!(namespaced_key("", "websocket:leader"), "websocket:leader");
+    }
+}
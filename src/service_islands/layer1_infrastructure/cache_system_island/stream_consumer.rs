@@ -0,0 +1,272 @@
+//! Market Data Stream Consumer
+//!
+//! Follower-side reader for `market_data_stream`. Remembers the id of the last
+//! entry it processed, so after a Redis blip the next read resumes right after
+//! it and replays whatever the leader published during the gap (at-least-once,
+//! as far back as the stream's MAXLEN retention reaches).
+//!
+//! Each entry carries the full dashboard as a `data` JSON field, plus a few
+//! top-level fields (`btc_price`, `timestamp`, `partial_failure`) that consumers
+//! such as `redis-cli XRANGE` can read without deserializing the whole blob.
+
+use anyhow::{bail, Context, Result};
+use parking_lot::Mutex;
+use tracing::{debug, warn};
+
+use super::CacheManager;
+
+/// Stream the leader publishes every aggregated dashboard to
+pub const MARKET_DATA_STREAM: &str = "market_data_stream";
+
+/// Approximate number of entries `market_data_stream` retains
+pub const MARKET_DATA_STREAM_MAXLEN: usize = 1000;
+
+/// Most entries replayed by a single read (the rest follow on the next one)
+const MAX_ENTRIES_PER_READ: usize = 50;
+
+/// One `market_data_stream` entry: summary fields plus the full dashboard
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketDataStreamEntry {
+    /// `btc_price_usd` of the dashboard (absent when BTC wasn't fetched)
+    pub btc_price: Option<f64>,
+    /// The dashboard's `timestamp` (RFC 3339)
+    pub timestamp: Option<String>,
+    /// Whether some data groups failed to fetch
+    pub partial_failure: bool,
+    /// Full dashboard, as published
+    pub data: serde_json::Value,
+}
+
+impl MarketDataStreamEntry {
+    /// Entry for an aggregated dashboard
+    pub fn from_dashboard(data: &serde_json::Value) -> Self {
+        Self {
+            btc_price: data["btc_price_usd"].as_f64(),
+            timestamp: data["timestamp"].as_str().map(str::to_string),
+            partial_failure: data["partial_failure"].as_bool().unwrap_or(false),
+            data: data.clone(),
+        }
+    }
+
+    /// Stream fields, `data` last (summary fields are omitted when unknown)
+    pub fn to_fields(&self) -> Result<Vec<(String, String)>> {
+        let mut fields = Vec::with_capacity(4);
+        if let Some(btc_price) = self.btc_price {
+            fields.push(("btc_price".to_string(), btc_price.to_string()));
+        }
+        if let Some(timestamp) = &self.timestamp {
+            fields.push(("timestamp".to_string(), timestamp.clone()));
+        }
+        fields.push(("partial_failure".to_string(), self.partial_failure.to_string()));
+        fields.push(("data".to_string(), serde_json::to_string(&self.data)?));
+        Ok(fields)
+    }
+
+    /// Parse stream fields; entries with only `data` (older leaders) get their
+    /// summary fields from the dashboard itself
+    pub fn from_fields(fields: Vec<(String, String)>) -> Result<Self> {
+        let mut summary = (None, None, None);
+        let mut data = None;
+        for (name, value) in fields {
+            match name.as_str() {
+                "btc_price" => summary.0 = value.parse::<f64>().ok(),
+                "timestamp" => summary.1 = Some(value),
+                "partial_failure" => summary.2 = value.parse::<bool>().ok(),
+                "data" => data = Some(value),
+                _ => {}
+            }
+        }
+
+        let data: serde_json::Value = serde_json::from_str(&data.context("entry has no data field")?)
+            .context("invalid data field")?;
+        let fallback = Self::from_dashboard(&data);
+        Ok(Self {
+            btc_price: summary.0.or(fallback.btc_price),
+            timestamp: summary.1.or(fallback.timestamp),
+            partial_failure: summary.2.unwrap_or(fallback.partial_failure),
+            data,
+        })
+    }
+}
+
+/// Parse a full stream id (`<ms>-<seq>`, both decimal)
+///
+/// Rejects anything else (bare `<ms>`, `-`/`+`, `$`, garbage), so only ids Redis
+/// could have issued reach it.
+pub fn parse_stream_id(id: &str) -> Result<(u64, u64)> {
+    let parsed = id.split_once('-').and_then(|(ms, seq)| {
+        let digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+        if !digits(ms) || !digits(seq) {
+            return None;
+        }
+        Some((ms.parse().ok()?, seq.parse().ok()?))
+    });
+    match parsed {
+        Some(id) if id != (0, 0) => Ok(id),
+        _ => bail!("Invalid stream id {:?} (expected <ms>-<seq>, e.g. 1700000000000-0)", id),
+    }
+}
+
+/// The `market_data_stream` entry with exactly this id (like `XRANGE id id`)
+///
+/// `Ok(None)` when no such entry exists, e.g. it was trimmed by MAXLEN retention.
+/// The stream backend only reads after an id, so this reads one entry after the
+/// id's predecessor and checks that it is the requested one.
+pub async fn read_entry(cache_manager: &CacheManager, id: &str) -> Result<Option<MarketDataStreamEntry>> {
+    let (ms, seq) = parse_stream_id(id)?;
+    let predecessor = match seq {
+        0 => format!("{}-{}", ms - 1, u64::MAX),
+        seq => format!("{}-{}", ms, seq - 1),
+    };
+
+    let entries = cache_manager.read_stream(MARKET_DATA_STREAM, &predecessor, 1, None).await?;
+    match entries.into_iter().next() {
+        Some((entry_id, fields)) if parse_stream_id(&entry_id).ok() == Some((ms, seq)) => {
+            MarketDataStreamEntry::from_fields(fields).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// The newest `market_data_stream` entry (`Ok(None)` when the stream is empty)
+pub async fn read_latest_entry(cache_manager: &CacheManager) -> Result<Option<MarketDataStreamEntry>> {
+    let entries = cache_manager.read_stream_latest(MARKET_DATA_STREAM, 1).await?;
+    entries
+        .into_iter()
+        .next()
+        .map(|(_, fields)| MarketDataStreamEntry::from_fields(fields))
+        .transpose()
+}
+
+/// Reads `market_data_stream` from the last processed entry onwards
+#[derive(Default)]
+pub struct MarketDataStreamConsumer {
+    last_id: Mutex<Option<String>>,
+}
+
+impl MarketDataStreamConsumer {
+    /// Create a consumer with no saved position (the first read starts at the latest entry)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of the last processed entry, if any
+    pub fn last_id(&self) -> Option<String> {
+        self.last_id.lock().clone()
+    }
+
+    /// Forget the saved position, so the next read starts at the latest entry again
+    ///
+    /// Called while leading: the position stops moving then, and resuming from it after
+    /// losing leadership would replay everything this instance published itself.
+    pub fn reset(&self) {
+        *self.last_id.lock() = None;
+    }
+
+    /// Entries published since the last read, oldest first
+    ///
+    /// A cold start (no saved id) returns only the latest entry, like `XREAD $` plus
+    /// the current tip. On error the saved id is kept, so the next read replays from it.
+    /// Entries with a missing or unreadable `data` field are skipped but still advance the position.
+    pub async fn read_new(&self, cache_manager: &CacheManager) -> Result<Vec<MarketDataStreamEntry>> {
+        let entries = match self.last_id() {
+            Some(last_id) => {
+                cache_manager
+                    .read_stream(MARKET_DATA_STREAM, &last_id, MAX_ENTRIES_PER_READ, None)
+                    .await?
+            }
+            None => cache_manager.read_stream_latest(MARKET_DATA_STREAM, 1).await?,
+        };
+
+        let Some((newest_id, _)) = entries.last() else {
+            return Ok(Vec::new());
+        };
+        *self.last_id.lock() = Some(newest_id.clone());
+        if entries.len() > 1 {
+            debug!(entries = entries.len(), "Replaying market_data_stream entries since the last read");
+        }
+
+        Ok(entries
+            .into_iter()
+            .filter_map(|(id, fields)| match MarketDataStreamEntry::from_fields(fields) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    warn!(%id, error = format!("{:#}", e), "Skipping unreadable market_data_stream entry");
+                    None
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use super::super::memory_l2::MemoryL2Cache;
+    use super::super::CacheSystemBuilder;
+
+    #[tokio::test]
+    async fn test_resumes_after_last_processed_entry() {
+        let l2 = Arc::new(MemoryL2Cache::new());
+        let cache = CacheSystemBuilder::new()
+            .with_l1(Arc::new(multi_tier_cache::L1Cache::new().await.unwrap()))
+            .with_l2(l2.clone())
+            .with_streams(l2)
+            .build()
+            .await
+            .unwrap();
+        let manager = cache.cache_manager();
+        let publish = |n: u32| {
+            let fields = MarketDataStreamEntry::from_dashboard(&serde_json::json!({ "btc_price_usd": n }))
+                .to_fields()
+                .unwrap();
+            manager.publish_to_stream(MARKET_DATA_STREAM, fields, Some(MARKET_DATA_STREAM_MAXLEN))
+        };
+        let prices = |entries: Vec<MarketDataStreamEntry>| entries.iter().map(|e| e.btc_price.unwrap()).collect::<Vec<_>>();
+
+        let consumer = MarketDataStreamConsumer::new();
+        assert!(consumer.read_new(manager).await.unwrap().is_empty());
+
+        publish(1).await.unwrap();
+        publish(2).await.unwrap();
+        // Cold start: only the latest entry
+        assert_eq!(prices(consumer.read_new(manager).await.unwrap()), vec![2.0]);
+
+        // Entries published while we weren't reading are replayed in order
+        publish(3).await.unwrap();
+        publish(4).await.unwrap();
+        assert_eq!(prices(consumer.read_new(manager).await.unwrap()), vec![3.0, 4.0]);
+        assert!(consumer.read_new(manager).await.unwrap().is_empty());
+
+        // After a reset (leadership), only the tip is read instead of everything since
+        publish(6).await.unwrap();
+        publish(7).await.unwrap();
+        consumer.reset();
+        assert_eq!(prices(consumer.read_new(manager).await.unwrap()), vec![7.0]);
+
+        // Exact lookup by id
+        let id = manager.publish_to_stream(MARKET_DATA_STREAM, MarketDataStreamEntry::from_dashboard(
+            &serde_json::json!({ "btc_price_usd": 5 })).to_fields().unwrap(), None).await.unwrap();
+        assert_eq!(read_entry(manager, &id).await.unwrap().unwrap().btc_price, Some(5.0));
+        assert!(read_entry(manager, "1-0").await.unwrap().is_none());
+        for invalid in ["", "$", "+", "123", "1-x", "-1", "0-0", "1-0; DEL x"] {
+            assert!(parse_stream_id(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_entry_fields_round_trip() {
+        let dashboard = serde_json::json!({ "btc_price_usd": 96000.5, "timestamp": "t", "partial_failure": true });
+        let entry = MarketDataStreamEntry::from_dashboard(&dashboard);
+        let fields = entry.to_fields().unwrap();
+        assert_eq!(fields[0], ("btc_price".to_string(), "96000.5".to_string()));
+        assert_eq!(MarketDataStreamEntry::from_fields(fields).unwrap(), entry);
+
+        // Data-only entries from older leaders still yield the summary fields
+        let legacy = vec![("data".to_string(), dashboard.to_string())];
+        assert_eq!(MarketDataStreamEntry::from_fields(legacy).unwrap(), entry);
+        assert!(MarketDataStreamEntry::from_fields(vec![("btc_price".to_string(), "1".to_string())]).is_err());
+    }
+}
@@ -1,11 +1,61 @@
 use anyhow::{Context, Result};
-use redis::{AsyncCommands, Client};
+use redis::aio::MultiplexedConnection;
+use redis::{Client, FromRedisValue, RedisError, ScriptInvocation};
+use std::collections::{HashSet, VecDeque};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 use tokio::time;
 use tracing::{debug, error, info, warn};
 
+use crate::service_islands::layer1_infrastructure::cache_system_island::namespaced::namespaced_key;
+
+/// Redis key of the leader lock (before any `CACHE_KEY_PREFIX`)
+const ELECTION_KEY: &str = "websocket:leader";
+
+/// Redis key claimed by the follower allowed to seed a cold cache (see `try_claim_cold_fetch`)
+const COLD_FETCH_KEY: &str = "websocket:cold_fetch";
+
+/// Redis key prefix of used admin request signatures (see `try_claim_admin_signature`)
+const ADMIN_SIGNATURE_KEY: &str = "websocket:admin_signature";
+
+/// Redis set of cluster-wide admin flags (see `set_shared_flag`)
+const SHARED_FLAGS_KEY: &str = "websocket:admin_flags";
+
+/// Connection attempts before a leader election operation gives up
+const RECONNECT_ATTEMPTS: u32 = 3;
+
+/// Base delay between reconnect attempts (doubled after each failure)
+const RECONNECT_BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// Sliding window over which leadership transitions are counted
+const FLAP_WINDOW: Duration = Duration::from_secs(60);
+
+/// More transitions than this within `FLAP_WINDOW` means leadership is flapping
+const FLAP_THRESHOLD: usize = 3;
+
+/// Recent leadership transitions (acquired or lost) of this node
+#[derive(Debug, Default)]
+struct FlapDetector {
+    transitions: VecDeque<Instant>,
+}
+
+impl FlapDetector {
+    /// Record a transition at `now`
+    fn record(&mut self, now: Instant) {
+        self.transitions.push_back(now);
+    }
+
+    /// Transitions within `FLAP_WINDOW` before `now` (older ones are dropped)
+    fn recent(&mut self, now: Instant) -> usize {
+        while self.transitions.front().is_some_and(|t| now.duration_since(*t) > FLAP_WINDOW) {
+            self.transitions.pop_front();
+        }
+        self.transitions.len()
+    }
+}
+
 /// Leader Election Service using Redis distributed locking
 ///
 /// This service implements a distributed leader election pattern using Redis SET NX EX.
@@ -20,21 +70,44 @@ use tracing::{debug, error, info, warn};
 /// # Failover:
 /// - Maximum failover time: TTL duration (10 seconds)
 /// - Typical failover time: 5-8 seconds
+///
+/// # Connection handling:
+/// - A single multiplexed connection is cached and shared by all heartbeats
+/// - Connection-level errors drop the cached connection and reconnect with backoff
+/// - A failed renewal keeps leadership while the last renewed lock can't have expired yet
 pub struct LeaderElectionService {
     /// Redis client for distributed locking
     redis_client: Client,
 
+    /// Cached multiplexed connection (None until (re)connected)
+    connection: Mutex<Option<MultiplexedConnection>>,
+
     /// Unique identifier for this node
     node_id: String,
 
     /// Redis key for the leader election lock
     election_key: String,
 
+    /// Redis key for the follower cold-cache fetch claim
+    cold_fetch_key: String,
+
+    /// Redis key prefix for used admin request signatures
+    admin_signature_key: String,
+
+    /// Redis key for cluster-wide admin flags
+    shared_flags_key: String,
+
     /// How often to check/renew leadership (seconds)
     heartbeat_interval: Duration,
 
     /// How long the lock is valid (seconds)
     lock_ttl: Duration,
+
+    /// No shared Redis (`CACHE_L2=none`): this node is always the leader
+    standalone: bool,
+
+    /// Leadership changed more than `FLAP_THRESHOLD` times within `FLAP_WINDOW`
+    leadership_unstable: AtomicBool,
 }
 
 impl LeaderElectionService {
@@ -45,11 +118,15 @@ impl LeaderElectionService {
     /// * `node_id` - Unique identifier for this node instance
     ///
     /// # Example
-    /// ```
+    /// ```no_run
+    /// # use web_server_report_websocket::service_islands::layer1_infrastructure::LeaderElectionService;
+    /// # async fn connect() -> anyhow::Result<()> {
     /// let service = LeaderElectionService::new(
     ///     "redis://localhost:6379",
     ///     "ws-instance-1".to_string()
     /// ).await?;
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn new(redis_url: &str, node_id: String) -> Result<Self> {
         let redis_client = Client::open(redis_url)
@@ -74,10 +151,40 @@ impl LeaderElectionService {
 
         Ok(Self {
             redis_client,
+            connection: Mutex::new(Some(conn)),
+            node_id,
+            election_key: ELECTION_KEY.to_string(),
+            cold_fetch_key: COLD_FETCH_KEY.to_string(),
+            admin_signature_key: ADMIN_SIGNATURE_KEY.to_string(),
+            shared_flags_key: SHARED_FLAGS_KEY.to_string(),
+            heartbeat_interval: Duration::from_secs(5),
+            lock_ttl: Duration::from_secs(10),
+            standalone: false,
+            leadership_unstable: AtomicBool::new(false),
+        })
+    }
+
+    /// Create a service that never contacts Redis and always leads
+    ///
+    /// For single-process runs without Redis (`CACHE_L2=none`); never use it with
+    /// more than one instance, since every standalone node fetches from the APIs.
+    pub fn standalone(node_id: String) -> Result<Self> {
+        info!("Leader election disabled (standalone) for node: {}", node_id);
+
+        Ok(Self {
+            // Never connected: standalone nodes skip every Redis call
+            redis_client: Client::open("redis://127.0.0.1:6379")
+                .context("Failed to create Redis client for leader election")?,
+            connection: Mutex::new(None),
             node_id,
-            election_key: "websocket:leader".to_string(),
+            election_key: ELECTION_KEY.to_string(),
+            cold_fetch_key: COLD_FETCH_KEY.to_string(),
+            admin_signature_key: ADMIN_SIGNATURE_KEY.to_string(),
+            shared_flags_key: SHARED_FLAGS_KEY.to_string(),
             heartbeat_interval: Duration::from_secs(5),
             lock_ttl: Duration::from_secs(10),
+            standalone: true,
+            leadership_unstable: AtomicBool::new(false),
         })
     }
 
@@ -87,28 +194,32 @@ impl LeaderElectionService {
     /// Returns true if this node successfully acquired leadership.
     ///
     /// # Example
-    /// ```
+    /// ```no_run
+    /// # use web_server_report_websocket::service_islands::layer1_infrastructure::LeaderElectionService;
+    /// # async fn acquire(service: LeaderElectionService) -> anyhow::Result<()> {
     /// if service.try_acquire_leadership().await? {
     ///     println!("I am the leader!");
     /// }
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn try_acquire_leadership(&self) -> Result<bool> {
-        let mut conn = self
-            .redis_client
-            .get_multiplexed_async_connection()
-            .await
-            .context("Failed to get Redis connection")?;
+        if self.standalone {
+            return Ok(true);
+        }
 
         // SET key value NX EX seconds
         // NX = Only set if key doesn't exist
         // EX = Set expiration time in seconds
-        let result: Option<String> = redis::cmd("SET")
-            .arg(&self.election_key)
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(&self.election_key)
             .arg(&self.node_id)
             .arg("NX") // Only set if not exists
             .arg("EX") // Set expiration
-            .arg(self.lock_ttl.as_secs())
-            .query_async(&mut conn)
+            .arg(self.lock_ttl.as_secs());
+
+        let result: Option<String> = self
+            .query(&cmd)
             .await
             .context("Failed to execute SET NX EX command")?;
 
@@ -123,18 +234,91 @@ impl LeaderElectionService {
         Ok(acquired)
     }
 
+    /// Claim the one follower fetch allowed per `ttl` while the cache is cold
+    ///
+    /// SET NX EX like the leader lock, but never renewed or released: the claim just
+    /// expires, so at most one follower across the cluster fetches per `ttl`.
+    pub async fn try_claim_cold_fetch(&self, ttl: Duration) -> Result<bool> {
+        self.try_claim(&self.cold_fetch_key, ttl)
+            .await
+            .context("Failed to claim the cold-cache fetch")
+    }
+
+    /// Claim an admin request signature for `ttl`, so no other instance accepts it again
+    ///
+    /// False when any node already claimed it (a replay). Standalone nodes have only
+    /// `AdminRequestVerifier`'s own memory and always succeed here.
+    pub async fn try_claim_admin_signature(&self, signature: &str, ttl: Duration) -> Result<bool> {
+        self.try_claim(&format!("{}:{}", self.admin_signature_key, signature), ttl)
+            .await
+            .context("Failed to claim the admin request signature")
+    }
+
+    /// Raise or clear a cluster-wide admin flag (e.g. a disabled data source)
+    ///
+    /// Admin requests reach whichever instance the load balancer picks, so switches
+    /// that must hold on every node (and survive a leader change) live in Redis.
+    /// `Ok(None)` on standalone nodes, which have no shared state; otherwise whether
+    /// the flag changed.
+    pub async fn set_shared_flag(&self, flag: &str, raised: bool) -> Result<Option<bool>> {
+        if self.standalone {
+            return Ok(None);
+        }
+
+        let mut cmd = redis::cmd(if raised { "SADD" } else { "SREM" });
+        cmd.arg(&self.shared_flags_key).arg(flag);
+        let changed: u32 = self
+            .query(&cmd)
+            .await
+            .context("Failed to update shared admin flags")?;
+        Ok(Some(changed > 0))
+    }
+
+    /// Currently raised cluster-wide admin flags (`None` on standalone nodes)
+    pub async fn shared_flags(&self) -> Result<Option<HashSet<String>>> {
+        if self.standalone {
+            return Ok(None);
+        }
+
+        let mut cmd = redis::cmd("SMEMBERS");
+        cmd.arg(&self.shared_flags_key);
+        let flags: HashSet<String> = self
+            .query(&cmd)
+            .await
+            .context("Failed to read shared admin flags")?;
+        Ok(Some(flags))
+    }
+
+    /// SET `key` NX EX `ttl` (at least 1s); true when this call created it
+    async fn try_claim(&self, key: &str, ttl: Duration) -> Result<bool> {
+        if self.standalone {
+            return Ok(true);
+        }
+
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(key)
+            .arg(&self.node_id)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl.as_secs().max(1));
+
+        let result: Option<String> = self.query(&cmd).await?;
+        Ok(result.is_some())
+    }
+
     /// Check if this node is currently the leader
     ///
     /// Returns true if the lock is held by this node.
     pub async fn is_leader(&self) -> Result<bool> {
-        let mut conn = self
-            .redis_client
-            .get_multiplexed_async_connection()
-            .await
-            .context("Failed to get Redis connection")?;
+        if self.standalone {
+            return Ok(true);
+        }
+
+        let mut cmd = redis::cmd("GET");
+        cmd.arg(&self.election_key);
 
-        let current_leader: Option<String> = conn
-            .get(&self.election_key)
+        let current_leader: Option<String> = self
+            .query(&cmd)
             .await
             .context("Failed to get leader from Redis")?;
 
@@ -148,11 +332,9 @@ impl LeaderElectionService {
     ///
     /// Returns true if leadership was successfully renewed.
     pub async fn renew_leadership(&self) -> Result<bool> {
-        let mut conn = self
-            .redis_client
-            .get_multiplexed_async_connection()
-            .await
-            .context("Failed to get Redis connection")?;
+        if self.standalone {
+            return Ok(true);
+        }
 
         // Lua script for atomic check-and-renew
         // Only extend TTL if we're still the owner
@@ -166,11 +348,11 @@ impl LeaderElectionService {
             "#,
         );
 
-        let result: i32 = script
-            .key(&self.election_key)
-            .arg(&self.node_id)
-            .arg(self.lock_ttl.as_secs())
-            .invoke_async(&mut conn)
+        let mut invocation = script.key(&self.election_key);
+        invocation.arg(&self.node_id).arg(self.lock_ttl.as_secs());
+
+        let result: i32 = self
+            .invoke(&invocation)
             .await
             .context("Failed to renew leadership")?;
 
@@ -189,12 +371,12 @@ impl LeaderElectionService {
     ///
     /// Deletes the lock if this node is the owner.
     /// Use this during graceful shutdown to allow faster failover.
+    /// Ownership is checked in Redis, so callers needn't trust a local leader flag,
+    /// and releasing twice is a no-op.
     pub async fn release_leadership(&self) -> Result<()> {
-        let mut conn = self
-            .redis_client
-            .get_multiplexed_async_connection()
-            .await
-            .context("Failed to get Redis connection")?;
+        if self.standalone {
+            return Ok(());
+        }
 
         // Lua script for atomic check-and-delete
         // Only delete if we own the lock
@@ -208,10 +390,11 @@ impl LeaderElectionService {
             "#,
         );
 
-        let result: i32 = script
-            .key(&self.election_key)
-            .arg(&self.node_id)
-            .invoke_async(&mut conn)
+        let mut invocation = script.key(&self.election_key);
+        invocation.arg(&self.node_id);
+
+        let result: i32 = self
+            .invoke(&invocation)
             .await
             .context("Failed to release leadership")?;
 
@@ -228,16 +411,23 @@ impl LeaderElectionService {
     ///
     /// This spawns a background task that:
     /// - Tries to acquire leadership every heartbeat_interval
-    /// - If leader, renews the lock periodically
+    /// - If leader, renews the lock periodically (clearing the flag first when the
+    ///   heartbeat ran later than the lock TTL)
     /// - Updates the is_leader_flag atomically
+    /// - Flags leadership as unstable (error log, `/health`) while it changes more than
+    ///   `FLAP_THRESHOLD` times per `FLAP_WINDOW`, a symptom of slow Redis
     ///
     /// # Arguments
     /// * `is_leader_flag` - Shared atomic boolean that tracks leadership status
     ///
     /// # Example
-    /// ```
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use std::sync::atomic::AtomicBool;
+    /// # use web_server_report_websocket::service_islands::layer1_infrastructure::LeaderElectionService;
+    /// # async fn monitor() -> anyhow::Result<()> {
     /// let is_leader = Arc::new(AtomicBool::new(false));
-    /// let service = Arc::new(LeaderElectionService::new(...).await?);
+    /// let service = Arc::new(LeaderElectionService::new("redis://localhost:6379", "ws-instance-1".to_string()).await?);
     ///
     /// tokio::spawn({
     ///     let service = service.clone();
@@ -246,6 +436,8 @@ impl LeaderElectionService {
     ///         service.monitor_leadership(is_leader).await;
     ///     }
     /// });
+    /// # Ok(())
+    /// # }
     /// ```
     pub async fn monitor_leadership(self: Arc<Self>, is_leader_flag: Arc<AtomicBool>) {
         info!(
@@ -255,29 +447,57 @@ impl LeaderElectionService {
 
         let mut interval = time::interval(self.heartbeat_interval);
 
+        // When the command that last set or extended this node's lock was sent
+        let mut last_renewal: Option<Instant> = None;
+        let mut flaps = FlapDetector::default();
+
         loop {
             interval.tick().await;
 
             let was_leader = is_leader_flag.load(Ordering::Relaxed);
 
+            // A tick arriving after the lock TTL (process paused, VM suspended) means the
+            // lock may already belong to another node: stop acting as leader right away
+            // rather than after the renew round trip
+            if was_leader && last_renewal.is_some_and(|t| t.elapsed() >= self.lock_ttl) {
+                warn!("⚠️ Leadership heartbeat ran late, lock may have expired; pausing leader work until renewed");
+                is_leader_flag.store(false, Ordering::Relaxed);
+            }
+
+            // Redis starts the TTL when it runs the command, somewhere during the round
+            // trip: count from before sending so this node never outlives the real lock
+            let sent_at = Instant::now();
+
             // Try to acquire or renew leadership
-            let is_leader = if was_leader {
+            let refreshed = if was_leader {
                 // Already leader - try to renew
-                match self.renew_leadership().await {
-                    Ok(renewed) => renewed,
-                    Err(e) => {
-                        error!("❌ Failed to renew leadership: {}", e);
-                        false
-                    }
-                }
+                self.renew_leadership().await
             } else {
                 // Not leader - try to acquire
-                match self.try_acquire_leadership().await {
-                    Ok(acquired) => acquired,
-                    Err(e) => {
-                        error!("❌ Failed to acquire leadership: {}", e);
-                        false
+                self.try_acquire_leadership().await
+            };
+
+            let is_leader = match refreshed {
+                Ok(true) => {
+                    last_renewal = Some(sent_at);
+                    true
+                }
+                Ok(false) => false,
+                Err(e) if was_leader => {
+                    // Redis unreachable, not a lost lock: the key we last renewed is still
+                    // ours until its TTL runs out, so don't fail over on a brief blip
+                    let lock_may_be_held = last_renewal
+                        .is_some_and(|t| t.elapsed() < self.lock_ttl);
+                    if lock_may_be_held {
+                        warn!("⚠️ Failed to renew leadership, keeping it until lock TTL: {}", e);
+                    } else {
+                        error!("❌ Failed to renew leadership: {}", e);
                     }
+                    lock_may_be_held
+                }
+                Err(e) => {
+                    error!("❌ Failed to acquire leadership: {}", e);
+                    false
                 }
             };
 
@@ -296,9 +516,107 @@ impl LeaderElectionService {
                     self.node_id
                 );
             }
+
+            let now = Instant::now();
+            if is_leader != was_leader {
+                flaps.record(now);
+            }
+            let transitions = flaps.recent(now);
+            let unstable = transitions > FLAP_THRESHOLD;
+            if unstable != self.leadership_unstable.swap(unstable, Ordering::Relaxed) {
+                if unstable {
+                    error!(
+                        "🚨 Leadership flapping detected: node {} changed leadership {} times in {}s (slow or overloaded Redis?)",
+                        self.node_id, transitions, FLAP_WINDOW.as_secs()
+                    );
+                } else {
+                    info!("✅ Leadership stable again on node {}", self.node_id);
+                }
+            }
+        }
+    }
+
+    /// Whether leadership is currently flapping (see `monitor_leadership`)
+    pub fn leadership_unstable(&self) -> bool {
+        self.leadership_unstable.load(Ordering::Relaxed)
+    }
+
+    /// Get the cached connection, reconnecting with backoff if there is none
+    async fn connection(&self) -> Result<MultiplexedConnection> {
+        let mut cached = self.connection.lock().await;
+        if let Some(conn) = cached.as_ref() {
+            return Ok(conn.clone());
+        }
+
+        let mut attempt = 0;
+        loop {
+            match self.redis_client.get_multiplexed_async_connection().await {
+                Ok(conn) => {
+                    if attempt > 0 {
+                        info!("🔌 Leader election reconnected to Redis after {} retries", attempt);
+                    }
+                    *cached = Some(conn.clone());
+                    return Ok(conn);
+                }
+                Err(e) => {
+                    attempt += 1;
+                    if attempt >= RECONNECT_ATTEMPTS {
+                        return Err(e).context("Failed to reconnect to Redis for leader election");
+                    }
+                    let delay = RECONNECT_BASE_DELAY * 2_u32.pow(attempt - 1);
+                    warn!(delay_ms = delay.as_millis(), attempt, "Redis reconnect failed, retrying: {}", e);
+                    time::sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    /// Whether an error means the connection itself is broken (vs. a command error)
+    fn is_connection_error(e: &RedisError) -> bool {
+        e.is_io_error() || e.is_connection_dropped() || e.is_timeout() || e.is_unrecoverable_error()
+    }
+
+    /// Drop the cached connection so the next call reconnects
+    async fn reset_connection(&self, e: &RedisError) {
+        warn!("🔌 Leader election Redis connection lost, reconnecting: {}", e);
+        *self.connection.lock().await = None;
+    }
+
+    /// Run a command on the cached connection, reconnecting once on connection errors
+    async fn query<T: FromRedisValue>(&self, cmd: &redis::Cmd) -> Result<T> {
+        let mut conn = self.connection().await?;
+        match cmd.query_async(&mut conn).await {
+            Err(e) if Self::is_connection_error(&e) => {
+                self.reset_connection(&e).await;
+                let mut conn = self.connection().await?;
+                Ok(cmd.query_async(&mut conn).await?)
+            }
+            result => Ok(result?),
         }
     }
 
+    /// Run a Lua script on the cached connection, reconnecting once on connection errors
+    async fn invoke<T: FromRedisValue>(&self, invocation: &ScriptInvocation<'_>) -> Result<T> {
+        let mut conn = self.connection().await?;
+        match invocation.invoke_async(&mut conn).await {
+            Err(e) if Self::is_connection_error(&e) => {
+                self.reset_connection(&e).await;
+                let mut conn = self.connection().await?;
+                Ok(invocation.invoke_async(&mut conn).await?)
+            }
+            result => Ok(result?),
+        }
+    }
+
+    /// Put the leader lock under `<key_prefix>:` (`CACHE_KEY_PREFIX`), like the cache keys
+    pub fn with_key_prefix(mut self, key_prefix: &str) -> Self {
+        self.election_key = namespaced_key(key_prefix, ELECTION_KEY);
+        self.cold_fetch_key = namespaced_key(key_prefix, COLD_FETCH_KEY);
+        self.admin_signature_key = namespaced_key(key_prefix, ADMIN_SIGNATURE_KEY);
+        self.shared_flags_key = namespaced_key(key_prefix, SHARED_FLAGS_KEY);
+        self
+    }
+
     /// Get the node ID
     pub fn node_id(&self) -> &str {
         &self.node_id
@@ -314,6 +632,20 @@ impl LeaderElectionService {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_flap_detector_counts_transitions_in_window() {
+        let start = Instant::now();
+        let mut flaps = FlapDetector::default();
+        for seconds in [0, 10, 20, 30] {
+            flaps.record(start + Duration::from_secs(seconds));
+        }
+        assert_eq!(flaps.recent(start + Duration::from_secs(30)), 4);
+        assert!(flaps.recent(start + Duration::from_secs(30)) > FLAP_THRESHOLD);
+
+        // The first two transitions fall out of the window
+        assert_eq!(flaps.recent(start + Duration::from_secs(75)), 2);
+    }
+
     #[tokio::test]
     #[ignore] // Requires Redis running
     async fn test_leader_election() {
@@ -339,4 +671,49 @@ mod tests {
         // Should no longer be leader
         assert!(!service.is_leader().await.unwrap());
     }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis running
+    async fn test_release_leadership_only_deletes_own_lock_and_is_repeatable() {
+        let leader = LeaderElectionService::new("redis://127.0.0.1:6379", "test-node-1".to_string())
+            .await
+            .unwrap()
+            .with_key_prefix("test_release_leadership");
+        let other = LeaderElectionService::new("redis://127.0.0.1:6379", "test-node-2".to_string())
+            .await
+            .unwrap()
+            .with_key_prefix("test_release_leadership");
+        assert!(leader.try_acquire_leadership().await.unwrap());
+
+        // A node that doesn't hold the lock leaves it alone
+        other.release_leadership().await.unwrap();
+        assert!(leader.is_leader().await.unwrap());
+
+        leader.release_leadership().await.unwrap();
+        assert!(!leader.is_leader().await.unwrap());
+        leader.release_leadership().await.unwrap();
+        assert!(other.try_acquire_leadership().await.unwrap());
+        other.release_leadership().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis running
+    async fn test_shared_flags() {
+        let service = LeaderElectionService::new("redis://127.0.0.1:6379", "test-node-1".to_string())
+            .await
+            .unwrap()
+            .with_key_prefix("test_shared_flags");
+
+        service.set_shared_flag("source_disabled:indices", false).await.unwrap();
+        assert_eq!(service.set_shared_flag("source_disabled:indices", true).await.unwrap(), Some(true));
+        assert_eq!(service.set_shared_flag("source_disabled:indices", true).await.unwrap(), Some(false));
+        assert!(service.shared_flags().await.unwrap().unwrap().contains("source_disabled:indices"));
+
+        assert_eq!(service.set_shared_flag("source_disabled:indices", false).await.unwrap(), Some(true));
+        assert!(service.shared_flags().await.unwrap().unwrap().is_empty());
+
+        let standalone = LeaderElectionService::standalone("test-node-2".to_string()).unwrap();
+        assert_eq!(standalone.set_shared_flag("source_disabled:indices", true).await.unwrap(), None);
+        assert_eq!(standalone.shared_flags().await.unwrap(), None);
+    }
 }
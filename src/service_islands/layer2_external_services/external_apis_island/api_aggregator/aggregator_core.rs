@@ -5,12 +5,24 @@
 use reqwest::Client;
 use anyhow::Result;
 use std::sync::Arc;
-use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use tokio::sync::Semaphore;
 use tracing::{info, debug, error};
 use crate::service_islands::layer2_external_services::external_apis_island::market_data_api::MarketDataApi;
+use crate::service_islands::layer2_external_services::external_apis_island::binance_ws_source::BinanceWsSource;
+use super::category_throttle::CategoryThrottle;
+use crate::config::CategoryIntervals;
+use super::last_good::LastGoodValues;
+use super::source_switches::SourceSwitches;
+use crate::service_islands::layer2_external_services::external_apis_island::price_vwap::PriceMode;
 use crate::service_islands::layer1_infrastructure::CacheSystemIsland;
 use crate::performance::OPTIMIZED_HTTP_CLIENT;
+use crate::dto::websocket::DashboardTimings;
 
+/// Default `MAX_CONCURRENT_FETCHES`: every data group of an aggregation at once
+pub const DEFAULT_MAX_CONCURRENT_FETCHES: usize = 5;
 
 /// API Aggregator
 ///
@@ -20,13 +32,49 @@ pub struct ApiAggregator {
     pub market_api: Arc<MarketDataApi>,
     pub client: Client,
     pub cache_system: Option<Arc<CacheSystemIsland>>,
+    // Live Binance prices (preferred over REST while connected)
+    pub binance_ws: Arc<BinanceWsSource>,
+    // Last values of slow-moving categories (FNG, RSI, indices) between refreshes
+    pub category_throttle: CategoryThrottle,
+    // Last successful value per dashboard field, substituted when a fetch fails
+    pub last_good: LastGoodValues,
+    // Data groups switched off at runtime (skipped, not failed)
+    pub source_switches: SourceSwitches,
+    // Single-source (Binance) or multi-exchange VWAP crypto prices
+    pub price_mode: PriceMode,
+    // Data group fetches allowed in flight at once, shared by overlapping aggregations
+    pub fetch_permits: RwLock<Arc<Semaphore>>,
     // Statistics
     pub total_aggregations: Arc<AtomicUsize>,
     pub successful_aggregations: Arc<AtomicUsize>,
     pub partial_failures: Arc<AtomicUsize>,
+    // Per-group timings of the most recent aggregation
+    pub last_timings: Mutex<Option<DashboardTimings>>,
+}
+
+/// Dashboard aggregation counters (see `ApiAggregator::aggregation_stats`)
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct AggregationStats {
+    pub total_aggregations: usize,
+    /// Aggregations where every data group was fetched
+    pub successful_aggregations: usize,
+    /// Aggregations missing at least one data group
+    pub partial_failures: usize,
+    /// Per-group fetch times of the most recent aggregation (None before the first)
+    pub last_timings: Option<DashboardTimings>,
 }
 
 impl ApiAggregator {
+    /// Current aggregation counters
+    pub fn aggregation_stats(&self) -> AggregationStats {
+        AggregationStats {
+            total_aggregations: self.total_aggregations.load(Ordering::Relaxed),
+            successful_aggregations: self.successful_aggregations.load(Ordering::Relaxed),
+            partial_failures: self.partial_failures.load(Ordering::Relaxed),
+            last_timings: *self.last_timings.lock(),
+        }
+    }
+
     /// Create a new ApiAggregator
     #[allow(dead_code)]
     pub async fn new(taapi_secret: String) -> Result<Self> {
@@ -56,9 +104,16 @@ impl ApiAggregator {
             market_api,
             client,
             cache_system: None, // Will be set by with_cache method
+            binance_ws: Arc::new(BinanceWsSource::new()),
+            category_throttle: CategoryThrottle::default(),
+            last_good: LastGoodValues::new(),
+            source_switches: SourceSwitches::new(),
+            price_mode: PriceMode::default(),
+            fetch_permits: RwLock::new(Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_FETCHES))),
             total_aggregations: Arc::new(AtomicUsize::new(0)),
             successful_aggregations: Arc::new(AtomicUsize::new(0)),
             partial_failures: Arc::new(AtomicUsize::new(0)),
+            last_timings: Mutex::new(None),
         })
     }
 
@@ -89,6 +144,33 @@ impl ApiAggregator {
         Ok(aggregator)
     }
 
+    /// Refresh FNG, RSI and US indices on these intervals instead of every aggregation
+    pub fn with_category_intervals(mut self, intervals: CategoryIntervals) -> Self {
+        self.category_throttle = CategoryThrottle::new(intervals);
+        self
+    }
+
+    /// Accept Binance multi-ticker responses with at least `min_symbols` of the dashboard coins
+    ///
+    /// Defaults to all of them; missing coins are logged and left out of the prices.
+    pub fn with_min_crypto_symbols(self, min_symbols: usize) -> Self {
+        self.market_api.min_crypto_symbols.store(min_symbols, std::sync::atomic::Ordering::Relaxed);
+        self
+    }
+
+    /// Source crypto prices according to `price_mode` (default: single-source Binance)
+    pub fn with_price_mode(mut self, price_mode: PriceMode) -> Self {
+        self.price_mode = price_mode;
+        self
+    }
+
+    /// Run at most `limit` data group fetches at once (at least 1)
+    ///
+    /// Aggregations already waiting keep the previous limit.
+    pub fn set_max_concurrent_fetches(&self, limit: usize) {
+        *self.fetch_permits.write() = Arc::new(Semaphore::new(limit.max(1)));
+    }
+
     /// Health check for API Aggregator
     pub async fn health_check(&self) -> bool {
         // Test that we can coordinate API calls
@@ -0,0 +1,110 @@
+//! Category Throttle Component
+//!
+//! Slow-moving data (Fear & Greed, RSI, US indices) is refreshed on its own,
+//! longer interval instead of every fetch tick. Between refreshes the aggregator
+//! reuses the last successful value without touching the cache or the API.
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tracing::debug;
+use super::aggregator_core::ApiAggregator;
+use crate::config::CategoryIntervals;
+
+/// Data groups refreshed on their own interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SlowCategory {
+    Fng,
+    Rsi,
+    Indices,
+}
+
+impl CategoryIntervals {
+    fn get(&self, category: SlowCategory) -> Duration {
+        match category {
+            SlowCategory::Fng => self.fng,
+            SlowCategory::Rsi => self.rsi,
+            SlowCategory::Indices => self.indices,
+        }
+    }
+}
+
+/// Last successful value and fetch time per slow category
+#[derive(Default)]
+pub struct CategoryThrottle {
+    intervals: CategoryIntervals,
+    last: Mutex<HashMap<SlowCategory, (Instant, serde_json::Value)>>,
+}
+
+impl CategoryThrottle {
+    /// Create a throttle with the given refresh intervals
+    pub fn new(intervals: CategoryIntervals) -> Self {
+        Self {
+            intervals,
+            last: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Last value, if it was fetched within the category's interval
+    fn fresh(&self, category: SlowCategory) -> Option<serde_json::Value> {
+        let interval = self.intervals.get(category);
+        self.last
+            .lock()
+            .get(&category)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < interval)
+            .map(|(_, value)| value.clone())
+    }
+
+    fn store(&self, category: SlowCategory, value: &serde_json::Value) {
+        self.last.lock().insert(category, (Instant::now(), value.clone()));
+    }
+}
+
+impl ApiAggregator {
+    /// Run `fetch` only if `category`'s last successful fetch is older than its interval
+    ///
+    /// `fetch` is lazy: when the last value is still fresh it is dropped unpolled.
+    pub(crate) async fn fetch_throttled<T, Fut>(&self, category: SlowCategory, fetch: Fut) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        Fut: Future<Output = Result<T>>,
+    {
+        if let Some(value) = self.category_throttle.fresh(category)
+            .and_then(|value| serde_json::from_value(value).ok())
+        {
+            debug!(?category, "Reusing last value until the category's next refresh");
+            return Ok(value);
+        }
+
+        let value = fetch.await?;
+        if let Ok(json) = serde_json::to_value(&value) {
+            self.category_throttle.store(category, &json);
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fresh_respects_interval() {
+        let throttle = CategoryThrottle::new(CategoryIntervals {
+            fng: Duration::from_secs(60),
+            rsi: Duration::ZERO,
+            indices: Duration::from_secs(60),
+        });
+        assert!(throttle.fresh(SlowCategory::Fng).is_none());
+
+        throttle.store(SlowCategory::Fng, &serde_json::json!({ "value": 10 }));
+        throttle.store(SlowCategory::Rsi, &serde_json::json!({ "value": 33.4 }));
+
+        assert_eq!(throttle.fresh(SlowCategory::Fng), Some(serde_json::json!({ "value": 10 })));
+        assert!(throttle.fresh(SlowCategory::Rsi).is_none());
+        assert!(throttle.fresh(SlowCategory::Indices).is_none());
+    }
+}
@@ -3,47 +3,129 @@
 //! This module contains all the cryptocurrency price fetching methods with caching.
 
 use anyhow::Result;
+use serde::Serialize;
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 use tracing::{info, debug, warn};
 use super::aggregator_core::ApiAggregator;
+use crate::dto::MarketUpdatePayload;
+use crate::service_islands::layer1_infrastructure::cache_system_island::cache_manager::{CacheManager, CacheStrategy};
+use crate::service_islands::layer2_external_services::external_apis_island::market_data_api::MarketDataApi;
+use crate::service_islands::layer2_external_services::external_apis_island::price_vwap::PriceMode;
+
+/// Fetch prices from the REST API(s) for `price_mode`, as per-coin JSON entries
+///
+/// VWAP entries also carry each exchange's price under `exchanges`.
+async fn fetch_price_entries(market_api: &MarketDataApi, price_mode: PriceMode) -> Result<HashMap<String, serde_json::Value>> {
+    let last_updated = chrono::Utc::now().to_rfc3339();
+    let mut result = HashMap::new();
+
+    match price_mode {
+        PriceMode::Single => {
+            for (coin, (price_usd, change_24h)) in market_api.fetch_multi_crypto_prices().await? {
+                result.insert(coin, serde_json::json!({
+                    "price_usd": price_usd,
+                    "change_24h": change_24h,
+                    "source": "binance",
+                    "last_updated": last_updated
+                }));
+            }
+        }
+        PriceMode::Vwap { max_deviation_percent } => {
+            for (coin, vwap) in market_api.fetch_vwap_crypto_prices(max_deviation_percent).await? {
+                result.insert(coin, serde_json::json!({
+                    "price_usd": vwap.price,
+                    "change_24h": vwap.change_24h,
+                    "source": "vwap",
+                    "exchanges": vwap.exchanges,
+                    "last_updated": last_updated
+                }));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Run `fetch` and overwrite `cache_key` (with `strategy`) with the result, ignoring anything cached
+///
+/// The force-refresh and live-price paths: the value is always fetched (or taken from
+/// the stream), even if the entry was written a moment ago. A failed cache write is
+/// logged; the fetched value is still returned.
+async fn fetch_and_overwrite<T, Fut>(cache_manager: &CacheManager, cache_key: &str, strategy: CacheStrategy, fetch: Fut) -> Result<T>
+where
+    T: Serialize,
+    Fut: Future<Output = Result<T>>,
+{
+    let value = fetch.await?;
+    match serde_json::to_value(&value) {
+        Ok(json) => {
+            if let Err(e) = cache_manager.set_with_strategy(cache_key, json, strategy).await {
+                warn!(cache_key, "Failed to cache refreshed value: {}", e);
+            }
+        }
+        Err(e) => warn!(cache_key, "Failed to serialize refreshed value: {}", e),
+    }
+    Ok(value)
+}
 
 impl ApiAggregator {
     /// Fetch all crypto prices with type-safe automatic caching
     ///
+    /// Prefers fresh prices from the Binance WebSocket stream when it's running
+    /// (single-source mode only); they are written through to the cache like REST
+    /// results, so cache readers (followers, symbol snapshots) see them too.
+    ///
     /// ✨ NEW: Uses get_or_compute_typed() for automatic caching
     ///
     /// Returns HashMap with coin symbols as keys: BTC, ETH, SOL, XRP, ADA, LINK, BNB
     /// Each value is a JSON object with price_usd and change_24h
     ///
-    /// force_refresh: If true, bypasses cache and forces API fetch, then updates cache
+    /// force_refresh: If true, always calls the REST API (never a cached entry or live
+    /// stream prices, however fresh) and overwrites the cache with the result. A normal
+    /// call that was already computing when the force refresh finished may overwrite it
+    /// with its own (equally fresh) API result.
+    ///
+    /// Cached entries live for `REALTIME_CACHE_TTL_SECONDS` (default 5s).
     pub async fn fetch_all_crypto_prices_with_cache(&self, force_refresh: bool) -> Result<HashMap<String, serde_json::Value>> {
         let cache_key = "multi_crypto_prices_realtime";
 
+        // Live stream prices need no HTTP call (REST below is the fallback).
+        // The stream is Binance-only, so VWAP mode always goes to REST.
+        if let Some(live_prices) = self.binance_ws.latest_prices()
+            .filter(|_| self.price_mode == PriceMode::Single && !force_refresh)
+        {
+            debug!("Using live Binance WebSocket prices");
+            let last_updated = chrono::Utc::now().to_rfc3339();
+            let mut result = HashMap::new();
+            for (coin, (price_usd, change_24h)) in live_prices {
+                result.insert(coin, serde_json::json!({
+                    "price_usd": price_usd,
+                    "change_24h": change_24h,
+                    "source": "binance_ws",
+                    "last_updated": last_updated
+                }));
+            }
+
+            if let Some(ref cache) = self.cache_system {
+                return fetch_and_overwrite(&cache.cache_manager, cache_key, cache.realtime_strategy(), async { Ok(result) }).await;
+            }
+            return Ok(result);
+        }
+
         // Handle force refresh: bypass cache and update
         if force_refresh {
             if let Some(ref cache) = self.cache_system {
                 info!("Force refresh - fetching fresh crypto prices from API");
 
-                // Fetch from API
-                let raw_data = self.market_api.fetch_multi_crypto_prices().await?;
-
-                // Convert HashMap<String, (f64, f64)> to HashMap<String, serde_json::Value>
-                let mut result = HashMap::new();
-                for (coin, (price_usd, change_24h)) in raw_data {
-                    result.insert(coin, serde_json::json!({
-                        "price_usd": price_usd,
-                        "change_24h": change_24h,
-                        "source": "binance",
-                        "last_updated": chrono::Utc::now().to_rfc3339()
-                    }));
-                }
-
-                // Update cache
-                let cache_value = serde_json::to_value(&result).unwrap_or(serde_json::json!({}));
-                let _ = cache.cache_manager.set_with_strategy(cache_key, cache_value,
-                    crate::service_islands::layer1_infrastructure::cache_system_island::cache_manager::realtime_strategy()).await;
-                debug!("All crypto prices cached after force refresh (RealTime - 30s TTL)");
+                let result = fetch_and_overwrite(
+                    &cache.cache_manager,
+                    cache_key,
+                    cache.realtime_strategy(),
+                    fetch_price_entries(&self.market_api, self.price_mode),
+                ).await?;
+                debug!("All crypto prices cached after force refresh (realtime TTL)");
 
                 return Ok(result);
             }
@@ -52,24 +134,14 @@ impl ApiAggregator {
         // Normal flow: Use type-safe caching
         if let Some(ref cache) = self.cache_system {
             let market_api = Arc::clone(&self.market_api);
+            let price_mode = self.price_mode;
 
-                match cache.cache_manager.get_or_compute_typed(
+            match cache.cache_manager.get_or_compute_typed(
                 cache_key,
-                crate::service_islands::layer1_infrastructure::cache_system_island::cache_manager::realtime_strategy(),
+                cache.realtime_strategy(),
                 || async move {
                     debug!("Fetching all crypto prices from API");
-                    let raw_data = market_api.fetch_multi_crypto_prices().await?;
-
-                    // Convert HashMap<String, (f64, f64)> to HashMap<String, serde_json::Value>
-                    let mut result = HashMap::new();
-                    for (coin, (price_usd, change_24h)) in raw_data {
-                        result.insert(coin, serde_json::json!({
-                            "price_usd": price_usd,
-                            "change_24h": change_24h,
-                            "source": "binance",
-                            "last_updated": chrono::Utc::now().to_rfc3339()
-                        }));
-                    }
+                    let result = fetch_price_entries(&market_api, price_mode).await?;
 
                     debug!("All crypto prices fetched and ready for caching");
                     Ok(result)
@@ -84,18 +156,90 @@ impl ApiAggregator {
         } else {
             // No cache system - direct API call
             warn!("No cache system - calling API directly");
-            let raw_data = self.market_api.fetch_multi_crypto_prices().await?;
+            fetch_price_entries(&self.market_api, self.price_mode).await
+        }
+    }
 
-            let mut result = HashMap::new();
-            for (coin, (price_usd, change_24h)) in raw_data {
-                result.insert(coin, serde_json::json!({
-                    "price_usd": price_usd,
-                    "change_24h": change_24h,
-                    "source": "binance",
-                    "last_updated": chrono::Utc::now().to_rfc3339()
-                }));
-            }
-            Ok(result)
+    /// Latest price for one coin (e.g. "BTC"), without running a dashboard aggregation
+    ///
+    /// Served from the live Binance stream when it's running, otherwise from the
+    /// `multi_crypto_prices_realtime` cache. Never calls an upstream API; `None`
+    /// if the symbol is unknown or nothing is cached yet.
+    pub async fn get_symbol_price(&self, symbol: &str) -> Option<MarketUpdatePayload> {
+        let symbol = symbol.to_uppercase();
+
+        if let Some((price, change_24h)) = self.binance_ws.latest_prices()
+            .and_then(|prices| prices.get(&symbol).copied())
+        {
+            return Some(MarketUpdatePayload {
+                symbol,
+                price,
+                change_24h,
+                volume: None,
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            });
         }
+
+        let cache = self.cache_system.as_ref()?;
+        let prices = match cache.read_cache_manager().get("multi_crypto_prices_realtime").await {
+            Ok(prices) => prices?,
+            Err(e) => {
+                warn!(error = %e, "Failed to read cached crypto prices");
+                return None;
+            }
+        };
+
+        let entry = prices.get(&symbol)?;
+        let timestamp = entry.get("last_updated")
+            .and_then(|v| v.as_str())
+            .and_then(|v| chrono::DateTime::parse_from_rfc3339(v).ok())
+            .map(|t| t.timestamp_millis())
+            .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+
+        Some(MarketUpdatePayload {
+            symbol,
+            price: entry.get("price_usd")?.as_f64()?,
+            change_24h: entry.get("change_24h").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            volume: None,
+            timestamp,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use crate::service_islands::layer1_infrastructure::cache_system_island::cache_manager::realtime_strategy;
+    use crate::service_islands::layer1_infrastructure::cache_system_island::memory_l2::MemoryL2Cache;
+    use crate::service_islands::layer1_infrastructure::cache_system_island::CacheSystemBuilder;
+
+    #[tokio::test]
+    async fn test_force_refresh_ignores_a_fresh_entry() {
+        let l2 = Arc::new(MemoryL2Cache::new());
+        let cache = CacheSystemBuilder::new()
+            .with_l1(Arc::new(multi_tier_cache::L1Cache::new().await.unwrap()))
+            .with_l2(l2)
+            .build()
+            .await
+            .unwrap();
+        let manager = &cache.cache_manager;
+        let strategy = || realtime_strategy(std::time::Duration::from_secs(5));
+        manager.set_with_strategy("prices", serde_json::json!({ "BTC": 1 }), strategy()).await.unwrap();
+
+        let api_calls = AtomicUsize::new(0);
+        let fetch = || async {
+            api_calls.fetch_add(1, Ordering::SeqCst);
+            Ok(HashMap::from([("BTC".to_string(), 2)]))
+        };
+
+        let forced: HashMap<String, i32> = fetch_and_overwrite(manager, "prices", strategy(), fetch()).await.unwrap();
+        assert_eq!(forced["BTC"], 2);
+        assert_eq!(api_calls.load(Ordering::SeqCst), 1);
+
+        // Normal reads now see the forced value without another API call
+        let cached: HashMap<String, i32> = manager.get_or_compute_typed("prices", strategy(), fetch).await.unwrap();
+        assert_eq!(cached["BTC"], 2);
+        assert_eq!(api_calls.load(Ordering::SeqCst), 1);
     }
 }
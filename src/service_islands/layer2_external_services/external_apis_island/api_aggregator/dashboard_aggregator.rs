@@ -4,48 +4,260 @@
 //! multiple API calls concurrently and handles error processing.
 
 use anyhow::Result;
+use std::future::Future;
 use std::sync::atomic::Ordering;
+use tokio::sync::Semaphore;
 use tokio::time::{timeout, Duration};
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 use super::aggregator_core::ApiAggregator;
+use super::category_throttle::SlowCategory;
+use crate::dto::websocket::DashboardTimings;
+use crate::service_islands::layer2_external_services::external_apis_island::market_data_api::DASHBOARD_SYMBOLS;
+
+/// Data groups without which the dashboard is not worth serving (sets `critical_failure`)
+///
+/// The other groups (`fng`, `rsi`, `indices`) are cosmetic: missing them only sets `partial_failure`.
+pub const CRITICAL_DATA_GROUPS: &[&str] = &["crypto", "global"];
+
+/// Fields of the non-crypto data groups, for last-good substitution
+const GROUP_FIELDS: &[(&str, &[&str])] = &[
+    ("global", &[
+        "market_cap_usd",
+        "volume_24h_usd",
+        "market_cap_change_percentage_24h_usd",
+        "btc_market_cap_percentage",
+        "eth_market_cap_percentage",
+    ]),
+    ("fng", &["fng_value", "fng_classification"]),
+    ("rsi", &["btc_rsi_14"]),
+    ("indices", &["us_stock_indices"]),
+];
+
+/// Dashboard fields that change on every aggregation regardless of market data
+const VOLATILE_DASHBOARD_FIELDS: &[&str] = &[
+    "content_hash",
+    "fetch_duration_ms",
+    "last_updated",
+    "timestamp",
+    "timings",
+];
+
+/// Run `future`, returning its output and how long it took in milliseconds
+async fn timed<F: Future>(future: F) -> (F::Output, u64) {
+    let started = std::time::Instant::now();
+    let output = future.await;
+    (output, started.elapsed().as_millis() as u64)
+}
+
+/// Build and run a future with `start` once a permit from `permits` is free
+///
+/// The future (and any timeout in it) only starts after the permit is acquired, and the
+/// permit is held until it completes; a closed semaphore runs it unlimited.
+async fn limited<F: Future>(permits: &Semaphore, start: impl FnOnce() -> F) -> F::Output {
+    let _permit = permits.acquire().await.ok();
+    start().await
+}
+
+/// Run `future` unless `enabled` is false (then it is dropped unpolled)
+async fn unless_disabled<F: Future>(enabled: bool, future: F) -> Option<F::Output> {
+    match enabled {
+        true => Some(future.await),
+        false => None,
+    }
+}
+
+/// `value`, or 0.0 with `field` recorded in `non_finite` when it is NaN or infinite
+///
+/// serde_json serializes non-finite numbers as `null` without complaint, which the
+/// frontend can't tell apart from a missing value.
+fn finite_or_flag(field: &str, value: f64, non_finite: &mut Vec<String>) -> f64 {
+    if value.is_finite() {
+        return value;
+    }
+    warn!(field, %value, "Non-finite dashboard value, treating it as a failed fetch");
+    non_finite.push(field.to_string());
+    0.0
+}
+
+/// Stable hash of a dashboard summary's market fields (hex FNV-1a 64)
+///
+/// Timestamps and fetch duration are excluded, so two aggregations with identical
+/// prices hash the same and downstream change detection can skip redundant updates.
+pub fn dashboard_content_hash(data: &serde_json::Value) -> String {
+    let mut market_fields = data.clone();
+    if let Some(obj) = market_fields.as_object_mut() {
+        for field in VOLATILE_DASHBOARD_FIELDS {
+            obj.remove(*field);
+        }
+    }
+
+    // FNV-1a: stable across processes and Rust versions, unlike DefaultHasher
+    let hash = canonical_json_string(&market_fields)
+        .bytes()
+        .fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    format!("{:016x}", hash)
+}
+
+/// Serialize `value` with object keys sorted at every level
+///
+/// `serde_json::Map` iteration order depends on crate features (`preserve_order`
+/// keeps insertion order), so hashes are computed over this canonical form instead
+/// of `to_string()`.
+pub fn canonical_json_string(value: &serde_json::Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::from(key.as_str()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// Dashboard coins absent from a successful price fetch (they are zero-filled)
+fn missing_symbols<V>(prices: &std::collections::HashMap<String, V>) -> Vec<&'static str> {
+    DASHBOARD_SYMBOLS.iter()
+        .copied()
+        .filter(|symbol| !prices.contains_key(*symbol))
+        .collect()
+}
+
+/// `sources` entry for one data group: where the value came from and whether it was fetched
+///
+/// `fetched` is `Some(source)` on success; a `None` source (cached values written before
+/// fetchers tagged one) falls back to `default_source`.
+fn source_status(fetched: Option<Option<&str>>, default_source: &str) -> serde_json::Value {
+    match fetched {
+        Some(source) => serde_json::json!({
+            "source": source.unwrap_or(default_source),
+            "ok": true,
+        }),
+        None => serde_json::json!({ "source": null, "ok": false }),
+    }
+}
 
 impl ApiAggregator {
     /// Fetch dashboard summary v2 - Main method for Layer 2 dashboard data
     /// Returns a focused summary with essential market data
     /// 
     /// force_realtime_refresh: If true, forces refresh of RealTime cached data (crypto prices)
+    ///
+    /// Fields whose fetch failed carry their last successful value (when there is one)
+    /// and are listed in `stale_fields`; `partial_failure` and `sources` still report the failure.
+    ///
+    /// At most `MAX_CONCURRENT_FETCHES` groups are fetched at once. Each group's 8s timeout
+    /// is only created once it holds a permit, so time spent queueing never counts against
+    /// it; with a limit of 1 an aggregation can therefore take up to five timeouts (40s).
+    ///
+    /// All fetchers share one retry budget (`MAX_RETRIES_PER_TICK`), refilled here; once it
+    /// is spent, calls fail on their next rate limit or error instead of backing off again.
     pub async fn fetch_dashboard_summary_v2(&self, force_realtime_refresh: bool) -> Result<serde_json::Value> {
         let start_time = std::time::Instant::now();
         self.total_aggregations.fetch_add(1, Ordering::Relaxed);
 
         info!("Starting dashboard summary v2 aggregation");
 
+        let permits = self.fetch_permits.read().clone();
+        self.market_api.retry_budget.refill();
+
+        // Groups switched off by an operator are skipped entirely. The switches are read
+        // once, so a toggle mid-aggregation can't skip a group and then report it as failed
+        let disabled_groups = self.source_switches.disabled();
+        let enabled = |group: &str| !disabled_groups.contains(&group);
+        if !disabled_groups.is_empty() {
+            debug!(?disabled_groups, "Skipping disabled data sources");
+        }
+
         // Fetch essential data concurrently with shorter timeouts for summary
         // OPTIMIZED: Single multi-crypto API call instead of 7 individual calls
-        let multi_crypto_future = timeout(Duration::from_secs(8), self.fetch_all_crypto_prices_with_cache(force_realtime_refresh));
-        let global_future = timeout(Duration::from_secs(8), self.fetch_global_with_cache());
-        let fng_future = timeout(Duration::from_secs(8), self.fetch_fng_with_cache());
-        let btc_rsi_14_future = timeout(Duration::from_secs(8), self.fetch_btc_rsi_14_with_cache());
-        let us_indices_future = timeout(Duration::from_secs(8), self.fetch_us_indices_with_cache());
-
-        let (multi_crypto_result, global_result, fng_result, btc_rsi_14_result, us_indices_result) = tokio::join!(
-            multi_crypto_future,
-            global_future, fng_future, btc_rsi_14_future, us_indices_future
+        // Each group is timed on its own so a slow aggregation can be pinned on one provider;
+        // its timeout and timing start once it holds a fetch permit, so queueing isn't counted
+        let fetch_timeout = Duration::from_secs(8);
+        let (
+            (multi_crypto_result, crypto_ms),
+            (global_result, global_ms),
+            (fng_result, fng_ms),
+            (btc_rsi_14_result, rsi_ms),
+            (us_indices_result, indices_ms),
+        ) = tokio::join!(
+            limited(&permits, || timed(unless_disabled(enabled("crypto"),
+                timeout(fetch_timeout, self.fetch_all_crypto_prices_with_cache(force_realtime_refresh))))),
+            limited(&permits, || timed(unless_disabled(enabled("global"),
+                timeout(fetch_timeout, self.fetch_global_with_cache())))),
+            // Slow-moving data is only refreshed on its own interval (see category_throttle)
+            limited(&permits, || timed(unless_disabled(enabled("fng"),
+                timeout(fetch_timeout, self.fetch_throttled(SlowCategory::Fng, self.fetch_fng_with_cache()))))),
+            limited(&permits, || timed(unless_disabled(enabled("rsi"),
+                timeout(fetch_timeout, self.fetch_throttled(SlowCategory::Rsi, self.fetch_btc_rsi_14_with_cache()))))),
+            limited(&permits, || timed(unless_disabled(enabled("indices"),
+                timeout(fetch_timeout, self.fetch_throttled(SlowCategory::Indices, self.fetch_us_indices_with_cache())))))
         );
+        let timings = DashboardTimings { crypto_ms, global_ms, fng_ms, rsi_ms, indices_ms };
+        *self.last_timings.lock() = Some(timings);
 
         let mut partial_failure = false;
+        let mut sources = serde_json::Map::new();
 
         // Process multi-crypto data (all 7 coins in one result)
         let mut crypto_prices = std::collections::HashMap::new();
+        let mut missing_coins = Vec::new();
         match multi_crypto_result {
-            Ok(Ok(prices_map)) => {
+            Some(Ok(Ok(prices_map))) => {
                 crypto_prices = prices_map;
+                // Fewer coins than configured (but at least BINANCE_MIN_SYMBOLS) still succeeds
+                missing_coins = missing_symbols(&crypto_prices);
+                if !missing_coins.is_empty() {
+                    partial_failure = true;
+                    warn!(missing = ?missing_coins, "Crypto prices fetched without some coins");
+                }
             }
+            None => {}
             _ => {
                 partial_failure = true;
                 warn!("Multi-crypto prices fetch failed");
             }
         }
+        let crypto_source = crypto_prices.get("BTC").or_else(|| crypto_prices.values().next());
+        let mut crypto_status = source_status(crypto_source.map(|data| data["source"].as_str()), "binance");
+        // VWAP mode: each coin's per-exchange prices
+        let exchanges: serde_json::Map<String, serde_json::Value> = crypto_prices.iter()
+            .filter_map(|(coin, data)| Some((coin.clone(), data.get("exchanges")?.clone())))
+            .collect();
+        if !exchanges.is_empty() {
+            crypto_status["exchanges"] = serde_json::Value::Object(exchanges);
+        }
+        if !missing_coins.is_empty() {
+            crypto_status["missing"] = serde_json::json!(missing_coins);
+        }
+        sources.insert("crypto".to_string(), crypto_status);
 
         // Helper structure for price data
         #[derive(Debug, Clone, Copy)]
@@ -57,7 +269,8 @@ impl ApiAggregator {
         impl CryptoPrice {
             fn from_json(data: &serde_json::Value) -> Self {
                 Self {
-                    price_usd: data["price_usd"].as_f64().unwrap_or(0.0),
+                    // A NaN price serialized by a fetcher arrives as null
+                    price_usd: data["price_usd"].as_f64().unwrap_or(f64::NAN),
                     change_24h: data["change_24h"].as_f64().unwrap_or(0.0),
                 }
             }
@@ -70,11 +283,20 @@ impl ApiAggregator {
             }
         }
 
+        // Non-finite numbers (e.g. a NaN out of VWAP) count as failed fetches: they are
+        // zeroed, then replaced by the last good value and listed in `stale_fields`
+        let mut non_finite: Vec<String> = Vec::new();
+
         // Extract price data once for each symbol
-        let get_price = |symbol: &str| -> CryptoPrice {
-            crypto_prices.get(symbol)
+        let mut get_price = |symbol: &str| -> CryptoPrice {
+            let price = crypto_prices.get(symbol)
                 .map(CryptoPrice::from_json)
-                .unwrap_or_else(CryptoPrice::default)
+                .unwrap_or_else(CryptoPrice::default);
+            let coin = symbol.to_lowercase();
+            CryptoPrice {
+                price_usd: finite_or_flag(&format!("{}_price_usd", coin), price.price_usd, &mut non_finite),
+                change_24h: finite_or_flag(&format!("{}_change_24h", coin), price.change_24h, &mut non_finite),
+            }
         };
 
         // Extract individual coin data
@@ -94,51 +316,76 @@ impl ApiAggregator {
         let (bnb_price, bnb_change) = (bnb.price_usd, bnb.change_24h);
 
         // Process global data
-        let (market_cap, volume_24h, market_cap_change, btc_dominance, eth_dominance) = match global_result {
-            Ok(Ok(global_data)) => (
-                global_data["market_cap"].as_f64().unwrap_or(0.0),
-                global_data["volume_24h"].as_f64().unwrap_or(0.0),
-                global_data["market_cap_change_percentage_24h_usd"].as_f64().unwrap_or(0.0),
-                global_data["btc_market_cap_percentage"].as_f64().unwrap_or(0.0),
-                global_data["eth_market_cap_percentage"].as_f64().unwrap_or(0.0)
+        let global_data = global_result.and_then(Result::ok).and_then(Result::ok);
+        sources.insert("global".to_string(), source_status(global_data.as_ref().map(|data| data.source.as_deref()), "coingecko"));
+        let (market_cap, volume_24h, market_cap_change, btc_dominance, eth_dominance) = match global_data {
+            Some(global_data) => (
+                finite_or_flag("market_cap_usd", global_data.market_cap, &mut non_finite),
+                finite_or_flag("volume_24h_usd", global_data.volume_24h, &mut non_finite),
+                finite_or_flag("market_cap_change_percentage_24h_usd", global_data.market_cap_change_percentage_24h_usd, &mut non_finite),
+                finite_or_flag("btc_market_cap_percentage", global_data.btc_market_cap_percentage, &mut non_finite),
+                global_data.eth_market_cap_percentage
+                    .map(|value| finite_or_flag("eth_market_cap_percentage", value, &mut non_finite)),
             ),
-            _ => {
-                partial_failure = true;
-                (0.0, 0.0, 0.0, 0.0, 0.0)
+            None => {
+                partial_failure |= enabled("global");
+                (0.0, 0.0, 0.0, 0.0, None)
             }
         };
 
         // Process FNG data
-        let fng_value = match fng_result {
-            Ok(Ok(fng_data)) => fng_data["value"].as_u64().unwrap_or(50) as u32,
-            _ => {
-                partial_failure = true;
-                50
-            }
-        };
+        let fng_data = fng_result.and_then(Result::ok).and_then(Result::ok);
+        sources.insert("fng".to_string(), source_status(fng_data.as_ref().map(|data| data.source.as_deref()), "alternative_me"));
+        // Missing indicators are null rather than a neutral-looking 50
+        let fng_value = fng_data.as_ref().map(|fng_data| fng_data.value);
+        let fng_classification = fng_data.and_then(|fng_data| fng_data.value_classification);
+        if fng_value.is_none() {
+            partial_failure |= enabled("fng");
+        }
 
         // Process RSI data
-        let btc_rsi_14_value = match btc_rsi_14_result {
-            Ok(Ok(btc_rsi_14_data)) => btc_rsi_14_data["value"].as_f64().unwrap_or(50.0),
-            _ => {
-                partial_failure = true;
-                50.0
-            }
-        };
+        let btc_rsi_14_data = btc_rsi_14_result.and_then(Result::ok).and_then(Result::ok);
+        sources.insert("rsi".to_string(), source_status(btc_rsi_14_data.as_ref().map(|data| data.source.as_deref()), "taapi"));
+        let btc_rsi_14_value = btc_rsi_14_data
+            .map(|btc_rsi_14_data| finite_or_flag("btc_rsi_14", btc_rsi_14_data.value, &mut non_finite));
+        if btc_rsi_14_value.is_none() {
+            partial_failure |= enabled("rsi");
+        }
 
         // Process US Stock Indices data
-        let us_indices = match us_indices_result {
-            Ok(Ok(indices_data)) => indices_data["indices"].clone(),
-            _ => {
-                partial_failure = true;
+        let indices_data = us_indices_result.and_then(Result::ok).and_then(Result::ok);
+        sources.insert("indices".to_string(), source_status(indices_data.as_ref().map(|data| data.source.as_deref()), "finnhub"));
+        let us_indices = match indices_data {
+            Some(mut indices_data) => {
+                for quote in indices_data.indices.values_mut() {
+                    for value in [&mut quote.price, &mut quote.change, &mut quote.change_percent] {
+                        *value = finite_or_flag("us_stock_indices", *value, &mut non_finite);
+                    }
+                }
+                serde_json::json!(indices_data.indices)
+            }
+            None => {
+                partial_failure |= enabled("indices");
                 serde_json::json!({})
             }
         };
 
+        for group in &disabled_groups {
+            sources.insert(group.to_string(), serde_json::json!({ "source": null, "ok": false, "disabled": true }));
+        }
+        partial_failure |= !non_finite.is_empty();
+
         let duration = start_time.elapsed();
 
+        let critical_failure = CRITICAL_DATA_GROUPS.iter()
+            .filter(|group| enabled(group))
+            .any(|group| sources.get(*group).is_some_and(|status| status["ok"] == false));
+
         // Update statistics
-        if partial_failure {
+        if critical_failure {
+            self.partial_failures.fetch_add(1, Ordering::Relaxed);
+            warn!(duration_ms = duration.as_millis(), "Dashboard summary v2 aggregated without critical data (crypto prices or global)");
+        } else if partial_failure {
             self.partial_failures.fetch_add(1, Ordering::Relaxed);
             warn!(duration_ms = duration.as_millis(), "Dashboard summary v2 aggregated with partial failures");
         } else {
@@ -146,8 +393,8 @@ impl ApiAggregator {
             info!(duration_ms = duration.as_millis(), "Dashboard summary v2 aggregated successfully");
         }
 
-        // Return focused summary JSON
-        Ok(serde_json::json!({
+        // Focused summary JSON
+        let mut summary = serde_json::json!({
             "btc_price_usd": btc_price,
             "btc_change_24h": btc_change,
             "eth_price_usd": eth_price,
@@ -168,12 +415,139 @@ impl ApiAggregator {
             "btc_market_cap_percentage": btc_dominance,
             "eth_market_cap_percentage": eth_dominance,
             "fng_value": fng_value,
+            "fng_classification": fng_classification,
             "btc_rsi_14": btc_rsi_14_value,
             "us_stock_indices": us_indices,
             "fetch_duration_ms": duration.as_millis() as u64,
+            "timings": {
+                "crypto_ms": timings.crypto_ms,
+                "global_ms": timings.global_ms,
+                "fng_ms": timings.fng_ms,
+                "rsi_ms": timings.rsi_ms,
+                "indices_ms": timings.indices_ms,
+            },
             "partial_failure": partial_failure,
+            "critical_failure": critical_failure,
+            "sources": sources,
             "last_updated": chrono::Utc::now().to_rfc3339(),
             "timestamp": chrono::Utc::now().to_rfc3339()
-        }))
+        });
+
+        // Hold over the last good value of every field whose group (or coin) failed;
+        // disabled groups are left empty
+        let crypto_symbols = if enabled("crypto") { DASHBOARD_SYMBOLS } else { &[] };
+        let mut fields_fetched: Vec<(String, bool)> = crypto_symbols.iter()
+            .flat_map(|symbol| {
+                let fetched = crypto_prices.contains_key(*symbol);
+                let coin = symbol.to_lowercase();
+                [(format!("{}_price_usd", coin), fetched), (format!("{}_change_24h", coin), fetched)]
+            })
+            .collect();
+        for (group, fields) in GROUP_FIELDS.iter().filter(|(group, _)| enabled(group)) {
+            let fetched = summary["sources"][*group]["ok"] == true;
+            fields_fetched.extend(fields.iter().map(|field| (field.to_string(), fetched)));
+        }
+        for (field, fetched) in &mut fields_fetched {
+            *fetched &= !non_finite.contains(field);
+        }
+        let stale_fields = self.last_good.fill_failed(&mut summary, &fields_fetched);
+        if !stale_fields.is_empty() {
+            warn!(?stale_fields, "Showing last good values for fields that failed to fetch");
+        }
+        summary["stale_fields"] = serde_json::json!(stale_fields);
+
+        let content_hash = dashboard_content_hash(&summary);
+        summary["content_hash"] = serde_json::Value::String(content_hash);
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_finite_values_are_zeroed_and_flagged() {
+        let mut non_finite = Vec::new();
+        assert_eq!(finite_or_flag("btc_price_usd", 96000.0, &mut non_finite), 96000.0);
+        assert_eq!(finite_or_flag("btc_rsi_14", f64::NAN, &mut non_finite), 0.0);
+        assert_eq!(finite_or_flag("market_cap_usd", f64::INFINITY, &mut non_finite), 0.0);
+        assert_eq!(non_finite, vec!["btc_rsi_14", "market_cap_usd"]);
+    }
+
+    #[test]
+    fn test_missing_symbols_lists_zero_filled_coins() {
+        let mut prices: std::collections::HashMap<String, f64> = DASHBOARD_SYMBOLS.iter()
+            .map(|symbol| (symbol.to_string(), 1.0))
+            .collect();
+        assert!(missing_symbols(&prices).is_empty());
+
+        prices.remove("LINK");
+        prices.remove("ADA");
+        assert_eq!(missing_symbols(&prices), vec!["ADA", "LINK"]);
+    }
+
+    #[tokio::test]
+    async fn test_limited_runs_one_at_a_time_with_one_permit() {
+        use std::sync::atomic::AtomicUsize;
+
+        let permits = Semaphore::new(1);
+        let (running, peak) = (AtomicUsize::new(0), AtomicUsize::new(0));
+        let task = || limited(&permits, || async {
+            peak.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            running.fetch_sub(1, Ordering::SeqCst);
+        });
+
+        tokio::join!(task(), task(), task());
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_queueing_behind_a_slow_group_does_not_count_against_the_timeout() {
+        let permits = Semaphore::new(1);
+        let slow = limited(&permits, || timeout(Duration::from_secs(1), tokio::time::sleep(Duration::from_millis(150))));
+        let queued = limited(&permits, || timeout(Duration::from_millis(100), async { 42 }));
+
+        // The slow group takes the only permit first; the other one waits 150ms for it
+        let (slow, queued) = tokio::join!(slow, async {
+            tokio::task::yield_now().await;
+            queued.await
+        });
+        assert!(slow.is_ok());
+        assert_eq!(queued.unwrap(), 42);
+    }
+
+    #[test]
+    fn test_content_hash_ignores_key_order_and_volatile_fields() {
+        let a: serde_json::Value = serde_json::from_str(
+            r#"{"btc_price_usd":96000.0,"us_stock_indices":{"DIA":{"price":1.0,"status":"success"},"SPY":{"price":2.0}},"timestamp":"t1"}"#,
+        ).unwrap();
+        let b: serde_json::Value = serde_json::from_str(
+            r#"{"timestamp":"t2","us_stock_indices":{"SPY":{"price":2.0},"DIA":{"status":"success","price":1.0}},"btc_price_usd":96000.0}"#,
+        ).unwrap();
+
+        assert_eq!(canonical_json_string(&a["us_stock_indices"]), canonical_json_string(&b["us_stock_indices"]));
+        assert_eq!(dashboard_content_hash(&a), dashboard_content_hash(&b));
+
+        let mut changed = b.clone();
+        changed["btc_price_usd"] = serde_json::json!(96001.0);
+        assert_ne!(dashboard_content_hash(&a), dashboard_content_hash(&changed));
+    }
+
+    #[test]
+    fn test_typed_data_reads_untagged_cache_entries() {
+        use crate::service_islands::layer2_external_services::external_apis_island::market_data_api::FngData;
+
+        // Cached before fetchers tagged a source
+        let cached: FngData = serde_json::from_str(
+            r#"{"value":10,"value_classification":"Extreme Fear","last_updated":"2025-11-15T13:45:35+00:00"}"#,
+        ).unwrap();
+        assert_eq!(cached.value, 10);
+
+        let status = source_status(Some(cached.source.as_deref()), "alternative_me");
+        assert_eq!(status, serde_json::json!({ "source": "alternative_me", "ok": true }));
+        assert_eq!(source_status(None, "alternative_me")["ok"], false);
     }
-}
\ No newline at end of file
+}
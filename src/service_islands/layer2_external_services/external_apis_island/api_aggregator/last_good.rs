@@ -0,0 +1,71 @@
+//! Last-Good Values Component
+//!
+//! Remembers the last successfully fetched value of each dashboard field, so a
+//! failed sub-fetch shows the previous (stale but plausible) value instead of
+//! `0` or `null`. Substituted fields are listed in the dashboard's `stale_fields`.
+
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+
+/// Last successful value per dashboard field
+#[derive(Default)]
+pub struct LastGoodValues {
+    values: Mutex<HashMap<String, serde_json::Value>>,
+}
+
+impl LastGoodValues {
+    /// Create an empty store (nothing to fall back on until the first success)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember fetched fields and substitute remembered values for failed ones
+    ///
+    /// `fields` pairs each fallible field of `summary` with whether it was fetched.
+    /// Returns the fields that were substituted; failed fields never fetched before
+    /// keep their default value and are not listed.
+    pub fn fill_failed(&self, summary: &mut serde_json::Value, fields: &[(String, bool)]) -> Vec<String> {
+        let Some(summary) = summary.as_object_mut() else {
+            return Vec::new();
+        };
+
+        let mut values = self.values.lock();
+        let mut stale_fields = Vec::new();
+        for (field, fetched) in fields {
+            if *fetched {
+                if let Some(value) = summary.get(field) {
+                    values.insert(field.clone(), value.clone());
+                }
+            } else if let Some(value) = values.get(field) {
+                summary.insert(field.clone(), value.clone());
+                stale_fields.push(field.clone());
+            }
+        }
+        stale_fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_fields_fall_back_to_last_good_value() {
+        let last_good = LastGoodValues::new();
+        let fields = |fng_ok: bool, btc_ok: bool| vec![("fng_value".to_string(), fng_ok), ("btc_price_usd".to_string(), btc_ok)];
+
+        // Nothing remembered yet: defaults stay and nothing is stale
+        let mut first = serde_json::json!({ "fng_value": null, "btc_price_usd": 96000.0 });
+        assert!(last_good.fill_failed(&mut first, &fields(false, true)).is_empty());
+        assert_eq!(first["fng_value"], serde_json::Value::Null);
+
+        let mut second = serde_json::json!({ "fng_value": 25, "btc_price_usd": 0.0 });
+        assert_eq!(last_good.fill_failed(&mut second, &fields(true, false)), vec!["btc_price_usd"]);
+        assert_eq!(second["btc_price_usd"], serde_json::json!(96000.0));
+
+        let mut third = serde_json::json!({ "fng_value": null, "btc_price_usd": 97000.0 });
+        assert_eq!(last_good.fill_failed(&mut third, &fields(false, true)), vec!["fng_value"]);
+        assert_eq!(third["fng_value"], serde_json::json!(25));
+    }
+}
@@ -7,12 +7,13 @@ use anyhow::Result;
 use std::sync::Arc;
 use tracing::{debug, warn};
 use super::aggregator_core::ApiAggregator;
+use crate::service_islands::layer2_external_services::external_apis_island::market_data_api::{FngData, GlobalData, IndicesData, RsiData};
 
 impl ApiAggregator {
     /// Fetch global data with type-safe automatic caching
     ///
     /// ✨ NEW: Uses get_or_compute_typed() for automatic caching
-    pub async fn fetch_global_with_cache(&self) -> Result<serde_json::Value> {
+    pub async fn fetch_global_with_cache(&self) -> Result<GlobalData> {
         if let Some(ref cache) = self.cache_system {
             let market_api = Arc::clone(&self.market_api);
 
@@ -36,7 +37,7 @@ impl ApiAggregator {
     /// Fetch Fear & Greed with type-safe automatic caching
     ///
     /// ✨ NEW: Uses get_or_compute_typed() for automatic caching
-    pub async fn fetch_fng_with_cache(&self) -> Result<serde_json::Value> {
+    pub async fn fetch_fng_with_cache(&self) -> Result<FngData> {
         if let Some(ref cache) = self.cache_system {
             let market_api = Arc::clone(&self.market_api);
 
@@ -60,7 +61,7 @@ impl ApiAggregator {
     /// Fetch RSI with type-safe automatic caching
     ///
     /// ✨ NEW: Uses get_or_compute_typed() for automatic caching
-    pub async fn fetch_btc_rsi_14_with_cache(&self) -> Result<serde_json::Value> {
+    pub async fn fetch_btc_rsi_14_with_cache(&self) -> Result<RsiData> {
         if let Some(ref cache) = self.cache_system {
             let market_api = Arc::clone(&self.market_api);
 
@@ -84,7 +85,7 @@ impl ApiAggregator {
     /// Fetch US Stock Indices with type-safe automatic caching
     ///
     /// ✨ NEW: Uses get_or_compute_typed() for automatic caching
-    pub async fn fetch_us_indices_with_cache(&self) -> Result<serde_json::Value> {
+    pub async fn fetch_us_indices_with_cache(&self) -> Result<IndicesData> {
         if let Some(ref cache) = self.cache_system {
             let market_api = Arc::clone(&self.market_api);
 
@@ -6,11 +6,17 @@
 //! - dashboard_aggregator: Dashboard data aggregation logic
 //! - crypto_fetchers: Cryptocurrency price fetching with caching
 //! - market_fetchers: Market data fetching (global, FNG, RSI, indices) with caching
+//! - category_throttle: Independent refresh intervals for slow-moving data
+//! - last_good: Last successful value per field, shown when a fetch fails
+//! - source_switches: Runtime on/off switch per data group
 
 pub mod aggregator_core;
 pub mod dashboard_aggregator;
 pub mod crypto_fetchers;
 pub mod market_fetchers;
+pub mod category_throttle;
+pub mod last_good;
+pub mod source_switches;
 
 // Re-export the main ApiAggregator struct
-pub use aggregator_core::ApiAggregator;
\ No newline at end of file
+pub use aggregator_core::{AggregationStats, ApiAggregator, DEFAULT_MAX_CONCURRENT_FETCHES};
\ No newline at end of file
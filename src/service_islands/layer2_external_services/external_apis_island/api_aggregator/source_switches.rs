@@ -0,0 +1,69 @@
+//! Source Switches Component
+//!
+//! Runtime on/off switch per dashboard data group, toggled through
+//! `/admin/source/:name/disable` and `/enable`. A disabled group is not fetched
+//! at all and counts as intentionally absent: it is flagged `disabled` in
+//! `sources` but sets neither `partial_failure` nor `critical_failure`, and its
+//! fields are not filled from last-good values.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{bail, Result};
+
+/// Data groups that can be switched off, in dashboard order
+pub const DATA_GROUPS: [&str; 5] = ["crypto", "global", "fng", "rsi", "indices"];
+
+/// Disabled flag per data group (all enabled at startup)
+#[derive(Default)]
+pub struct SourceSwitches {
+    disabled: [AtomicBool; DATA_GROUPS.len()],
+}
+
+impl SourceSwitches {
+    /// Every group enabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable `group`; returns whether the state changed
+    pub fn set_enabled(&self, group: &str, enabled: bool) -> Result<bool> {
+        let Some(index) = DATA_GROUPS.iter().position(|g| *g == group) else {
+            bail!("Unknown data source '{}' (expected one of: {})", group, DATA_GROUPS.join(", "));
+        };
+        Ok(self.disabled[index].swap(!enabled, Ordering::Relaxed) == enabled)
+    }
+
+    /// Whether `group` should be fetched (unknown groups count as enabled)
+    pub fn is_enabled(&self, group: &str) -> bool {
+        !DATA_GROUPS
+            .iter()
+            .position(|g| *g == group)
+            .is_some_and(|index| self.disabled[index].load(Ordering::Relaxed))
+    }
+
+    /// Currently disabled groups
+    pub fn disabled(&self) -> Vec<&'static str> {
+        DATA_GROUPS.into_iter().filter(|group| !self.is_enabled(group)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_toggle_independently() {
+        let switches = SourceSwitches::new();
+        assert!(switches.disabled().is_empty());
+
+        assert!(switches.set_enabled("indices", false).unwrap());
+        assert!(!switches.set_enabled("indices", false).unwrap());
+        assert!(!switches.is_enabled("indices"));
+        assert!(switches.is_enabled("rsi"));
+        assert_eq!(switches.disabled(), vec!["indices"]);
+
+        assert!(switches.set_enabled("indices", true).unwrap());
+        assert!(switches.disabled().is_empty());
+        assert!(switches.set_enabled("finnhub", false).is_err());
+    }
+}
@@ -2,18 +2,59 @@
 //
 // This module contains all API URL constants used by the market data API.
 
-/// API URLs - extracted from existing data_service.rs with cache-friendly grouping
+// API URLs - extracted from existing data_service.rs with cache-friendly grouping
 
 // Binance APIs (Primary)
 // Multi-symbol endpoint - fetches all crypto prices in a single request (OPTIMIZED)
-pub const BINANCE_MULTI_PRICE_URL: &str = r#"https://api.binance.com/api/v3/ticker/24hr?symbols=["BTCUSDT","ETHUSDT","SOLUSDT","XRPUSDT","ADAUSDT","LINKUSDT","BNBUSDT"]"#; // 10 sec cache (RealTime)
+pub const BINANCE_MULTI_PRICE_URL: &str = r#"https://api.binance.com/api/v3/ticker/24hr?symbols=["BTCUSDT","ETHUSDT","SOLUSDT","XRPUSDT","ADAUSDT","LINKUSDT","BNBUSDT"]"#; // realtime cache (REALTIME_CACHE_TTL_SECONDS)
+pub const BINANCE_TICKER_URL_TEMPLATE: &str = "https://api.binance.com/api/v3/ticker/24hr?symbol={symbol}"; // symbols missing from the multi-ticker
+
+// Coins shown on the dashboard (each has `<symbol>_price_usd` / `<symbol>_change_24h` fields)
+pub const DASHBOARD_SYMBOLS: &[&str] = &["BTC", "ETH", "SOL", "XRP", "ADA", "LINK", "BNB"];
+
+// Additional exchanges for PRICE_MODE=vwap (neither lists BNB)
+pub const COINBASE_STATS_URL_TEMPLATE: &str = "https://api.exchange.coinbase.com/products/{product}/stats";
+pub const COINBASE_PRODUCTS: &[(&str, &str)] = &[
+    ("BTC", "BTC-USD"), ("ETH", "ETH-USD"), ("SOL", "SOL-USD"),
+    ("XRP", "XRP-USD"), ("ADA", "ADA-USD"), ("LINK", "LINK-USD"),
+];
+pub const KRAKEN_TICKER_URL: &str = "https://api.kraken.com/0/public/Ticker?pair=XBTUSD,ETHUSD,SOLUSD,XRPUSD,ADAUSD,LINKUSD";
+// (symbol, requested pair, key in the response)
+pub const KRAKEN_PAIRS: &[(&str, &str, &str)] = &[
+    ("BTC", "XBTUSD", "XXBTZUSD"), ("ETH", "ETHUSD", "XETHZUSD"), ("SOL", "SOLUSD", "SOLUSD"),
+    ("XRP", "XRPUSD", "XXRPZUSD"), ("ADA", "ADAUSD", "ADAUSD"), ("LINK", "LINKUSD", "LINKUSD"),
+];
 
 // CoinGecko APIs (Fallback)
 pub const BASE_GLOBAL_URL: &str = "https://api.coingecko.com/api/v3/global"; // 30 sec cache
 
 // CoinMarketCap APIs (Fallback)
 pub const CMC_GLOBAL_URL: &str = "https://pro-api.coinmarketcap.com/v1/global-metrics/quotes/latest"; // 30 sec cache
+pub const CMC_FNG_URL: &str = "https://pro-api.coinmarketcap.com/v3/fear-and-greed/latest"; // 5 min cache
 
 // Other APIs
 pub const BASE_FNG_URL: &str = "https://api.alternative.me/fng/?limit=1"; // 5 min cache
-pub const BASE_RSI_URL_TEMPLATE: &str = "https://api.taapi.io/rsi?secret={secret}&exchange=binance&symbol=BTC/USDT&interval=1d"; // 5 min cache
\ No newline at end of file
+pub const BASE_RSI_URL_TEMPLATE: &str = "https://api.taapi.io/rsi?secret={secret}&exchange=binance&symbol=BTC/USDT&interval=1d"; // 5 min cache
+
+// US indices fetched from Finnhub as ETF proxies (free tier); overridden by STOCK_INDICES
+pub const DEFAULT_STOCK_INDICES: &[(&str, &str)] = &[
+    ("DIA", "SPDR Dow Jones Industrial Average ETF"), // DJIA proxy
+    ("SPY", "SPDR S&P 500 ETF Trust"),                // S&P 500 proxy
+    ("QQQM", "INVESCO NASDAQ 100 ETF"),               // Nasdaq 100 proxy
+];
+
+// Rate-limit quota tracking
+// Sentinel stored while a provider has not reported its remaining quota yet
+pub const QUOTA_UNKNOWN: u64 = u64::MAX;
+// Below this many remaining calls/credits a provider is treated as nearly exhausted
+pub const QUOTA_LOW_WATERMARK: u64 = 5;
+
+// Circuit breaker service names (one circuit per upstream provider)
+pub const SERVICE_BINANCE: &str = "binance";
+pub const SERVICE_COINGECKO: &str = "coingecko";
+pub const SERVICE_COINMARKETCAP: &str = "coinmarketcap";
+pub const SERVICE_ALTERNATIVE_ME: &str = "alternative_me";
+pub const SERVICE_TAAPI: &str = "taapi";
+pub const SERVICE_FINNHUB: &str = "finnhub";
+pub const SERVICE_COINBASE: &str = "coinbase";
+pub const SERVICE_KRAKEN: &str = "kraken";
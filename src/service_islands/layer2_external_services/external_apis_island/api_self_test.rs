@@ -0,0 +1,68 @@
+//! API Self-Test
+//!
+//! One live request per provider, used by `--check-apis` to catch bad or
+//! missing API keys before deploying. Keyed providers (TAAPI always, CoinMarketCap
+//! and Finnhub when their key is set) are required; keyless ones are informational.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::config::Config;
+use super::market_data_api::{
+    MarketDataApi, SERVICE_ALTERNATIVE_ME, SERVICE_BINANCE, SERVICE_COINGECKO, SERVICE_COINMARKETCAP,
+    SERVICE_FINNHUB, SERVICE_TAAPI,
+};
+
+/// Outcome of one provider check
+#[derive(Debug, Clone)]
+pub struct ApiCheck {
+    pub provider: &'static str,
+    /// Whether a failure should fail the self-test
+    pub required: bool,
+    pub latency: Duration,
+    /// Failure reason (None if the check passed)
+    pub error: Option<String>,
+}
+
+impl ApiCheck {
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Check every provider the config can reach, one request each, in order
+pub async fn check_configured_apis(config: &Config) -> Result<Vec<ApiCheck>> {
+    let api = MarketDataApi::with_all_keys(
+        config.taapi_secret.clone(),
+        config.cmc_api_key.clone(),
+        config.finnhub_api_key.clone(),
+    ).await?;
+
+    let mut checks = vec![
+        check(SERVICE_BINANCE, false, api.fetch_multi_crypto_prices()).await,
+        check(SERVICE_COINGECKO, false, api.fetch_global_data_coingecko()).await,
+        check(SERVICE_ALTERNATIVE_ME, false, api.fetch_fear_greed_index()).await,
+        check(SERVICE_TAAPI, true, api.fetch_btc_rsi_14()).await,
+    ];
+    if config.cmc_api_key.is_some() {
+        checks.push(check(SERVICE_COINMARKETCAP, true, api.fetch_global_data_cmc()).await);
+    }
+    if config.finnhub_api_key.is_some() {
+        checks.push(check(SERVICE_FINNHUB, true, api.fetch_us_stock_indices()).await);
+    }
+
+    Ok(checks)
+}
+
+async fn check<T>(provider: &'static str, required: bool, fetch: impl Future<Output = Result<T>>) -> ApiCheck {
+    let started = Instant::now();
+    let result = fetch.await;
+    ApiCheck {
+        provider,
+        required,
+        latency: started.elapsed(),
+        error: result.err().map(|e| e.to_string()),
+    }
+}
@@ -0,0 +1,261 @@
+//! Binance WebSocket Source Component
+//!
+//! Maintains a persistent connection to the Binance combined ticker stream and
+//! keeps the latest price/24h change per coin in memory, so the aggregator can
+//! read live prices without a REST call. Only the leader runs the stream; if it
+//! drops or goes stale the aggregator falls back to the REST endpoint.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use futures::{Stream, StreamExt};
+use parking_lot::{Mutex, RwLock};
+use serde::Deserialize;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::{self, Message};
+use tracing::{info, warn};
+
+use super::market_data_api::jittered_backoff;
+
+/// Combined stream of 24h tickers for the dashboard coins
+const BINANCE_WS_URL: &str = "wss://stream.binance.com:9443/stream?streams=btcusdt@ticker/ethusdt@ticker/solusdt@ticker/xrpusdt@ticker/adausdt@ticker/linkusdt@ticker/bnbusdt@ticker";
+
+/// Binance symbol → dashboard coin name
+const STREAM_SYMBOLS: &[(&str, &str)] = &[
+    ("BTCUSDT", "BTC"),
+    ("ETHUSDT", "ETH"),
+    ("SOLUSDT", "SOL"),
+    ("XRPUSDT", "XRP"),
+    ("ADAUSDT", "ADA"),
+    ("LINKUSDT", "LINK"),
+    ("BNBUSDT", "BNB"),
+];
+
+/// Prices older than this are not served (the REST fallback is used instead)
+const MAX_PRICE_AGE: Duration = Duration::from_secs(15);
+
+/// Reconnect backoff: base delay and the highest exponent used
+const RECONNECT_BASE_DELAY_MS: u64 = 1000;
+const RECONNECT_MAX_EXPONENT: u32 = 5;
+
+/// Envelope of a combined-stream message
+#[derive(Debug, Deserialize)]
+struct CombinedStreamMessage {
+    data: TickerEvent,
+}
+
+/// 24hr ticker event (only the fields we use)
+#[derive(Debug, Deserialize)]
+struct TickerEvent {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "c")]
+    last_price: String,
+    #[serde(rename = "P")]
+    price_change_percent: String,
+}
+
+/// Latest tick for one coin
+#[derive(Debug, Clone, Copy)]
+struct Tick {
+    price_usd: f64,
+    change_24h: f64,
+    received_at: Instant,
+}
+
+/// Binance WebSocket Source
+///
+/// Idle until `start()` is called; `stop()` closes the upstream connection.
+pub struct BinanceWsSource {
+    prices: Arc<RwLock<HashMap<String, Tick>>>,
+    connected: Arc<AtomicBool>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Default for BinanceWsSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BinanceWsSource {
+    /// Create an idle source
+    pub fn new() -> Self {
+        Self {
+            prices: Arc::new(RwLock::new(HashMap::new())),
+            connected: Arc::new(AtomicBool::new(false)),
+            task: Mutex::new(None),
+        }
+    }
+
+    /// Start the stream task if it isn't running
+    pub fn start(&self) {
+        let mut task = self.task.lock();
+        if task.as_ref().is_some_and(|handle| !handle.is_finished()) {
+            return;
+        }
+
+        info!("Starting Binance WebSocket price stream");
+        let prices = Arc::clone(&self.prices);
+        let connected = Arc::clone(&self.connected);
+        *task = Some(tokio::spawn(run_stream(prices, connected)));
+    }
+
+    /// Stop the stream task and forget buffered prices
+    pub fn stop(&self) {
+        if let Some(handle) = self.task.lock().take() {
+            info!("Stopping Binance WebSocket price stream");
+            handle.abort();
+        }
+        self.connected.store(false, Ordering::Relaxed);
+        self.prices.write().clear();
+    }
+
+    /// Whether the upstream WebSocket is currently connected
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// Latest (price_usd, change_24h) per coin, if connected and every coin is fresh
+    pub fn latest_prices(&self) -> Option<HashMap<String, (f64, f64)>> {
+        if !self.is_connected() {
+            return None;
+        }
+
+        let prices = self.prices.read();
+        STREAM_SYMBOLS
+            .iter()
+            .map(|(_, coin)| {
+                prices
+                    .get(*coin)
+                    .filter(|tick| tick.received_at.elapsed() <= MAX_PRICE_AGE)
+                    .map(|tick| (coin.to_string(), (tick.price_usd, tick.change_24h)))
+            })
+            .collect()
+    }
+}
+
+/// Connect, read ticks until the stream ends, then reconnect with backoff
+async fn run_stream(prices: Arc<RwLock<HashMap<String, Tick>>>, connected: Arc<AtomicBool>) {
+    let mut attempt = 0;
+    loop {
+        match read_stream(&prices, &connected).await {
+            Ok(()) => warn!("Binance WebSocket stream closed by server"),
+            Err(e) => warn!(error = %e, "Binance WebSocket stream failed"),
+        }
+        // A connection that got established starts the backoff over
+        if connected.swap(false, Ordering::Relaxed) {
+            attempt = 0;
+        }
+
+        let delay = jittered_backoff(RECONNECT_BASE_DELAY_MS, attempt.min(RECONNECT_MAX_EXPONENT));
+        attempt += 1;
+        warn!(delay_ms = delay.as_millis(), attempt, "Reconnecting to Binance WebSocket stream");
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// One connection's lifetime: returns when the stream closes
+async fn read_stream(prices: &RwLock<HashMap<String, Tick>>, connected: &AtomicBool) -> Result<()> {
+    let (stream, _) = tokio_tungstenite::connect_async(BINANCE_WS_URL)
+        .await
+        .context("Failed to connect to Binance WebSocket")?;
+    connected.store(true, Ordering::Relaxed);
+    info!("Connected to Binance WebSocket price stream");
+
+    read_ticks(stream, prices, MAX_PRICE_AGE).await
+}
+
+/// Record ticks from `stream` until it closes
+///
+/// Binance sends a ticker every second and pings in between, so a connection that
+/// stays silent for `read_timeout` is treated as dead (e.g. half-open TCP) and
+/// returned as an error, which reconnects instead of serving aging prices forever.
+async fn read_ticks<S>(mut stream: S, prices: &RwLock<HashMap<String, Tick>>, read_timeout: Duration) -> Result<()>
+where
+    S: Stream<Item = Result<Message, tungstenite::Error>> + Unpin,
+{
+    // tungstenite answers pings automatically while we keep reading
+    loop {
+        let Ok(message) = tokio::time::timeout(read_timeout, stream.next()).await else {
+            bail!("no message from Binance WebSocket for {:?}", read_timeout);
+        };
+        let Some(message) = message else {
+            return Ok(());
+        };
+        let text = match message.context("Binance WebSocket read error")? {
+            Message::Text(text) => text,
+            Message::Close(_) => return Ok(()),
+            _ => continue,
+        };
+
+        match serde_json::from_str::<CombinedStreamMessage>(&text) {
+            Ok(CombinedStreamMessage { data }) => record_tick(prices, data),
+            Err(e) => warn!(error = %e, "Ignoring unparseable Binance stream message"),
+        }
+    }
+}
+
+fn record_tick(prices: &RwLock<HashMap<String, Tick>>, event: TickerEvent) {
+    let Some((_, coin)) = STREAM_SYMBOLS.iter().find(|(symbol, _)| *symbol == event.symbol) else {
+        return;
+    };
+    let (Ok(price_usd), Ok(change_24h)) = (event.last_price.parse::<f64>(), event.price_change_percent.parse::<f64>()) else {
+        return;
+    };
+    if price_usd <= 0.0 {
+        return;
+    }
+
+    prices.write().insert(coin.to_string(), Tick {
+        price_usd,
+        change_24h,
+        received_at: Instant::now(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_prices_requires_every_coin() {
+        let source = BinanceWsSource::new();
+        source.connected.store(true, Ordering::Relaxed);
+
+        for (symbol, _) in &STREAM_SYMBOLS[1..] {
+            record_tick(&source.prices, TickerEvent {
+                symbol: symbol.to_string(),
+                last_price: "10.5".to_string(),
+                price_change_percent: "-1.25".to_string(),
+            });
+        }
+        assert!(source.latest_prices().is_none());
+
+        let json = r#"{"stream":"btcusdt@ticker","data":{"e":"24hrTicker","s":"BTCUSDT","c":"96000.10","P":"2.50"}}"#;
+        let message: CombinedStreamMessage = serde_json::from_str(json).unwrap();
+        record_tick(&source.prices, message.data);
+
+        let prices = source.latest_prices().unwrap();
+        assert_eq!(prices.len(), STREAM_SYMBOLS.len());
+        assert_eq!(prices["BTC"], (96000.10, 2.50));
+        assert_eq!(prices["ETH"], (10.5, -1.25));
+    }
+
+    #[tokio::test]
+    async fn test_silent_stream_is_treated_as_disconnected() {
+        let prices = RwLock::new(HashMap::new());
+        let tick = r#"{"stream":"btcusdt@ticker","data":{"e":"24hrTicker","s":"BTCUSDT","c":"96000.10","P":"2.50"}}"#;
+        let stream = futures::stream::iter([Ok(Message::Text(tick.to_string()))]).chain(futures::stream::pending());
+
+        let result = read_ticks(stream, &prices, Duration::from_millis(50)).await;
+        assert!(result.unwrap_err().to_string().contains("no message"));
+        assert_eq!(prices.read()["BTC"].price_usd, 96000.10);
+
+        let closed = futures::stream::iter([Ok(Message::Close(None))]);
+        assert!(read_ticks(closed, &prices, Duration::from_millis(50)).await.is_ok());
+    }
+}
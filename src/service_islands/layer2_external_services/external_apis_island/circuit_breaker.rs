@@ -2,15 +2,23 @@
 //!
 //! This component implements the circuit breaker pattern to handle failing external services gracefully.
 
+use serde::Serialize;
 use std::collections::HashMap;
-use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+/// How long a half-open probe holds its slot without reporting back
+///
+/// A probe dropped mid-flight (e.g. by the aggregation timeout) never records an
+/// outcome; after this long the next request may probe instead.
+const HALF_OPEN_PROBE_LEASE: Duration = Duration::from_secs(30);
 
 /// Circuit breaker states
-#[derive(Debug, Clone, PartialEq)]
-#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum CircuitState {
     Closed,     // Normal operation
     Open,       // Circuit is open, requests are blocked
@@ -19,7 +27,6 @@ pub enum CircuitState {
 
 /// Circuit breaker configuration
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub struct CircuitBreakerConfig {
     pub failure_threshold: usize,      // Number of failures to open circuit
     pub success_threshold: usize,      // Number of successes to close circuit
@@ -40,7 +47,6 @@ impl Default for CircuitBreakerConfig {
 
 /// Circuit breaker tracker for a specific service
 #[derive(Debug)]
-#[allow(dead_code)]
 struct CircuitBreakerTracker {
     state: CircuitState,
     config: CircuitBreakerConfig,
@@ -49,11 +55,142 @@ struct CircuitBreakerTracker {
     last_failure_time: Option<Instant>,
     last_success_time: Option<Instant>,
     state_change_time: Instant,
+    /// Set while the single half-open probe is in flight
+    probe_started: Option<Instant>,
     total_requests: usize,
     total_failures: usize,
 }
 
 impl CircuitBreakerTracker {
+    fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            state: CircuitState::Closed,
+            config,
+            failure_count: 0,
+            success_count: 0,
+            last_failure_time: None,
+            last_success_time: None,
+            state_change_time: Instant::now(),
+            probe_started: None,
+            total_requests: 0,
+            total_failures: 0,
+        }
+    }
+
+    fn transition(&mut self, state: CircuitState) {
+        self.state = state;
+        self.state_change_time = Instant::now();
+        self.failure_count = 0;
+        self.success_count = 0;
+        self.probe_started = None;
+    }
+
+    /// Time left before an open circuit lets a half-open probe through
+    fn time_until_half_open(&self) -> Option<Duration> {
+        if self.state != CircuitState::Open {
+            return None;
+        }
+        let timeout = Duration::from_secs(self.config.timeout_seconds);
+        Some(timeout.saturating_sub(self.state_change_time.elapsed()))
+    }
+
+    /// Returns true if a request may proceed, moving Open -> HalfOpen once the timeout elapsed
+    ///
+    /// While half-open only one probe runs at a time; the rest are blocked until it
+    /// reports back (or its lease runs out).
+    fn can_execute(&mut self) -> bool {
+        match self.state {
+            CircuitState::Closed => true,
+            CircuitState::HalfOpen => self.try_start_probe(),
+            CircuitState::Open => {
+                if self.time_until_half_open().is_some_and(|d| d.is_zero()) {
+                    self.transition(CircuitState::HalfOpen);
+                    self.try_start_probe()
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    fn try_start_probe(&mut self) -> bool {
+        if self.probe_started.is_some_and(|started| started.elapsed() < HALF_OPEN_PROBE_LEASE) {
+            return false;
+        }
+        self.probe_started = Some(Instant::now());
+        true
+    }
+
+    fn record_success(&mut self) {
+        self.total_requests += 1;
+        self.last_success_time = Some(Instant::now());
+        self.probe_started = None;
+
+        match self.state {
+            CircuitState::Closed => self.failure_count = 0,
+            CircuitState::HalfOpen => {
+                self.success_count += 1;
+                if self.success_count >= self.config.success_threshold {
+                    self.transition(CircuitState::Closed);
+                }
+            }
+            CircuitState::Open => {}
+        }
+    }
+
+    /// Returns true if this failure opened the circuit
+    fn record_failure(&mut self) -> bool {
+        self.total_requests += 1;
+        self.total_failures += 1;
+        self.probe_started = None;
+
+        // Failures spread far apart shouldn't accumulate into an open circuit
+        let reset_after = Duration::from_secs(self.config.reset_timeout_seconds);
+        if self.last_failure_time.is_some_and(|t| t.elapsed() >= reset_after) {
+            self.failure_count = 0;
+        }
+        self.last_failure_time = Some(Instant::now());
+
+        match self.state {
+            CircuitState::Closed => {
+                self.failure_count += 1;
+                if self.failure_count >= self.config.failure_threshold {
+                    self.transition(CircuitState::Open);
+                    return true;
+                }
+                false
+            }
+            CircuitState::HalfOpen => {
+                // Probe failed - back to open for another timeout period
+                self.transition(CircuitState::Open);
+                true
+            }
+            CircuitState::Open => false,
+        }
+    }
+
+    fn status(&self, service: &str) -> CircuitBreakerStatus {
+        CircuitBreakerStatus {
+            service: service.to_string(),
+            state: self.state.clone(),
+            failure_count: self.failure_count,
+            seconds_until_half_open: self.time_until_half_open().map(|d| d.as_secs()),
+            total_requests: self.total_requests,
+            total_failures: self.total_failures,
+        }
+    }
+}
+
+/// Point-in-time view of a single service's circuit
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitBreakerStatus {
+    pub service: String,
+    pub state: CircuitState,
+    pub failure_count: usize,
+    /// Only set while the circuit is open
+    pub seconds_until_half_open: Option<u64>,
+    pub total_requests: usize,
+    pub total_failures: usize,
 }
 
 /// Circuit Breaker
@@ -62,10 +199,140 @@ impl CircuitBreakerTracker {
 #[allow(dead_code)]
 pub struct CircuitBreaker {
     breakers: Arc<RwLock<HashMap<String, CircuitBreakerTracker>>>,
+    config: CircuitBreakerConfig,
     total_blocked: Arc<AtomicU64>,
     total_opened: Arc<AtomicU64>,
     start_time: Instant,
 }
 
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(CircuitBreakerConfig::default())
+    }
+}
+
 impl CircuitBreaker {
+    /// Create a circuit breaker applying `config` to every tracked service
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            breakers: Arc::new(RwLock::new(HashMap::new())),
+            config,
+            total_blocked: Arc::new(AtomicU64::new(0)),
+            total_opened: Arc::new(AtomicU64::new(0)),
+            start_time: Instant::now(),
+        }
+    }
+
+    /// Check whether a request to `service` may proceed
+    pub async fn can_execute(&self, service: &str) -> bool {
+        let mut breakers = self.breakers.write().await;
+        let tracker = breakers
+            .entry(service.to_string())
+            .or_insert_with(|| CircuitBreakerTracker::new(self.config.clone()));
+
+        let allowed = tracker.can_execute();
+        if !allowed {
+            self.total_blocked.fetch_add(1, Ordering::Relaxed);
+        } else if tracker.state == CircuitState::HalfOpen {
+            info!(service, "Circuit half-open, allowing probe request");
+        }
+        allowed
+    }
+
+    /// Record a successful call to `service`
+    pub async fn record_success(&self, service: &str) {
+        let mut breakers = self.breakers.write().await;
+        breakers
+            .entry(service.to_string())
+            .or_insert_with(|| CircuitBreakerTracker::new(self.config.clone()))
+            .record_success();
+    }
+
+    /// Record a failed call to `service`
+    pub async fn record_failure(&self, service: &str) {
+        let mut breakers = self.breakers.write().await;
+        let opened = breakers
+            .entry(service.to_string())
+            .or_insert_with(|| CircuitBreakerTracker::new(self.config.clone()))
+            .record_failure();
+
+        if opened {
+            self.total_opened.fetch_add(1, Ordering::Relaxed);
+            warn!(service, timeout_seconds = self.config.timeout_seconds, "Circuit breaker opened");
+        }
+    }
+
+    /// Status of every service seen so far, sorted by service name
+    pub async fn status(&self) -> Vec<CircuitBreakerStatus> {
+        let breakers = self.breakers.read().await;
+        let mut statuses: Vec<_> = breakers
+            .iter()
+            .map(|(service, tracker)| tracker.status(service))
+            .collect();
+        statuses.sort_by(|a, b| a.service.cmp(&b.service));
+        statuses
+    }
+
+    /// Services whose circuit is currently open
+    pub async fn open_services(&self) -> Vec<String> {
+        self.status()
+            .await
+            .into_iter()
+            .filter(|s| s.state == CircuitState::Open)
+            .map(|s| s.service)
+            .collect()
+    }
+
+    /// Number of requests rejected while a circuit was open
+    pub fn total_blocked(&self) -> u64 {
+        self.total_blocked.load(Ordering::Relaxed)
+    }
+
+    /// Number of times any circuit has opened
+    pub fn total_opened(&self) -> u64 {
+        self.total_opened.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_circuit_opens_and_recovers() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            success_threshold: 1,
+            timeout_seconds: 0,
+            reset_timeout_seconds: 300,
+        });
+
+        breaker.record_failure("svc").await;
+        assert_eq!(breaker.status().await[0].state, CircuitState::Closed);
+
+        breaker.record_failure("svc").await;
+        assert_eq!(breaker.open_services().await, vec!["svc".to_string()]);
+        assert_eq!(breaker.total_opened(), 1);
+
+        // Zero timeout: the next check moves straight to half-open, admitting one probe
+        assert!(breaker.can_execute("svc").await);
+        assert_eq!(breaker.status().await[0].state, CircuitState::HalfOpen);
+        assert!(!breaker.can_execute("svc").await, "only one probe while half-open");
+
+        breaker.record_success("svc").await;
+        assert_eq!(breaker.status().await[0].state, CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_blocks_requests() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 1,
+            ..CircuitBreakerConfig::default()
+        });
+
+        breaker.record_failure("svc").await;
+        assert!(!breaker.can_execute("svc").await);
+        assert_eq!(breaker.total_blocked(), 1);
+        assert!(breaker.status().await[0].seconds_until_half_open.is_some());
+    }
 }
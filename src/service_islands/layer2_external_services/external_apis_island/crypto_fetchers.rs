@@ -11,7 +11,7 @@ impl MarketDataApi {
         self.record_api_call();
 
         // Try Binance multi-ticker endpoint
-        match self.fetch_multi_crypto_prices_binance().await {
+        match self.call_with_breaker(SERVICE_BINANCE, self.fetch_multi_crypto_prices_binance()).await {
             Ok(data) => {
                 self.record_success();
                 Ok(data)
@@ -36,53 +36,63 @@ impl MarketDataApi {
 
         // Parse the JSON array into our HashMap
         let tickers: BinanceMultiTickerResponse = serde_json::from_value(response_json)?;
-        let mut prices = HashMap::new();
-        
-        for ticker in tickers {
-            let price_usd: f64 = ticker.last_price.parse().unwrap_or(0.0);
-            let change_24h: f64 = ticker.price_change_percent.parse().unwrap_or(0.0);
-            
-            // Map symbol to coin name
-            let coin_name = match ticker.symbol.as_str() {
-                "BTCUSDT" => "BTC",
-                "ETHUSDT" => "ETH",
-                "SOLUSDT" => "SOL",
-                "XRPUSDT" => "XRP",
-                "ADAUSDT" => "ADA",
-                "LINKUSDT" => "LINK",
-                "BNBUSDT" => "BNB",
-                _ => continue, // Skip unknown symbols
-            };
-            
-            prices.insert(coin_name.to_string(), (price_usd, change_24h));
+        let mut prices = binance_ticker_prices(tickers);
+
+        // Recover coins the multi-ticker omitted (e.g. during symbol maintenance) one by one
+        let omitted: Vec<&str> = DASHBOARD_SYMBOLS.iter()
+            .copied()
+            .filter(|coin| !prices.contains_key(*coin))
+            .collect();
+        if !omitted.is_empty() {
+            let fetches = omitted.iter().map(|coin| self.fetch_binance_ticker(coin));
+            let recovered: Vec<BinanceBtcPrice> = futures::future::join_all(fetches).await
+                .into_iter()
+                .zip(&omitted)
+                .filter_map(|(result, coin)| result
+                    .inspect_err(|e| warn!(coin, error = %e, "Binance single-ticker fallback failed"))
+                    .ok())
+                .collect();
+            info!(omitted = ?omitted, recovered = recovered.len(), "Binance multi-ticker response missing symbols, fetched them individually");
+            prices.extend(binance_ticker_prices(recovered));
         }
 
-        // Validate we got all 7 coins
-        if prices.len() != 7 {
-            return Err(anyhow::anyhow!(
-                "Binance multi-ticker validation failed: expected 7 coins, got {}",
-                prices.len()
-            ));
+        // Name the absent coins instead of just counting them
+        let missing: Vec<String> = DASHBOARD_SYMBOLS.iter()
+            .filter(|coin| !prices.contains_key(**coin))
+            .map(|coin| coin.to_string())
+            .collect();
+        if !missing.is_empty() {
+            let min_symbols = self.min_crypto_symbols.load(Ordering::Relaxed);
+            if prices.len() < min_symbols {
+                return Err(ExternalApiError::MissingSymbols { endpoint: SERVICE_BINANCE.to_string(), missing }.into());
+            }
+            warn!(missing = ?missing, received = prices.len(), min_symbols, "Binance multi-ticker response missing symbols, continuing with the rest");
         }
 
         // Validate each price is reasonable
         for (coin, (price, _)) in &prices {
             if *price <= 0.0 {
-                return Err(anyhow::anyhow!(
-                    "Binance {} price validation failed: price={}",
-                    coin, price
-                ));
+                return Err(ExternalApiError::invalid(
+                    SERVICE_BINANCE,
+                    format!("{} price validation failed: price={}", coin, price),
+                ).into());
             }
         }
 
         Ok(prices)
     }
 
+    /// 24h ticker for one dashboard coin (`/ticker/24hr?symbol=<COIN>USDT`)
+    async fn fetch_binance_ticker(&self, coin: &str) -> Result<BinanceBtcPrice> {
+        let url = BINANCE_TICKER_URL_TEMPLATE.replace("{symbol}", &format!("{}USDT", coin));
+        self.fetch_with_retry(&url, |ticker: BinanceBtcPrice| ticker).await
+    }
+
     /// Generic fetch with retry logic and exponential backoff
-    pub async fn fetch_with_retry<T, F>(&self, url: &str, transformer: F) -> Result<serde_json::Value>
+    pub async fn fetch_with_retry<T, R, F>(&self, url: &str, transformer: F) -> Result<R>
     where
         T: for<'de> serde::Deserialize<'de>,
-        F: Fn(T) -> serde_json::Value,
+        F: Fn(T) -> R,
     {
         let mut attempts = 0;
         let max_attempts = 3;
@@ -92,21 +102,28 @@ impl MarketDataApi {
                 .get(url)
                 .header("Accept", "application/json")
                 .send()
-                .await?;
+                .await
+                .map_err(|e| ExternalApiError::from_reqwest(url, e))?;
+
+            if let Some(provider) = QuotaProvider::from_url(url) {
+                self.record_quota_headers(provider, response.headers());
+            }
 
             match response.status() {
                 status if status.is_success() => {
-                    let data: T = response.json().await?;
+                    let data: T = response.json().await
+                        .map_err(|e| ExternalApiError::invalid(url, e.to_string()))?;
                     return Ok(transformer(data));
                 }
                 status if status == 418 => {
                     // 418 I'm a teapot - Binance uses this for rate limiting/blocking
                     attempts += 1;
-                    if attempts >= max_attempts {
-                        return Err(anyhow::anyhow!("Binance blocked request (418 I'm a teapot) after {} attempts for URL: {}. This usually means rate limiting or IP blocking.", max_attempts, url));
+                    if attempts >= max_attempts || !self.retry_allowed(url) {
+                        // Binance uses 418 for rate limiting or IP blocking
+                        return Err(ExternalApiError::RateLimited { endpoint: url.to_string(), attempts }.into());
                     }
 
-                    let delay = std::time::Duration::from_millis(2000 * (2_u64.pow(attempts)));
+                    let delay = jittered_backoff(2000, attempts);
                     warn!(url = %url, delay_ms = delay.as_millis(), attempt = attempts, max_attempts = max_attempts, "Binance blocking (418), retrying");
                     tokio::time::sleep(delay).await;
                     continue;
@@ -114,21 +131,33 @@ impl MarketDataApi {
                 status if status == 429 => {
                     // Rate limiting - implement exponential backoff
                     attempts += 1;
-                    if attempts >= max_attempts {
-                        return Err(anyhow::anyhow!("Rate limit exceeded after {} attempts for URL: {}", max_attempts, url));
+                    if attempts >= max_attempts || !self.retry_allowed(url) {
+                        return Err(ExternalApiError::RateLimited { endpoint: url.to_string(), attempts }.into());
                     }
 
-                    let delay = std::time::Duration::from_millis(1000 * (2_u64.pow(attempts)));
+                    let delay = jittered_backoff(1000, attempts);
                     warn!(url = %url, delay_ms = delay.as_millis(), attempt = attempts, max_attempts = max_attempts, "Rate limit (429) hit, retrying");
                     tokio::time::sleep(delay).await;
                     continue;
                 }
                 status => {
-                    return Err(anyhow::anyhow!("API returned status: {} for URL: {}", status, url));
+                    return Err(ExternalApiError::invalid(url, format!("status {}", status)).into());
                 }
             }
         }
 
-        Err(anyhow::anyhow!("Max retry attempts reached for URL: {}", url))
+        Err(ExternalApiError::RateLimited { endpoint: url.to_string(), attempts }.into())
     }
-}
\ No newline at end of file
+}
+
+/// `(price_usd, change_24h)` per dashboard coin, skipping tickers we didn't ask for
+fn binance_ticker_prices(tickers: impl IntoIterator<Item = BinanceBtcPrice>) -> HashMap<String, (f64, f64)> {
+    tickers.into_iter()
+        .filter_map(|ticker| {
+            let coin = ticker.symbol.strip_suffix("USDT").filter(|coin| DASHBOARD_SYMBOLS.contains(coin))?;
+            let price_usd: f64 = ticker.last_price.parse().unwrap_or(0.0);
+            let change_24h: f64 = ticker.price_change_percent.parse().unwrap_or(0.0);
+            Some((coin.to_string(), (price_usd, change_24h)))
+        })
+        .collect()
+}
@@ -0,0 +1,88 @@
+//! External API Errors
+//!
+//! Typed failures for upstream API calls. Fetchers still return `anyhow::Result`,
+//! but the errors they raise are `ExternalApiError` values, so callers can
+//! classify a failure with `ExternalApiError::find` instead of matching strings.
+
+use thiserror::Error;
+
+/// Failure of a call to an external market data API
+#[derive(Debug, Error)]
+pub enum ExternalApiError {
+    /// The provider kept answering 429 (or Binance 418) after all retries
+    #[error("{endpoint} rate limit exceeded after {attempts} attempts")]
+    RateLimited { endpoint: String, attempts: u32 },
+
+    /// The circuit breaker for this service is open; no request was made
+    #[error("Circuit breaker open for {service}")]
+    CircuitOpen { service: String },
+
+    /// The request timed out
+    #[error("{endpoint} request timed out")]
+    Timeout { endpoint: String },
+
+    /// Unexpected status, unparseable body or data that failed validation
+    #[error("Invalid response from {endpoint}: {reason}")]
+    InvalidResponse { endpoint: String, reason: String },
+
+    /// The response parsed but lacked some requested symbols (e.g. delisted or mistyped)
+    #[error("{endpoint} response missing symbols: {}", missing.join(", "))]
+    MissingSymbols { endpoint: String, missing: Vec<String> },
+
+    /// The provider refused the request for good (e.g. Finnhub 403 for a symbol
+    /// outside the plan); retrying cannot succeed
+    #[error("{endpoint} not available on this plan (status {status})")]
+    Unavailable { endpoint: String, status: u16 },
+
+    /// Connection-level failure (DNS, TLS, reset, ...)
+    #[error("Network error calling {endpoint}: {source}")]
+    Network {
+        endpoint: String,
+        #[source]
+        source: reqwest::Error,
+    },
+}
+
+impl ExternalApiError {
+    /// Classify a reqwest failure as `Timeout` or `Network`
+    pub fn from_reqwest(endpoint: &str, error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            Self::Timeout { endpoint: endpoint.to_string() }
+        } else {
+            Self::Network { endpoint: endpoint.to_string(), source: error }
+        }
+    }
+
+    /// Shorthand for `InvalidResponse`
+    pub fn invalid(endpoint: &str, reason: impl Into<String>) -> Self {
+        Self::InvalidResponse { endpoint: endpoint.to_string(), reason: reason.into() }
+    }
+
+    /// Temporary back-pressure from the provider or our own breaker, not an outage
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::RateLimited { .. } | Self::CircuitOpen { .. })
+    }
+
+    /// First `ExternalApiError` in an error's chain (including added context)
+    pub fn find(error: &anyhow::Error) -> Option<&Self> {
+        error.chain().find_map(|cause| cause.downcast_ref::<Self>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_find_through_context() {
+        let error: anyhow::Result<()> = Err(ExternalApiError::RateLimited {
+            endpoint: "coingecko".to_string(),
+            attempts: 3,
+        }.into());
+        let error = error.context("Primary error").unwrap_err();
+
+        assert!(ExternalApiError::find(&error).is_some_and(ExternalApiError::is_transient));
+        assert!(ExternalApiError::find(&anyhow::anyhow!("plain")).is_none());
+    }
+}
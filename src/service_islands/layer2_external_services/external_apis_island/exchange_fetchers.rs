@@ -0,0 +1,130 @@
+// Multi-Exchange Price Fetchers Component
+//
+// Binance, Coinbase and Kraken tickers combined into volume-weighted prices (`PRICE_MODE=vwap`).
+
+use super::price_vwap::{volume_weighted, ExchangeQuote, VwapPrice};
+
+impl MarketDataApi {
+    /// Volume-weighted prices for DASHBOARD_SYMBOLS across Binance, Coinbase and Kraken
+    ///
+    /// Exchanges are queried concurrently; a failing exchange is logged and left out.
+    pub async fn fetch_vwap_crypto_prices(&self, max_deviation_percent: f64) -> Result<HashMap<String, VwapPrice>> {
+        self.record_api_call();
+
+        let (binance, coinbase, kraken) = tokio::join!(
+            self.call_with_breaker(SERVICE_BINANCE, self.fetch_binance_quotes()),
+            self.call_with_breaker(SERVICE_COINBASE, self.fetch_coinbase_quotes()),
+            self.call_with_breaker(SERVICE_KRAKEN, self.fetch_kraken_quotes()),
+        );
+
+        let mut quotes: HashMap<&str, Vec<ExchangeQuote>> = HashMap::new();
+        for (exchange, result) in [(SERVICE_BINANCE, binance), (SERVICE_COINBASE, coinbase), (SERVICE_KRAKEN, kraken)] {
+            match result {
+                Ok(exchange_quotes) => {
+                    for (symbol, quote) in exchange_quotes {
+                        quotes.entry(symbol).or_default().push(quote);
+                    }
+                }
+                Err(e) => warn!(exchange, error = %e, "Exchange left out of VWAP prices"),
+            }
+        }
+
+        let prices: HashMap<String, VwapPrice> = DASHBOARD_SYMBOLS.iter()
+            .filter_map(|symbol| Some((symbol.to_string(), volume_weighted(quotes.get(symbol)?, max_deviation_percent)?)))
+            .collect();
+
+        let missing: Vec<String> = DASHBOARD_SYMBOLS.iter()
+            .filter(|symbol| !prices.contains_key(**symbol))
+            .map(|symbol| symbol.to_string())
+            .collect();
+        if !missing.is_empty() {
+            let min_symbols = self.min_crypto_symbols.load(Ordering::Relaxed);
+            if prices.len() < min_symbols {
+                self.record_failure();
+                return Err(ExternalApiError::MissingSymbols { endpoint: "vwap".to_string(), missing }.into());
+            }
+            warn!(missing = ?missing, "No exchange quoted some symbols, continuing with the rest");
+        }
+
+        self.record_success();
+        Ok(prices)
+    }
+
+    /// Binance 24h tickers for every dashboard coin (one request)
+    async fn fetch_binance_quotes(&self) -> Result<Vec<(&'static str, ExchangeQuote)>> {
+        self.fetch_with_retry(BINANCE_MULTI_PRICE_URL, |tickers: BinanceMultiTickerResponse| {
+            tickers.into_iter()
+                .filter_map(|ticker| {
+                    let symbol = *DASHBOARD_SYMBOLS.iter()
+                        .find(|symbol| ticker.symbol.strip_suffix("USDT") == Some(**symbol))?;
+                    let quote = ExchangeQuote {
+                        exchange: SERVICE_BINANCE,
+                        price: ticker.last_price.parse().ok().filter(|price: &f64| *price > 0.0)?,
+                        change_24h: ticker.price_change_percent.parse().ok(),
+                        volume: ticker.volume.parse().unwrap_or(0.0),
+                    };
+                    Some((symbol, quote))
+                })
+                .collect()
+        }).await
+    }
+
+    /// Coinbase 24h stats, one request per listed coin
+    async fn fetch_coinbase_quotes(&self) -> Result<Vec<(&'static str, ExchangeQuote)>> {
+        let requests = COINBASE_PRODUCTS.iter().map(|(symbol, product)| async move {
+            let url = COINBASE_STATS_URL_TEMPLATE.replace("{product}", product);
+            let stats: CoinbaseStats = self.fetch_with_retry(&url, |stats: CoinbaseStats| stats).await?;
+            let price: f64 = stats.last.parse().unwrap_or(0.0);
+            let open: f64 = stats.open.parse().unwrap_or(0.0);
+            if price <= 0.0 {
+                return Err(ExternalApiError::invalid(SERVICE_COINBASE, format!("{} price={}", product, price)).into());
+            }
+            Ok::<_, anyhow::Error>((*symbol, ExchangeQuote {
+                exchange: SERVICE_COINBASE,
+                price,
+                change_24h: (open > 0.0).then(|| (price - open) / open * 100.0),
+                volume: stats.volume.parse().unwrap_or(0.0),
+            }))
+        });
+
+        let mut quotes = Vec::new();
+        let mut first_error = None;
+        for result in futures::future::join_all(requests).await {
+            match result {
+                Ok(quote) => quotes.push(quote),
+                Err(e) => {
+                    warn!(error = %e, "Coinbase quote failed");
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) if quotes.is_empty() => Err(e),
+            _ => Ok(quotes),
+        }
+    }
+
+    /// Kraken tickers for every listed coin (one request)
+    ///
+    /// Kraken only reports the change since 00:00 UTC, so its quotes carry no 24h change.
+    async fn fetch_kraken_quotes(&self) -> Result<Vec<(&'static str, ExchangeQuote)>> {
+        let response: KrakenTickerResponse = self.fetch_with_retry(KRAKEN_TICKER_URL, |response: KrakenTickerResponse| response).await?;
+        if !response.error.is_empty() {
+            return Err(ExternalApiError::invalid(SERVICE_KRAKEN, response.error.join(", ")).into());
+        }
+
+        Ok(KRAKEN_PAIRS.iter()
+            .filter_map(|(symbol, pair, result_key)| {
+                let ticker = response.result.get(*result_key).or_else(|| response.result.get(*pair))?;
+                let quote = ExchangeQuote {
+                    exchange: SERVICE_KRAKEN,
+                    price: ticker.c.first()?.parse().ok().filter(|price: &f64| *price > 0.0)?,
+                    change_24h: None,
+                    volume: ticker.v.get(1).and_then(|volume| volume.parse().ok()).unwrap_or(0.0),
+                };
+                Some((*symbol, quote))
+            })
+            .collect())
+    }
+}
@@ -9,3 +9,59 @@ include!("models.rs");
 include!("market_data_core.rs");
 include!("crypto_fetchers.rs");
 include!("market_fetchers.rs");
+include!("exchange_fetchers.rs");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_data_missing_eth_dominance_is_not_zero() {
+        let mut data = GlobalData {
+            market_cap: 3.2e12,
+            volume_24h: 1.1e11,
+            market_cap_change_percentage_24h_usd: -0.4,
+            btc_market_cap_percentage: 57.1,
+            eth_market_cap_percentage: None,
+            source: None,
+            last_updated: String::new(),
+        };
+        assert_eq!(data.invalid_fields(&DEFAULT_REQUIRED_GLOBAL_FIELDS), vec!["eth_dominance=missing"]);
+        assert!(data.invalid_fields(&[GlobalField::MarketCap, GlobalField::BtcDominance]).is_empty());
+
+        data.eth_market_cap_percentage = Some(0.0);
+        data.volume_24h = 0.0;
+        assert_eq!(data.invalid_fields(&DEFAULT_REQUIRED_GLOBAL_FIELDS), vec!["volume_24h=0"]);
+    }
+
+    #[test]
+    fn test_single_ticker_fills_coin_missing_from_multi_ticker() {
+        let ticker = |symbol: &str, price: &str| BinanceBtcPrice {
+            symbol: symbol.to_string(),
+            last_price: price.to_string(),
+            price_change_percent: "1.5".to_string(),
+            volume: String::new(),
+        };
+        let mut prices = binance_ticker_prices(vec![ticker("BTCUSDT", "96000"), ticker("DOGEUSDT", "0.3")]);
+        assert_eq!(prices.keys().collect::<Vec<_>>(), vec!["BTC"]);
+
+        // /ticker/24hr?symbol= returns a single object, not an array
+        let single: BinanceBtcPrice = serde_json::from_str(
+            r#"{"symbol":"ETHUSDT","lastPrice":"3600.10","priceChangePercent":"-2.0","volume":"1"}"#,
+        ).unwrap();
+        prices.extend(binance_ticker_prices([single]));
+        assert_eq!(prices["ETH"], (3600.10, -2.0));
+    }
+
+    #[test]
+    fn test_cmc_fng_response_is_tagged_with_its_source() {
+        let response: CmcFngResponse = serde_json::from_str(
+            r#"{"data":{"value":38,"update_time":"2025-11-15T13:00:00.000Z","value_classification":"Fear"},"status":{"error_code":"0"}}"#,
+        ).unwrap();
+        let fng = response.data.into_fng_data().unwrap();
+        assert_eq!((fng.value, fng.value_classification.as_deref()), (38, Some("Fear")));
+        assert_eq!(fng.source.as_deref(), Some(SERVICE_COINMARKETCAP));
+
+        assert!(CmcFngData { value: 140.0, value_classification: None }.into_fng_data().is_none());
+    }
+}
@@ -5,9 +5,13 @@
 use reqwest::Client;
 use anyhow::Result;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
-use tracing::{info, warn, error};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use tracing::{debug, info, warn, error};
 use crate::performance::OPTIMIZED_HTTP_CLIENT;
+use super::circuit_breaker::CircuitBreaker;
+use super::errors::ExternalApiError;
+use super::provider_stats::ProviderStats;
+use super::retry_budget::RetryBudget;
 
 
 /// Market Data API
@@ -23,6 +27,64 @@ pub struct MarketDataApi {
     pub successful_calls: Arc<AtomicUsize>,
     pub failed_calls: Arc<AtomicUsize>,
     pub last_call_timestamp: Arc<AtomicU64>,
+    // Unix seconds of the last successful call (0 = none yet); lets health checks skip the ping
+    pub last_success_timestamp: Arc<AtomicU64>,
+    // Remaining provider quota as reported by rate-limit headers (QUOTA_UNKNOWN until seen)
+    pub coingecko_quota_remaining: Arc<AtomicU64>,
+    pub cmc_quota_remaining: Arc<AtomicU64>,
+    // Per-provider circuit breaker (see SERVICE_* constants)
+    pub circuit_breaker: Arc<CircuitBreaker>,
+    // Fewest DASHBOARD_SYMBOLS a Binance multi-ticker response may contain
+    pub min_crypto_symbols: Arc<AtomicUsize>,
+    // Calls and successes per provider (see SERVICE_* constants)
+    pub provider_stats: Arc<ProviderStats>,
+    // Try the historically more reliable global data source first
+    pub adaptive_source_ordering: Arc<AtomicBool>,
+    // Global data fields a provider must return for the response to count as valid
+    pub required_global_fields: Arc<parking_lot::RwLock<Vec<GlobalField>>>,
+    // (symbol, name) of the US indices / ETF proxies fetched from Finnhub
+    pub stock_indices: Arc<parking_lot::RwLock<Vec<(String, String)>>>,
+    // Retries all retry loops may spend together per aggregation (MAX_RETRIES_PER_TICK)
+    pub retry_budget: Arc<RetryBudget>,
+}
+
+/// A successful call this recent makes `health_check` skip its live ping
+const RECENT_SUCCESS_WINDOW: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Current Unix time in whole seconds (0 if the clock is before the epoch)
+fn unix_now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Providers whose remaining-quota response headers are tracked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaProvider {
+    CoinGecko,
+    CoinMarketCap,
+}
+
+impl QuotaProvider {
+    /// Candidate header names carrying the remaining quota (first one present wins)
+    fn header_names(self) -> &'static [&'static str] {
+        match self {
+            QuotaProvider::CoinGecko => &["x-ratelimit-remaining"],
+            QuotaProvider::CoinMarketCap => &["x-cmc-credits-remaining", "x-ratelimit-remaining"],
+        }
+    }
+
+    /// Resolve the provider from a request URL, if it is one we track
+    fn from_url(url: &str) -> Option<Self> {
+        if url.contains("api.coingecko.com") {
+            Some(QuotaProvider::CoinGecko)
+        } else if url.contains("coinmarketcap.com") {
+            Some(QuotaProvider::CoinMarketCap)
+        } else {
+            None
+        }
+    }
 }
 
 impl MarketDataApi {
@@ -57,24 +119,48 @@ impl MarketDataApi {
             successful_calls: Arc::new(AtomicUsize::new(0)),
             failed_calls: Arc::new(AtomicUsize::new(0)),
             last_call_timestamp: Arc::new(AtomicU64::new(0)),
+            last_success_timestamp: Arc::new(AtomicU64::new(0)),
+            coingecko_quota_remaining: Arc::new(AtomicU64::new(QUOTA_UNKNOWN)),
+            cmc_quota_remaining: Arc::new(AtomicU64::new(QUOTA_UNKNOWN)),
+            circuit_breaker: Arc::new(CircuitBreaker::default()),
+            min_crypto_symbols: Arc::new(AtomicUsize::new(DASHBOARD_SYMBOLS.len())),
+            provider_stats: Arc::new(ProviderStats::default()),
+            adaptive_source_ordering: Arc::new(AtomicBool::new(false)),
+            required_global_fields: Arc::new(parking_lot::RwLock::new(DEFAULT_REQUIRED_GLOBAL_FIELDS.to_vec())),
+            stock_indices: Arc::new(parking_lot::RwLock::new(
+                DEFAULT_STOCK_INDICES.iter().map(|(symbol, name)| (symbol.to_string(), name.to_string())).collect(),
+            )),
+            retry_budget: Arc::new(RetryBudget::default()),
         })
     }
 
     /// Health check for Market Data API
-    pub async fn health_check(&self) -> bool {
+    ///
+    /// Returns the connectivity error when the API is unreachable.
+    ///
+    /// A successful call within `RECENT_SUCCESS_WINDOW` proves the APIs reachable,
+    /// so the Binance ping only runs when there has been no recent traffic
+    /// (e.g. on followers), keeping health checks off the providers' rate limits.
+    pub async fn health_check(&self) -> Result<()> {
+        if let Some(age) = self.last_success_age() {
+            if age <= RECENT_SUCCESS_WINDOW {
+                debug!(age_secs = age.as_secs(), "Market Data API healthy from a recent successful call");
+                return Ok(());
+            }
+        }
+
         match self.test_api_connectivity().await {
             Ok(_) => {
                 info!("Market Data API connectivity test passed");
-                true
+                Ok(())
             }
             Err(e) => {
-                let error_str = e.to_string();
-                if error_str.contains("429") || error_str.contains("Too Many Requests") {
-                    warn!("Market Data API health check: Rate limited, but service is available");
-                    true // Rate limiting means API is working, just busy
+                if ExternalApiError::find(&e).is_some_and(ExternalApiError::is_transient) {
+                    warn!(error = %e, "Market Data API health check: Rate limited, but service is available");
+                    Ok(()) // Rate limiting means API is working, just busy
                 } else {
                     error!(error = %e, "Market Data API connectivity test failed");
-                    false
+                    Err(e)
                 }
             }
         }
@@ -86,34 +172,134 @@ impl MarketDataApi {
         let response = self.client
             .get("https://api.binance.com/api/v3/ping")
             .send()
-            .await?;
+            .await
+            .map_err(|e| ExternalApiError::from_reqwest(SERVICE_BINANCE, e))?;
 
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            Err(anyhow::anyhow!("API connectivity test failed with status: {}", response.status()))
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            status if status == 418 || status == 429 => Err(ExternalApiError::RateLimited {
+                endpoint: SERVICE_BINANCE.to_string(),
+                attempts: 1,
+            }.into()),
+            status => Err(ExternalApiError::invalid(SERVICE_BINANCE, format!("status {}", status)).into()),
         }
     }
 
     /// Record an API call for statistics
     pub fn record_api_call(&self) {
         self.api_calls_count.fetch_add(1, Ordering::Relaxed);
-        self.last_call_timestamp.store(
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or(std::time::Duration::from_secs(0))
-                .as_secs(),
-            Ordering::Relaxed
-        );
+        self.last_call_timestamp.store(unix_now_secs(), Ordering::Relaxed);
     }
 
     /// Record a successful API call
     pub fn record_success(&self) {
         self.successful_calls.fetch_add(1, Ordering::Relaxed);
+        self.last_success_timestamp.store(unix_now_secs(), Ordering::Relaxed);
+    }
+
+    /// Time since the last successful call, if there was one
+    pub fn last_success_age(&self) -> Option<std::time::Duration> {
+        match self.last_success_timestamp.load(Ordering::Relaxed) {
+            0 => None,
+            at => Some(std::time::Duration::from_secs(unix_now_secs().saturating_sub(at))),
+        }
     }
 
     /// Record a failed API call
     pub fn record_failure(&self) {
         self.failed_calls.fetch_add(1, Ordering::Relaxed);
     }
-}
\ No newline at end of file
+
+    /// Run an upstream call through the circuit breaker for `service`
+    ///
+    /// Fails fast without touching the network while the circuit is open.
+    pub async fn call_with_breaker<T, Fut>(&self, service: &str, call: Fut) -> Result<T>
+    where
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        if !self.circuit_breaker.can_execute(service).await {
+            return Err(ExternalApiError::CircuitOpen { service: service.to_string() }.into());
+        }
+
+        let result = call.await;
+        self.provider_stats.record(service, result.is_ok());
+        match &result {
+            Ok(_) => self.circuit_breaker.record_success(service).await,
+            Err(_) => self.circuit_breaker.record_failure(service).await,
+        }
+        result
+    }
+
+    /// Order in which global data sources are tried
+    ///
+    /// CoinGecko then CoinMarketCap, unless adaptive ordering is on and CoinMarketCap
+    /// has the better success record.
+    pub fn global_source_order(&self) -> Vec<&'static str> {
+        let default_order = [SERVICE_COINGECKO, SERVICE_COINMARKETCAP];
+        if self.adaptive_source_ordering.load(Ordering::Relaxed) {
+            self.provider_stats.order_by_reliability(&default_order)
+        } else {
+            default_order.to_vec()
+        }
+    }
+
+    /// Store the remaining quota reported in a provider's response headers
+    ///
+    /// Responses without a recognised header leave the last known value untouched.
+    pub fn record_quota_headers(&self, provider: QuotaProvider, headers: &reqwest::header::HeaderMap) {
+        let remaining = provider.header_names().iter().find_map(|name| {
+            headers.get(*name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<f64>().ok())
+        });
+
+        if let Some(remaining) = remaining {
+            let remaining = remaining.max(0.0) as u64;
+            self.quota_slot(provider).store(remaining, Ordering::Relaxed);
+            if remaining <= QUOTA_LOW_WATERMARK {
+                warn!(provider = ?provider, remaining, "API quota nearly exhausted");
+            }
+        }
+    }
+
+    /// Remaining quota for a provider, or None if it hasn't been reported yet
+    pub fn quota_remaining(&self, provider: QuotaProvider) -> Option<u64> {
+        match self.quota_slot(provider).load(Ordering::Relaxed) {
+            QUOTA_UNKNOWN => None,
+            remaining => Some(remaining),
+        }
+    }
+
+    /// Whether a provider's reported quota has dropped to the low watermark
+    pub fn is_quota_low(&self, provider: QuotaProvider) -> bool {
+        self.quota_remaining(provider)
+            .is_some_and(|remaining| remaining <= QUOTA_LOW_WATERMARK)
+    }
+
+    /// Take a retry from the per-aggregation budget; false (logged) once it is spent
+    ///
+    /// Retry loops give up with their last error then, so the group counts as failed.
+    pub fn retry_allowed(&self, endpoint: &str) -> bool {
+        let allowed = self.retry_budget.try_take();
+        if !allowed {
+            warn!(endpoint, "Retry budget for this aggregation spent (MAX_RETRIES_PER_TICK), not retrying");
+        }
+        allowed
+    }
+
+    fn quota_slot(&self, provider: QuotaProvider) -> &AtomicU64 {
+        match provider {
+            QuotaProvider::CoinGecko => &self.coingecko_quota_remaining,
+            QuotaProvider::CoinMarketCap => &self.cmc_quota_remaining,
+        }
+    }
+}
+
+/// Exponential backoff with full jitter: a random delay in `[0, base_ms * 2^attempt]`
+///
+/// Used by every retry loop so instances that hit a rate limit together (e.g. both
+/// nodes during a leader failover) don't retry in lockstep and re-trigger it.
+pub fn jittered_backoff(base_ms: u64, attempt: u32) -> std::time::Duration {
+    let ceiling = base_ms.saturating_mul(2_u64.saturating_pow(attempt));
+    std::time::Duration::from_millis(rand::random_range(0..=ceiling))
+}
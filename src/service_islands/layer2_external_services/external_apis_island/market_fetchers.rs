@@ -6,71 +6,94 @@ use futures;
 
 impl MarketDataApi {
     /// Fetch global market data with fallback chain
-    pub async fn fetch_global_data(&self) -> Result<serde_json::Value> {
+    ///
+    /// Sources are tried in `global_source_order`; CoinMarketCap is skipped while its
+    /// quota is nearly exhausted.
+    pub async fn fetch_global_data(&self) -> Result<GlobalData> {
         self.record_api_call();
 
-        // Try CoinGecko first
-        match self.fetch_global_data_coingecko().await {
-            Ok(data) => {
-                self.record_success();
-                Ok(data)
+        let mut errors: Vec<anyhow::Error> = Vec::new();
+        for service in self.global_source_order() {
+            // Don't burn the last few CoinMarketCap credits on an optional source
+            if service == SERVICE_COINMARKETCAP && self.is_quota_low(QuotaProvider::CoinMarketCap) {
+                warn!("Skipping CoinMarketCap global data: quota nearly exhausted");
+                errors.push(anyhow::anyhow!("Fallback skipped: CoinMarketCap quota nearly exhausted"));
+                continue;
             }
-            Err(e) => {
-                warn!(error = %e, "CoinGecko global data failed, trying CoinMarketCap");
-                // Fallback to CoinMarketCap
-                match self.fetch_global_data_cmc().await {
-                    Ok(data) => {
-                        self.record_success();
-                        Ok(data)
-                    }
-                    Err(cmc_err) => {
-                        self.record_failure();
-                        error!("Both CoinGecko and CoinMarketCap failed for global data");
-                        Err(anyhow::anyhow!("Primary error: {}. Fallback error: {}", e, cmc_err))
-                    }
+
+            let result = if service == SERVICE_COINMARKETCAP {
+                self.call_with_breaker(service, self.fetch_global_data_cmc()).await
+            } else {
+                self.call_with_breaker(service, self.fetch_global_data_coingecko()).await
+            };
+            match result {
+                Ok(data) => {
+                    self.record_success();
+                    return Ok(data);
+                }
+                Err(e) => {
+                    warn!(service, error = %e, "Global data source failed, trying next");
+                    errors.push(e);
                 }
             }
         }
+
+        self.record_failure();
+        error!("All global data sources failed");
+        let last = errors.pop().unwrap_or_else(|| anyhow::anyhow!("No global data source available"));
+        if errors.is_empty() {
+            return Err(last);
+        }
+        let earlier: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        let message = format!("Primary error: {}. Fallback error: {}", earlier.join("; "), last);
+        Err(last.context(message))
     }
 
     /// Fetch global data from CoinGecko
-    async fn fetch_global_data_coingecko(&self) -> Result<serde_json::Value> {
-        let result = self.fetch_with_retry(BASE_GLOBAL_URL, |global_data: CoinGeckoGlobal| {
-            let market_cap = global_data.data.total_market_cap.get("usd").copied().unwrap_or(0.0);
-            let volume_24h = global_data.data.total_volume.get("usd").copied().unwrap_or(0.0);
-            let market_cap_change_24h = global_data.data.market_cap_change_percentage_24h_usd;
-            let btc_dominance = global_data.data.market_cap_percentage.get("btc").copied().unwrap_or(0.0);
-            let eth_dominance = global_data.data.market_cap_percentage.get("eth").copied().unwrap_or(0.0);
-
-            serde_json::json!({
-                "market_cap": market_cap,
-                "volume_24h": volume_24h,
-                "market_cap_change_percentage_24h_usd": market_cap_change_24h,
-                "btc_market_cap_percentage": btc_dominance,
-                "eth_market_cap_percentage": eth_dominance,
-                "source": "coingecko",
-                "last_updated": chrono::Utc::now().to_rfc3339()
-            })
+    pub(crate) async fn fetch_global_data_coingecko(&self) -> Result<GlobalData> {
+        // Missing totals default to 0.0 here; `validate_global_data` rejects the required ones
+        let result = self.fetch_with_retry(BASE_GLOBAL_URL, |global_data: CoinGeckoGlobal| GlobalData {
+            market_cap: global_data.data.total_market_cap.get("usd").copied().unwrap_or(0.0),
+            volume_24h: global_data.data.total_volume.get("usd").copied().unwrap_or(0.0),
+            market_cap_change_percentage_24h_usd: global_data.data.market_cap_change_percentage_24h_usd,
+            btc_market_cap_percentage: global_data.data.market_cap_percentage.get("btc").copied().unwrap_or(0.0),
+            eth_market_cap_percentage: global_data.data.market_cap_percentage.get("eth").copied(),
+            source: Some(SERVICE_COINGECKO.to_string()),
+            last_updated: chrono::Utc::now().to_rfc3339(),
         }).await?;
 
-        // Post-processing validation: check if we got meaningful data
-        let market_cap = result.get("market_cap").and_then(|v| v.as_f64()).unwrap_or(0.0);
-        let volume_24h = result.get("volume_24h").and_then(|v| v.as_f64()).unwrap_or(0.0);
-        let btc_dominance = result.get("btc_market_cap_percentage").and_then(|v| v.as_f64()).unwrap_or(0.0);
-
-        // Critical validation: if any essential data is missing or invalid, return error
-        if market_cap <= 0.0 || volume_24h <= 0.0 || btc_dominance <= 0.0 {
-            return Err(anyhow::anyhow!(
-                "CoinGecko data validation failed: market_cap={}, volume_24h={}, btc_dominance={}",
-                market_cap, volume_24h, btc_dominance
-            ));
-        }
+        self.validate_global_data(SERVICE_COINGECKO, result)
+    }
 
-        Ok(result)
+    /// Reject global data missing any of `required_global_fields`
+    fn validate_global_data(&self, endpoint: &str, data: GlobalData) -> Result<GlobalData> {
+        let invalid = data.invalid_fields(&self.required_global_fields.read());
+        if !invalid.is_empty() {
+            return Err(ExternalApiError::invalid(endpoint, invalid.join(", ")).into());
+        }
+        Ok(data)
     }
 
     /// Fetch global data from CoinMarketCap
-    async fn fetch_global_data_cmc(&self) -> Result<serde_json::Value> {
+    pub(crate) async fn fetch_global_data_cmc(&self) -> Result<GlobalData> {
+        let cmc_data: CmcGlobalResponse = self.fetch_cmc(CMC_GLOBAL_URL).await?;
+        let Some(usd_quote) = cmc_data.data.quote.get("USD") else {
+            return Err(ExternalApiError::invalid(SERVICE_COINMARKETCAP, "missing USD quote").into());
+        };
+
+        self.validate_global_data(SERVICE_COINMARKETCAP, GlobalData {
+            market_cap: usd_quote.total_market_cap,
+            volume_24h: usd_quote.total_volume_24h,
+            market_cap_change_percentage_24h_usd: usd_quote.market_cap_change_percentage_24h,
+            btc_market_cap_percentage: usd_quote.btc_dominance,
+            eth_market_cap_percentage: usd_quote.eth_dominance,
+            source: Some(SERVICE_COINMARKETCAP.to_string()),
+            last_updated: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// GET a CoinMarketCap endpoint with the API key, retrying on 429
+    async fn fetch_cmc<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
         let cmc_key = self.cmc_api_key.as_ref()
             .ok_or_else(|| anyhow::anyhow!("CoinMarketCap API key not provided"))?;
 
@@ -79,86 +102,107 @@ impl MarketDataApi {
 
         while attempts < max_attempts {
             let response = self.client
-                .get(CMC_GLOBAL_URL)
+                .get(url)
                 .header("X-CMC_PRO_API_KEY", cmc_key)
                 .header("Accept", "application/json")
                 .send()
-                .await?;
+                .await
+                .map_err(|e| ExternalApiError::from_reqwest(SERVICE_COINMARKETCAP, e))?;
+
+            self.record_quota_headers(QuotaProvider::CoinMarketCap, response.headers());
 
             match response.status() {
                 status if status.is_success() => {
-                    let cmc_data: CmcGlobalResponse = response.json().await?;
-
-                    if let Some(usd_quote) = cmc_data.data.quote.get("USD") {
-                        return Ok(serde_json::json!({
-                            "market_cap": usd_quote.total_market_cap,
-                            "volume_24h": usd_quote.total_volume_24h,
-                            "market_cap_change_percentage_24h_usd": usd_quote.market_cap_change_percentage_24h,
-                            "btc_market_cap_percentage": usd_quote.btc_dominance,
-                            "eth_market_cap_percentage": usd_quote.eth_dominance,
-                            "source": "coinmarketcap",
-                            "last_updated": chrono::Utc::now().to_rfc3339()
-                        }));
-                    }
-                    return Err(anyhow::anyhow!("Invalid CoinMarketCap global response structure"));
+                    return response.json().await
+                        .map_err(|e| ExternalApiError::invalid(SERVICE_COINMARKETCAP, e.to_string()).into());
                 }
                 status if status == 429 => {
                     attempts += 1;
-                    if attempts >= max_attempts {
-                        return Err(anyhow::anyhow!("CoinMarketCap global API rate limit exceeded after {} attempts", max_attempts));
+                    if attempts >= max_attempts || !self.retry_allowed(SERVICE_COINMARKETCAP) {
+                        return Err(ExternalApiError::RateLimited { endpoint: SERVICE_COINMARKETCAP.to_string(), attempts }.into());
                     }
 
-                    let delay = std::time::Duration::from_millis(1000 * (2_u64.pow(attempts)));
-                    warn!(delay_ms = delay.as_millis(), attempt = attempts, max_attempts = max_attempts, "CoinMarketCap global API rate limit (429), retrying");
+                    let delay = jittered_backoff(1000, attempts);
+                    warn!(delay_ms = delay.as_millis(), attempt = attempts, max_attempts = max_attempts, "CoinMarketCap API rate limit (429), retrying");
                     tokio::time::sleep(delay).await;
                     continue;
                 }
                 status => {
-                    return Err(anyhow::anyhow!("CoinMarketCap global API returned status: {}", status));
+                    return Err(ExternalApiError::invalid(SERVICE_COINMARKETCAP, format!("status {}", status)).into());
                 }
             }
         }
 
-        Err(anyhow::anyhow!("CoinMarketCap global API max retry attempts reached"))
+        Err(ExternalApiError::RateLimited { endpoint: SERVICE_COINMARKETCAP.to_string(), attempts }.into())
     }
 
-    /// Fetch Fear & Greed Index
-    pub async fn fetch_fear_greed_index(&self) -> Result<serde_json::Value> {
+    /// Fetch Fear & Greed Index with fallback chain
+    ///
+    /// alternative.me first, then CoinMarketCap's index when a key is configured (and
+    /// its quota isn't nearly exhausted). The resolved provider is in `FngData::source`.
+    pub async fn fetch_fear_greed_index(&self) -> Result<FngData> {
         self.record_api_call();
 
-        match self.fetch_fear_greed_internal().await {
+        let primary = match self.call_with_breaker(SERVICE_ALTERNATIVE_ME, self.fetch_fear_greed_internal()).await {
+            Ok(data) => {
+                self.record_success();
+                return Ok(data);
+            }
+            Err(e) => e,
+        };
+
+        if self.cmc_api_key.is_none() {
+            self.record_failure();
+            return Err(primary);
+        }
+        if self.is_quota_low(QuotaProvider::CoinMarketCap) {
+            warn!("Skipping CoinMarketCap Fear & Greed fallback: quota nearly exhausted");
+            self.record_failure();
+            let message = format!("{}. Fallback skipped: CoinMarketCap quota nearly exhausted", primary);
+            return Err(primary.context(message));
+        }
+
+        warn!(error = %primary, "alternative.me Fear & Greed failed, trying CoinMarketCap");
+        match self.call_with_breaker(SERVICE_COINMARKETCAP, self.fetch_fear_greed_cmc()).await {
             Ok(data) => {
                 self.record_success();
                 Ok(data)
             }
             Err(e) => {
                 self.record_failure();
-                Err(e)
+                let message = format!("Primary error: {}. Fallback error: {}", primary, e);
+                Err(e.context(message))
             }
         }
     }
 
+    /// Fear & Greed Index from CoinMarketCap
+    async fn fetch_fear_greed_cmc(&self) -> Result<FngData> {
+        let response: CmcFngResponse = self.fetch_cmc(CMC_FNG_URL).await?;
+        response.data.into_fng_data()
+            .ok_or_else(|| ExternalApiError::invalid(SERVICE_COINMARKETCAP, "Fear & Greed value out of range").into())
+    }
+
     /// Internal Fear & Greed fetching
-    async fn fetch_fear_greed_internal(&self) -> Result<serde_json::Value> {
-        self.fetch_with_retry(BASE_FNG_URL, |fng_data: FearGreedResponse| {
-            let fng_value: u32 = fng_data
-                .data
-                .first()
-                .and_then(|d| d.value.parse().ok())
-                .unwrap_or(50); // Default neutral value
-
-            serde_json::json!({
-                "value": fng_value,
-                "last_updated": chrono::Utc::now().to_rfc3339()
+    async fn fetch_fear_greed_internal(&self) -> Result<FngData> {
+        let fng_data = self.fetch_with_retry(BASE_FNG_URL, |fng_data: FearGreedResponse| {
+            let latest = fng_data.data.into_iter().next()?;
+            Some(FngData {
+                value: latest.value.parse().ok()?,
+                value_classification: latest.value_classification,
+                source: Some(SERVICE_ALTERNATIVE_ME.to_string()),
+                last_updated: chrono::Utc::now().to_rfc3339(),
             })
-        }).await
+        }).await?;
+
+        fng_data.ok_or_else(|| ExternalApiError::invalid(SERVICE_ALTERNATIVE_ME, "missing or non-numeric value").into())
     }
 
     /// Fetch RSI data
-    pub async fn fetch_btc_rsi_14(&self) -> Result<serde_json::Value> {
+    pub async fn fetch_btc_rsi_14(&self) -> Result<RsiData> {
         self.record_api_call();
 
-        match self.fetch_btc_rsi_14_internal().await {
+        match self.call_with_breaker(SERVICE_TAAPI, self.fetch_btc_rsi_14_internal()).await {
             Ok(data) => {
                 self.record_success();
                 Ok(data)
@@ -171,7 +215,7 @@ impl MarketDataApi {
     }
 
     /// Internal RSI fetching
-    async fn fetch_btc_rsi_14_internal(&self) -> Result<serde_json::Value> {
+    async fn fetch_btc_rsi_14_internal(&self) -> Result<RsiData> {
         let url = BASE_RSI_URL_TEMPLATE.replace("{secret}", &self.taapi_secret);
 
         // RSI uses a different approach because URL is dynamic
@@ -182,42 +226,45 @@ impl MarketDataApi {
             let response = self.client
                 .get(&url)
                 .send()
-                .await?;
+                .await
+                .map_err(|e| ExternalApiError::from_reqwest(SERVICE_TAAPI, e.without_url()))?;
 
             match response.status() {
                 status if status.is_success() => {
-                    let btc_rsi_14_data: TaapiRsiResponse = response.json().await?;
-                    return Ok(serde_json::json!({
-                        "value": btc_rsi_14_data.value,
-                        "period": "14",
-                        "last_updated": chrono::Utc::now().to_rfc3339()
-                    }));
+                    let btc_rsi_14_data: TaapiRsiResponse = response.json().await
+                        .map_err(|e| ExternalApiError::invalid(SERVICE_TAAPI, e.without_url().to_string()))?;
+                    return Ok(RsiData {
+                        value: btc_rsi_14_data.value,
+                        period: Some("14".to_string()),
+                        source: Some(SERVICE_TAAPI.to_string()),
+                        last_updated: chrono::Utc::now().to_rfc3339(),
+                    });
                 }
                 status if status == 429 => {
                     attempts += 1;
-                    if attempts >= max_attempts {
-                        return Err(anyhow::anyhow!("RSI API rate limit exceeded after {} attempts", max_attempts));
+                    if attempts >= max_attempts || !self.retry_allowed(SERVICE_TAAPI) {
+                        return Err(ExternalApiError::RateLimited { endpoint: SERVICE_TAAPI.to_string(), attempts }.into());
                     }
 
-                    let delay = std::time::Duration::from_millis(1000 * (2_u64.pow(attempts)));
+                    let delay = jittered_backoff(1000, attempts);
                     warn!(delay_ms = delay.as_millis(), attempt = attempts, max_attempts = max_attempts, "RSI API rate limit (429), retrying");
                     tokio::time::sleep(delay).await;
                     continue;
                 }
                 status => {
-                    return Err(anyhow::anyhow!("RSI API returned status: {}", status));
+                    return Err(ExternalApiError::invalid(SERVICE_TAAPI, format!("status {}", status)).into());
                 }
             }
         }
 
-        Err(anyhow::anyhow!("RSI API max retry attempts reached"))
+        Err(ExternalApiError::RateLimited { endpoint: SERVICE_TAAPI.to_string(), attempts }.into())
     }
 
     /// Fetch US Stock Market Indices from Finnhub
-    pub async fn fetch_us_stock_indices(&self) -> Result<serde_json::Value> {
+    pub async fn fetch_us_stock_indices(&self) -> Result<IndicesData> {
         self.record_api_call();
 
-        match self.fetch_us_indices_internal().await {
+        match self.call_with_breaker(SERVICE_FINNHUB, self.fetch_us_indices_internal()).await {
             Ok(data) => {
                 self.record_success();
                 Ok(data)
@@ -230,19 +277,15 @@ impl MarketDataApi {
     }
 
     /// Internal US stock indices fetching
-    async fn fetch_us_indices_internal(&self) -> Result<serde_json::Value> {
+    async fn fetch_us_indices_internal(&self) -> Result<IndicesData> {
         let finnhub_key = self.finnhub_api_key.as_ref()
             .ok_or_else(|| anyhow::anyhow!("Finnhub API key not provided"))?;
 
-        // Define the indices we want to fetch (using ETFs as proxies for free tier)
-        let indices = vec![
-            ("DIA", "SPDR Dow Jones Industrial Average ETF"),  // DJIA proxy
-            ("SPY", "SPDR S&P 500 ETF Trust"),                // S&P 500 proxy
-            ("QQQM", "INVESCO NASDAQ 100 ETF"),                      // Nasdaq 100 proxy
-        ];
+        // Configured indices (ETF proxies for the free tier by default)
+        let indices = self.stock_indices.read().clone();
 
         let mut results = HashMap::new();
-        let mut all_success = true;
+        let mut first_error = None;
 
         // Fetch each index concurrently
         let futures: Vec<_> = indices.iter().map(|(symbol, name)| {
@@ -259,103 +302,153 @@ impl MarketDataApi {
                     results.insert(symbol.to_string(), index_data);
                 }
                 Err(e) => {
-                    warn!(index_name = %name, error = %e, "Failed to fetch US stock index");
-                    all_success = false;
+                    // A permanent refusal is a plan limit, not an outage: report it on
+                    // the quote without failing the fetch (or tripping the breaker)
+                    let status = if matches!(ExternalApiError::find(&e), Some(ExternalApiError::Unavailable { .. })) {
+                        warn!(index_name = %name, error = %e, "US stock index unavailable on this Finnhub plan");
+                        "unavailable"
+                    } else {
+                        warn!(index_name = %name, error = %e, "Failed to fetch US stock index");
+                        first_error.get_or_insert(e);
+                        "failed"
+                    };
                     // Insert placeholder data for failed fetch
-                    results.insert(symbol.to_string(), serde_json::json!({
-                        "symbol": symbol,
-                        "name": name,
-                        "price": 0.0,
-                        "change": 0.0,
-                        "change_percent": 0.0,
-                        "status": "failed"
-                    }));
+                    results.insert(symbol.to_string(), IndexQuote {
+                        symbol: symbol.to_string(),
+                        name: name.to_string(),
+                        price: 0.0,
+                        change: 0.0,
+                        change_percent: 0.0,
+                        status: status.to_string(),
+                    });
                 }
             }
         }
 
-        if !all_success {
-            return Err(anyhow::anyhow!("Some US indices failed to fetch"));
+        if let Some(e) = first_error {
+            let message = format!("Some US indices failed to fetch: {}", e);
+            return Err(e.context(message));
         }
 
-        Ok(serde_json::json!({
-            "indices": results,
-            "source": "finnhub",
-            "last_updated": chrono::Utc::now().to_rfc3339()
-        }))
+        Ok(IndicesData {
+            indices: results,
+            source: Some(SERVICE_FINNHUB.to_string()),
+            last_updated: chrono::Utc::now().to_rfc3339(),
+        })
     }
 
     /// Fetch single index from Finnhub
-    async fn fetch_single_index(&self, symbol: &str, name: &str, api_key: &str) -> Result<serde_json::Value> {
+    ///
+    /// Rate limits, server errors and network failures are retried; 403 (symbol not
+    /// on the plan) fails at once with `ExternalApiError::Unavailable`.
+    async fn fetch_single_index(&self, symbol: &str, name: &str, api_key: &str) -> Result<IndexQuote> {
         let url = format!("https://finnhub.io/api/v1/quote?symbol={}&token={}", symbol, api_key);
+        let endpoint = format!("{} {}", SERVICE_FINNHUB, symbol);
 
         let mut attempts = 0;
         let max_attempts = 3;
 
         while attempts < max_attempts {
-            let response = self.client
-                .get(&url)
-                .send()
-                .await?;
+            let response = match self.client.get(&url).send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    attempts += 1;
+                    let error = ExternalApiError::from_reqwest(&endpoint, e.without_url());
+                    if attempts >= max_attempts || !self.retry_allowed(&endpoint) {
+                        return Err(error.into());
+                    }
+
+                    let delay = jittered_backoff(1000, attempts);
+                    warn!(symbol = %symbol, error = %error, delay_ms = delay.as_millis(), attempt = attempts, max_attempts = max_attempts, "Finnhub request failed, retrying");
+                    tokio::time::sleep(delay).await;
+                    continue;
+                }
+            };
 
             match response.status() {
                 status if status.is_success() => {
-                    let finnhub_data: FinnhubQuoteResponse = response.json().await?;
+                    let finnhub_data: FinnhubQuoteResponse = response.json().await
+                        .map_err(|e| ExternalApiError::invalid(&endpoint, e.without_url().to_string()))?;
 
                     // Validate data
                     if finnhub_data.current_price <= 0.0 {
-                        return Err(anyhow::anyhow!("Invalid price data for {}: {}", symbol, finnhub_data.current_price));
+                        return Err(ExternalApiError::invalid(&endpoint, format!("price={}", finnhub_data.current_price)).into());
                     }
 
-                    return Ok(serde_json::json!({
-                        "symbol": symbol,
-                        "name": name,
-                        "price": finnhub_data.current_price,
-                        "change": finnhub_data.change,
-                        "change_percent": finnhub_data.percent_change,
-                        "status": "success"
-                    }));
+                    return Ok(IndexQuote {
+                        symbol: symbol.to_string(),
+                        name: name.to_string(),
+                        price: finnhub_data.current_price,
+                        change: finnhub_data.change,
+                        change_percent: finnhub_data.percent_change,
+                        status: "success".to_string(),
+                    });
                 }
                 status if status == 429 => {
                     attempts += 1;
-                    if attempts >= max_attempts {
-                        return Err(anyhow::anyhow!("Finnhub rate limit exceeded for {} after {} attempts", symbol, max_attempts));
+                    if attempts >= max_attempts || !self.retry_allowed(&endpoint) {
+                        return Err(ExternalApiError::RateLimited { endpoint, attempts }.into());
                     }
 
-                    let delay = std::time::Duration::from_millis(1000 * (2_u64.pow(attempts)));
+                    let delay = jittered_backoff(1000, attempts);
                     warn!(symbol = %symbol, delay_ms = delay.as_millis(), attempt = attempts, max_attempts = max_attempts, "Finnhub rate limit (429), retrying");
                     tokio::time::sleep(delay).await;
                     continue;
                 }
+                // Free tier: premium symbols are refused for good, never retry
+                status if status == 403 => {
+                    return Err(ExternalApiError::Unavailable { endpoint, status: status.as_u16() }.into());
+                }
+                status if status.is_server_error() => {
+                    attempts += 1;
+                    if attempts >= max_attempts || !self.retry_allowed(&endpoint) {
+                        return Err(ExternalApiError::invalid(&endpoint, format!("status {} after {} attempts", status, attempts)).into());
+                    }
+
+                    let delay = jittered_backoff(1000, attempts);
+                    warn!(symbol = %symbol, %status, delay_ms = delay.as_millis(), attempt = attempts, max_attempts = max_attempts, "Finnhub server error, retrying");
+                    tokio::time::sleep(delay).await;
+                    continue;
+                }
                 status => {
-                    return Err(anyhow::anyhow!("Finnhub API returned status {} for {}", status, symbol));
+                    return Err(ExternalApiError::invalid(&endpoint, format!("status {}", status)).into());
                 }
             }
         }
 
-        Err(anyhow::anyhow!("Finnhub API max retry attempts reached for {}", symbol))
+        Err(ExternalApiError::RateLimited { endpoint, attempts }.into())
     }
 
     /// Get API statistics
-    #[allow(dead_code)]
-    pub fn get_api_stats(&self) -> serde_json::Value {
+    pub async fn get_api_stats(&self) -> ApiStats {
         let total_calls = self.api_calls_count.load(std::sync::atomic::Ordering::Relaxed);
         let successful_calls = self.successful_calls.load(std::sync::atomic::Ordering::Relaxed);
-        let failed_calls = self.failed_calls.load(std::sync::atomic::Ordering::Relaxed);
-        let last_call = self.last_call_timestamp.load(std::sync::atomic::Ordering::Relaxed);
-
-        serde_json::json!({
-            "total_api_calls": total_calls,
-            "successful_calls": successful_calls,
-            "failed_calls": failed_calls,
-            "success_rate": if total_calls > 0 {
+
+        ApiStats {
+            total_api_calls: total_calls,
+            successful_calls,
+            failed_calls: self.failed_calls.load(std::sync::atomic::Ordering::Relaxed),
+            success_rate: if total_calls > 0 {
                 (successful_calls as f64 / total_calls as f64 * 100.0).round()
             } else {
                 0.0
             },
-            "last_call_timestamp": last_call,
-            "has_coinmarketcap_key": self.cmc_api_key.is_some(),
-            "has_finnhub_key": self.finnhub_api_key.is_some()
-        })
+            last_call_timestamp: self.last_call_timestamp.load(std::sync::atomic::Ordering::Relaxed),
+            last_success_timestamp: self.last_success_timestamp.load(std::sync::atomic::Ordering::Relaxed),
+            providers: self.provider_stats.snapshot(),
+            adaptive_source_ordering: self.adaptive_source_ordering.load(std::sync::atomic::Ordering::Relaxed),
+            global_source_order: self.global_source_order(),
+            has_coinmarketcap_key: self.cmc_api_key.is_some(),
+            has_finnhub_key: self.finnhub_api_key.is_some(),
+            quota_remaining: QuotaRemaining {
+                coingecko: self.quota_remaining(QuotaProvider::CoinGecko),
+                coinmarketcap: self.quota_remaining(QuotaProvider::CoinMarketCap),
+            },
+            circuit_breakers: CircuitBreakerStats {
+                services: self.circuit_breaker.status().await,
+                total_blocked: self.circuit_breaker.total_blocked(),
+                total_opened: self.circuit_breaker.total_opened(),
+            },
+        }
     }
 }
\ No newline at end of file
@@ -12,13 +12,28 @@
 pub mod market_data_api;
 pub mod api_aggregator;
 pub mod circuit_breaker;
+pub mod binance_ws_source;
+pub mod errors;
+pub mod api_self_test;
+pub mod price_vwap;
+pub mod provider_stats;
+pub mod retry_budget;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::sync::Arc;
 use tracing::info;
 
-use market_data_api::MarketDataApi;
-use api_aggregator::ApiAggregator;
+use market_data_api::{ApiStats, MarketDataApi};
+use api_aggregator::{AggregationStats, ApiAggregator};
+use crate::config::CategoryIntervals;
+use price_vwap::PriceMode;
+
+/// Body of `GET /stats`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ExternalApiStats {
+    pub api: ApiStats,
+    pub aggregations: AggregationStats,
+}
 
 /// External APIs Island - Main entry point for Layer 2
 ///
@@ -35,6 +50,9 @@ impl ExternalApisIsland {
         cmc_api_key: Option<String>,
         finnhub_api_key: Option<String>,
         cache_system: Option<Arc<crate::service_islands::layer1_infrastructure::CacheSystemIsland>>,
+        category_intervals: CategoryIntervals,
+        min_crypto_symbols: usize,
+        price_mode: PriceMode,
     ) -> Result<Self> {
         info!("Initializing External APIs Island");
 
@@ -47,19 +65,25 @@ impl ExternalApisIsland {
 
         // Initialize API Aggregator (move the original values)
         let aggregator = if let Some(cache) = cache_system {
-            Arc::new(ApiAggregator::with_cache_and_all_keys(
+            ApiAggregator::with_cache_and_all_keys(
                 taapi_secret,
                 cmc_api_key,
                 finnhub_api_key,
                 cache
-            ).await?)
+            ).await?
         } else {
-            Arc::new(ApiAggregator::with_all_keys(
+            ApiAggregator::with_all_keys(
                 taapi_secret,
                 cmc_api_key,
                 finnhub_api_key
-            ).await?)
+            ).await?
         };
+        let aggregator = Arc::new(
+            aggregator
+                .with_category_intervals(category_intervals)
+                .with_min_crypto_symbols(min_crypto_symbols)
+                .with_price_mode(price_mode),
+        );
 
         info!("External APIs Island initialized successfully");
 
@@ -70,10 +94,83 @@ impl ExternalApisIsland {
     }
 
     /// Health check for External APIs Island
-    pub async fn health_check(&self) -> Result<bool> {
-        let market_api_healthy = self.market_api.health_check().await;
-        let aggregator_healthy = self.aggregator.health_check().await;
-        Ok(market_api_healthy && aggregator_healthy)
+    ///
+    /// Returns the first failing component's error.
+    pub async fn health_check(&self) -> Result<()> {
+        // The aggregator's instance does the fetching, so its recent successes count
+        self.aggregator.market_api.health_check().await.context("Market Data API unhealthy")?;
+        if !self.aggregator.health_check().await {
+            anyhow::bail!("API Aggregator coordination test failed");
+        }
+        Ok(())
+    }
+
+    /// Live Binance price stream used by the aggregator (idle until started)
+    pub fn binance_ws(&self) -> &Arc<binance_ws_source::BinanceWsSource> {
+        &self.aggregator.binance_ws
+    }
+
+    /// Latest cached price for one coin, without a dashboard aggregation
+    pub async fn get_symbol_price(&self, symbol: &str) -> Option<crate::dto::MarketUpdatePayload> {
+        self.aggregator.get_symbol_price(symbol).await
+    }
+
+    /// Current circuit breaker status for every upstream provider
+    ///
+    /// Reads from the aggregator's MarketDataApi since that's the instance doing the fetching.
+    pub async fn circuit_breaker_status(&self) -> Vec<circuit_breaker::CircuitBreakerStatus> {
+        self.aggregator.market_api.circuit_breaker.status().await
+    }
+
+    /// API call counters and aggregation counters for `/stats`
+    ///
+    /// Reads the aggregator's MarketDataApi since that's the instance doing the fetching.
+    pub async fn stats(&self) -> ExternalApiStats {
+        ExternalApiStats {
+            api: self.aggregator.market_api.get_api_stats().await,
+            aggregations: self.aggregator.aggregation_stats(),
+        }
+    }
+
+    /// Order global data sources by their success rate (both API instances)
+    pub fn set_adaptive_source_ordering(&self, enabled: bool) {
+        use std::sync::atomic::Ordering;
+        self.market_api.adaptive_source_ordering.store(enabled, Ordering::Relaxed);
+        self.aggregator.market_api.adaptive_source_ordering.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Global data fields a provider response must carry (both API instances)
+    pub fn set_required_global_fields(&self, fields: &[market_data_api::GlobalField]) {
+        *self.market_api.required_global_fields.write() = fields.to_vec();
+        *self.aggregator.market_api.required_global_fields.write() = fields.to_vec();
+    }
+
+    /// (symbol, name) of the US indices to fetch from Finnhub (both API instances)
+    pub fn set_stock_indices(&self, indices: &[(String, String)]) {
+        *self.market_api.stock_indices.write() = indices.to_vec();
+        *self.aggregator.market_api.stock_indices.write() = indices.to_vec();
+    }
+
+    /// Retries the aggregator's fetchers may spend together per aggregation (0 = unlimited)
+    pub fn set_max_retries_per_tick(&self, limit: usize) {
+        self.aggregator.market_api.retry_budget.set_limit(limit);
+    }
+
+    /// Data groups the aggregator fetches at once (1 serializes them)
+    pub fn set_max_concurrent_fetches(&self, limit: usize) {
+        self.aggregator.set_max_concurrent_fetches(limit);
+    }
+
+    /// Switch a data group (`crypto`, `global`, `fng`, `rsi`, `indices`) on or off
+    ///
+    /// Returns whether the state changed; unknown groups are an error.
+    pub fn set_source_enabled(&self, group: &str, enabled: bool) -> Result<bool> {
+        self.aggregator.source_switches.set_enabled(group, enabled)
+    }
+
+    /// Data groups currently switched off
+    pub fn disabled_sources(&self) -> Vec<&'static str> {
+        self.aggregator.source_switches.disabled()
     }
 
     /// Fetch dashboard summary v2 - Main Layer 2 functionality
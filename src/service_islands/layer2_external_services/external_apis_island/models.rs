@@ -28,11 +28,37 @@ pub(crate) struct BinanceBtcPrice {
     pub last_price: String,
     #[serde(rename = "priceChangePercent")]
     pub price_change_percent: String,
+    // 24h base-asset volume (weights the VWAP)
+    #[serde(default)]
+    pub volume: String,
 }
 
 // Binance Multi-Ticker response (array of tickers)
 pub(crate) type BinanceMultiTickerResponse = Vec<BinanceBtcPrice>;
 
+// Coinbase 24h product stats
+#[derive(Debug, Deserialize)]
+pub(crate) struct CoinbaseStats {
+    pub open: String,
+    pub last: String,
+    pub volume: String,
+}
+
+// Kraken ticker response; `c` = [last price, lot volume], `v` = [today, last 24h] volume
+#[derive(Debug, Deserialize)]
+pub(crate) struct KrakenTickerResponse {
+    #[serde(default)]
+    pub error: Vec<String>,
+    #[serde(default)]
+    pub result: HashMap<String, KrakenTicker>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct KrakenTicker {
+    pub c: Vec<String>,
+    pub v: Vec<String>,
+}
+
 // Fear & Greed Index response structures
 #[derive(Debug, Deserialize)]
 pub(crate) struct FearGreedResponse {
@@ -42,6 +68,9 @@ pub(crate) struct FearGreedResponse {
 #[derive(Debug, Deserialize)]
 pub(crate) struct FearGreedData {
     pub value: String,
+    // Official label for the value, e.g. "Extreme Fear"
+    #[serde(default)]
+    pub value_classification: Option<String>,
 }
 
 // TAAPI RSI response structures
@@ -61,13 +90,38 @@ pub(crate) struct CmcGlobalData {
     pub quote: HashMap<String, CmcGlobalQuote>,
 }
 
+#[derive(Debug, Deserialize)]
+pub(crate) struct CmcFngResponse {
+    pub data: CmcFngData,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct CmcFngData {
+    pub value: f64,
+    #[serde(default)]
+    pub value_classification: Option<String>,
+}
+
+impl CmcFngData {
+    /// As `FngData`; None when the value is outside 0-100
+    fn into_fng_data(self) -> Option<FngData> {
+        (0.0..=100.0).contains(&self.value).then(|| FngData {
+            value: self.value.round() as u32,
+            value_classification: self.value_classification,
+            source: Some(SERVICE_COINMARKETCAP.to_string()),
+            last_updated: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct CmcGlobalQuote {
     pub total_market_cap: f64,
     pub total_volume_24h: f64,
     pub market_cap_change_percentage_24h: f64,
     pub btc_dominance: f64,
-    pub eth_dominance: f64,
+    #[serde(default)]
+    pub eth_dominance: Option<f64>,
 }
 
 // Finnhub response structures
@@ -91,4 +145,166 @@ pub(crate) struct FinnhubQuoteResponse {
     #[allow(dead_code)]
     #[serde(rename = "pc")]
     pub previous_close: f64,
-}
\ No newline at end of file
+}
+// Normalized fetcher outputs
+//
+// What the fetchers return and the aggregator composes. Field names match the JSON
+// previously cached under the same keys, so existing cache entries still deserialize.
+
+/// Global market data (CoinGecko, CoinMarketCap fallback)
+#[derive(Debug, Clone, Deserialize, serde::Serialize)]
+pub struct GlobalData {
+    pub market_cap: f64,
+    pub volume_24h: f64,
+    pub market_cap_change_percentage_24h_usd: f64,
+    pub btc_market_cap_percentage: f64,
+    /// None when the provider omitted ETH dominance (a reported 0.0 stays `Some`)
+    #[serde(default)]
+    pub eth_market_cap_percentage: Option<f64>,
+    #[serde(default)]
+    pub source: Option<String>,
+    pub last_updated: String,
+}
+
+/// Global data fields a provider response must carry to be accepted (`REQUIRED_GLOBAL_FIELDS`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalField {
+    MarketCap,
+    Volume24h,
+    BtcDominance,
+    EthDominance,
+}
+
+/// Default `REQUIRED_GLOBAL_FIELDS`: everything the dashboard shows
+pub const DEFAULT_REQUIRED_GLOBAL_FIELDS: [GlobalField; 4] = [
+    GlobalField::MarketCap,
+    GlobalField::Volume24h,
+    GlobalField::BtcDominance,
+    GlobalField::EthDominance,
+];
+
+impl GlobalField {
+    /// Name used in `REQUIRED_GLOBAL_FIELDS` and error messages
+    pub fn name(self) -> &'static str {
+        match self {
+            GlobalField::MarketCap => "market_cap",
+            GlobalField::Volume24h => "volume_24h",
+            GlobalField::BtcDominance => "btc_dominance",
+            GlobalField::EthDominance => "eth_dominance",
+        }
+    }
+
+    /// Parse a `REQUIRED_GLOBAL_FIELDS` entry
+    pub fn from_name(name: &str) -> Option<Self> {
+        DEFAULT_REQUIRED_GLOBAL_FIELDS.into_iter().find(|field| field.name() == name)
+    }
+}
+
+impl GlobalData {
+    /// Required fields that are absent or implausible, as `name=value` pairs
+    ///
+    /// Totals and BTC dominance must be positive (providers fill gaps with 0);
+    /// ETH dominance only needs to be present and non-negative.
+    pub fn invalid_fields(&self, required: &[GlobalField]) -> Vec<String> {
+        required
+            .iter()
+            .filter_map(|&field| {
+                let value = match field {
+                    GlobalField::MarketCap => Some(self.market_cap),
+                    GlobalField::Volume24h => Some(self.volume_24h),
+                    GlobalField::BtcDominance => Some(self.btc_market_cap_percentage),
+                    GlobalField::EthDominance => self.eth_market_cap_percentage,
+                };
+                let valid = match (field, value) {
+                    (_, None) => false,
+                    (GlobalField::EthDominance, Some(v)) => v.is_finite() && v >= 0.0,
+                    (_, Some(v)) => v.is_finite() && v > 0.0,
+                };
+                (!valid).then(|| match value {
+                    Some(v) => format!("{}={}", field.name(), v),
+                    None => format!("{}=missing", field.name()),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Fear & Greed Index
+#[derive(Debug, Clone, Deserialize, serde::Serialize)]
+pub struct FngData {
+    pub value: u32,
+    #[serde(default)]
+    pub value_classification: Option<String>,
+    #[serde(default)]
+    pub source: Option<String>,
+    pub last_updated: String,
+}
+
+/// BTC RSI-14 from TAAPI
+#[derive(Debug, Clone, Deserialize, serde::Serialize)]
+pub struct RsiData {
+    pub value: f64,
+    #[serde(default)]
+    pub period: Option<String>,
+    #[serde(default)]
+    pub source: Option<String>,
+    pub last_updated: String,
+}
+
+/// US stock indices keyed by ETF symbol
+#[derive(Debug, Clone, Deserialize, serde::Serialize)]
+pub struct IndicesData {
+    pub indices: HashMap<String, IndexQuote>,
+    #[serde(default)]
+    pub source: Option<String>,
+    pub last_updated: String,
+}
+
+/// One index quote ("status" is "success", "failed" or "unavailable")
+#[derive(Debug, Clone, Deserialize, serde::Serialize)]
+pub struct IndexQuote {
+    pub symbol: String,
+    pub name: String,
+    pub price: f64,
+    pub change: f64,
+    pub change_percent: f64,
+    pub status: String,
+}
+
+// API statistics
+
+/// Call counters and provider health of one `MarketDataApi` (see `get_api_stats`)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ApiStats {
+    pub total_api_calls: usize,
+    pub successful_calls: usize,
+    pub failed_calls: usize,
+    /// Percentage of calls that succeeded, rounded (0 before the first call)
+    pub success_rate: f64,
+    /// Unix seconds of the last call (0 before the first call)
+    pub last_call_timestamp: u64,
+    /// Unix seconds of the last successful call (0 before the first success)
+    pub last_success_timestamp: u64,
+    pub providers: std::collections::BTreeMap<String, super::provider_stats::ProviderCounts>,
+    pub adaptive_source_ordering: bool,
+    pub global_source_order: Vec<&'static str>,
+    pub has_coinmarketcap_key: bool,
+    pub has_finnhub_key: bool,
+    pub quota_remaining: QuotaRemaining,
+    pub circuit_breakers: CircuitBreakerStats,
+}
+
+/// Remaining provider quota from rate-limit headers (None until a response reported it)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct QuotaRemaining {
+    pub coingecko: Option<u64>,
+    pub coinmarketcap: Option<u64>,
+}
+
+/// Per-service circuit state plus lifetime totals
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CircuitBreakerStats {
+    pub services: Vec<super::circuit_breaker::CircuitBreakerStatus>,
+    pub total_blocked: u64,
+    pub total_opened: u64,
+}
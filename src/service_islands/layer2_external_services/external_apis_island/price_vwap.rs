@@ -0,0 +1,107 @@
+//! Multi-Exchange Price Aggregation
+//!
+//! With `PRICE_MODE=vwap` each coin's price is a volume-weighted average of the
+//! Binance, Coinbase and Kraken tickers, so one exchange's bad print can't move
+//! the dashboard. Quotes too far from the median are left out.
+
+use std::collections::BTreeMap;
+
+/// Default `VWAP_MAX_DEVIATION_PERCENT`
+pub const DEFAULT_VWAP_MAX_DEVIATION_PERCENT: f64 = 2.0;
+
+/// How crypto prices are sourced (`PRICE_MODE`)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PriceMode {
+    /// Binance only: the WebSocket stream when running, otherwise REST
+    #[default]
+    Single,
+    /// Volume-weighted average across exchanges, excluding quotes more than
+    /// `max_deviation_percent` away from the median price
+    Vwap { max_deviation_percent: f64 },
+}
+
+/// One exchange's 24h ticker for a coin
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExchangeQuote {
+    pub exchange: &'static str,
+    pub price: f64,
+    /// Rolling 24h change; None if the exchange doesn't report one
+    pub change_24h: Option<f64>,
+    /// 24h volume in the base asset
+    pub volume: f64,
+}
+
+/// Volume-weighted price for one coin
+#[derive(Debug, Clone, PartialEq)]
+pub struct VwapPrice {
+    pub price: f64,
+    pub change_24h: f64,
+    /// Every exchange's quoted price, including excluded outliers
+    pub exchanges: BTreeMap<String, f64>,
+}
+
+/// Combine one coin's quotes into a volume-weighted price
+///
+/// Quotes deviating more than `max_deviation_percent` from the median are dropped.
+/// If that drops everything (e.g. two exchanges far apart), the highest-volume
+/// quote is used alone. Without any volume the kept prices are averaged evenly.
+pub fn volume_weighted(quotes: &[ExchangeQuote], max_deviation_percent: f64) -> Option<VwapPrice> {
+    let mut prices: Vec<f64> = quotes.iter().map(|quote| quote.price).collect();
+    prices.sort_by(f64::total_cmp);
+    let median = match prices.len() {
+        0 => return None,
+        n if n % 2 == 1 => prices[n / 2],
+        n => (prices[n / 2 - 1] + prices[n / 2]) / 2.0,
+    };
+
+    let mut kept: Vec<&ExchangeQuote> = quotes.iter()
+        .filter(|quote| ((quote.price - median) / median * 100.0).abs() <= max_deviation_percent)
+        .collect();
+    if kept.is_empty() {
+        kept.extend(quotes.iter().max_by(|a, b| a.volume.total_cmp(&b.volume)));
+    }
+
+    let weighted = |values: Vec<(f64, f64)>| -> Option<f64> {
+        let total_volume: f64 = values.iter().map(|(_, volume)| volume).sum();
+        match values.len() {
+            0 => None,
+            n if total_volume <= 0.0 => Some(values.iter().map(|(value, _)| value).sum::<f64>() / n as f64),
+            _ => Some(values.iter().map(|(value, volume)| value * volume).sum::<f64>() / total_volume),
+        }
+    };
+
+    Some(VwapPrice {
+        price: weighted(kept.iter().map(|quote| (quote.price, quote.volume)).collect())?,
+        change_24h: weighted(kept.iter().filter_map(|quote| Some((quote.change_24h?, quote.volume))).collect())
+            .unwrap_or(0.0),
+        exchanges: quotes.iter().map(|quote| (quote.exchange.to_string(), quote.price)).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(exchange: &'static str, price: f64, volume: f64) -> ExchangeQuote {
+        ExchangeQuote { exchange, price, change_24h: Some(1.0), volume }
+    }
+
+    #[test]
+    fn test_volume_weighted_excludes_outliers() {
+        let quotes = [
+            quote("binance", 100.0, 3.0),
+            quote("coinbase", 101.0, 1.0),
+            quote("kraken", 150.0, 10.0),
+        ];
+        let vwap = volume_weighted(&quotes, 2.0).unwrap();
+        assert_eq!(vwap.price, 100.25);
+        assert_eq!(vwap.change_24h, 1.0);
+        assert_eq!(vwap.exchanges.len(), 3);
+
+        // Two quotes far apart: trust the bigger market
+        let split = volume_weighted(&quotes[1..], 2.0).unwrap();
+        assert_eq!(split.price, 150.0);
+
+        assert!(volume_weighted(&[], 2.0).is_none());
+    }
+}
@@ -0,0 +1,80 @@
+//! Provider Statistics Component
+//!
+//! Call and success counts per upstream provider, recorded by
+//! `MarketDataApi::call_with_breaker`. With `ADAPTIVE_SOURCE_ORDERING` enabled they
+//! decide which global data source is tried first.
+
+use std::collections::{BTreeMap, HashMap};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// Counts for one provider
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct ProviderCounts {
+    pub calls: u64,
+    pub successes: u64,
+}
+
+impl ProviderCounts {
+    /// Success rate with add-one smoothing, so a new provider starts at 0.5
+    /// instead of jumping to 0 or 1 on its first call
+    pub fn reliability(&self) -> f64 {
+        (self.successes as f64 + 1.0) / (self.calls as f64 + 2.0)
+    }
+}
+
+/// Per-provider call outcomes (keyed by `SERVICE_*` name)
+#[derive(Debug, Default)]
+pub struct ProviderStats {
+    counts: Mutex<HashMap<String, ProviderCounts>>,
+}
+
+impl ProviderStats {
+    /// Record one completed call to `service`
+    pub fn record(&self, service: &str, success: bool) {
+        let mut counts = self.counts.lock();
+        let entry = counts.entry(service.to_string()).or_default();
+        entry.calls += 1;
+        if success {
+            entry.successes += 1;
+        }
+    }
+
+    /// Counts for one provider (zero if it was never called)
+    pub fn get(&self, service: &str) -> ProviderCounts {
+        self.counts.lock().get(service).copied().unwrap_or_default()
+    }
+
+    /// Counts for every provider called so far
+    pub fn snapshot(&self) -> BTreeMap<String, ProviderCounts> {
+        self.counts.lock().iter().map(|(service, counts)| (service.clone(), *counts)).collect()
+    }
+
+    /// `services` reordered most reliable first; ties keep their given order
+    pub fn order_by_reliability<'a>(&self, services: &[&'a str]) -> Vec<&'a str> {
+        let mut ordered = services.to_vec();
+        ordered.sort_by(|a, b| self.get(b).reliability().total_cmp(&self.get(a).reliability()));
+        ordered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_by_reliability() {
+        let stats = ProviderStats::default();
+        let services = ["coingecko", "coinmarketcap"];
+        assert_eq!(stats.order_by_reliability(&services), services);
+
+        for _ in 0..4 {
+            stats.record("coingecko", false);
+            stats.record("coinmarketcap", true);
+        }
+        stats.record("coingecko", true);
+        assert_eq!(stats.order_by_reliability(&services), ["coinmarketcap", "coingecko"]);
+        assert_eq!(stats.get("coingecko").calls, 5);
+    }
+}
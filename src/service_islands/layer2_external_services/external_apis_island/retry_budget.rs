@@ -0,0 +1,64 @@
+//! Retry Budget Component
+//!
+//! Caps the retries all of `MarketDataApi`'s retry loops may spend together
+//! between refills (`MAX_RETRIES_PER_TICK`). The aggregator refills it at the
+//! start of every aggregation, so a tick that hits rate limits on several
+//! providers gives up after the budget instead of retrying each call 3 times.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Retries allowed per aggregation (0 = unlimited)
+#[derive(Debug, Default)]
+pub struct RetryBudget {
+    limit: AtomicUsize,
+    remaining: AtomicUsize,
+}
+
+impl RetryBudget {
+    /// Allow `limit` retries per refill (0 = unlimited); the budget starts full
+    pub fn set_limit(&self, limit: usize) {
+        self.limit.store(limit, Ordering::Relaxed);
+        self.refill();
+    }
+
+    /// Restore the full budget (start of an aggregation)
+    pub fn refill(&self) {
+        self.remaining.store(self.limit.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+
+    /// Take one retry; false when the budget is spent
+    pub fn try_take(&self) -> bool {
+        if self.limit.load(Ordering::Relaxed) == 0 {
+            return true;
+        }
+        self.remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| remaining.checked_sub(1))
+            .is_ok()
+    }
+
+    /// Retries left before the next refill (None when unlimited)
+    pub fn remaining(&self) -> Option<usize> {
+        (self.limit.load(Ordering::Relaxed) > 0).then(|| self.remaining.load(Ordering::Relaxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_runs_out_until_refilled() {
+        let budget = RetryBudget::default();
+        assert!((0..10).all(|_| budget.try_take()), "unlimited by default");
+        assert_eq!(budget.remaining(), None);
+
+        budget.set_limit(2);
+        assert!(budget.try_take());
+        assert!(budget.try_take());
+        assert!(!budget.try_take());
+        assert_eq!(budget.remaining(), Some(0));
+
+        budget.refill();
+        assert_eq!(budget.remaining(), Some(2));
+    }
+}
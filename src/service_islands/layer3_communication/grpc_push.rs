@@ -0,0 +1,162 @@
+//! gRPC Push Component
+//!
+//! Optional gRPC server (`GRPC_PORT`) streaming every full dashboard broadcast to
+//! the main report service, so it doesn't have to poll Redis. Subscribers read the
+//! same `dashboard` topic channel as WebSocket clients and get the same
+//! pre-serialized JSON. The contract is `proto/dashboard.proto`; it carries the
+//! dashboard as opaque JSON text, whose shape is the WebSocket `DashboardUpdate`.
+//! The message structs below mirror the proto by hand (no `protoc` at build time),
+//! and a test keeps them in step with it.
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use futures::Stream;
+use tokio::sync::broadcast::error::RecvError;
+use tonic::{Request, Response, Status};
+use tracing::{info, warn};
+
+use super::websocket_service::broadcast_service::BroadcastService;
+use super::websocket_service::serialization_pool::TOPIC_DASHBOARD;
+
+include!(concat!(env!("OUT_DIR"), "/dashboard.DashboardStream.rs"));
+
+pub use dashboard_stream_server::{DashboardStream, DashboardStreamServer};
+
+/// `dashboard.SubscribeRequest`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SubscribeRequest {
+    /// Name of the subscribing service, for logs only
+    #[prost(string, tag = "1")]
+    pub consumer: String,
+}
+
+/// `dashboard.DashboardUpdate`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DashboardUpdate {
+    /// The `DashboardUpdate` ServerMessage exactly as WebSocket clients receive it (JSON)
+    #[prost(string, tag = "1")]
+    pub json: String,
+}
+
+/// Serves `DashboardStream` from the WebSocket broadcast channels
+pub struct DashboardPush {
+    broadcast_service: Arc<BroadcastService>,
+}
+
+impl DashboardPush {
+    pub fn new(broadcast_service: Arc<BroadcastService>) -> Self {
+        Self { broadcast_service }
+    }
+}
+
+#[tonic::async_trait]
+impl DashboardStream for DashboardPush {
+    type SubscribeStream = Pin<Box<dyn Stream<Item = Result<DashboardUpdate, Status>> + Send>>;
+
+    async fn subscribe(&self, request: Request<SubscribeRequest>) -> Result<Response<Self::SubscribeStream>, Status> {
+        let consumer = request.into_inner().consumer;
+        info!("📡 gRPC dashboard subscriber connected: {}", if consumer.is_empty() { "(unnamed)" } else { &consumer });
+
+        // Lagging subscribers skip to the newest dashboards, like WebSocket clients
+        let receiver = self.broadcast_service.subscribe_topic(TOPIC_DASHBOARD);
+        let updates = futures::stream::unfold(receiver, move |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(prepared) => {
+                        let update = DashboardUpdate { json: prepared.json.to_string() };
+                        return Some((Ok(update), receiver));
+                    }
+                    Err(RecvError::Lagged(skipped)) => warn!("gRPC dashboard subscriber lagging, {} updates dropped", skipped),
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(updates)))
+    }
+}
+
+/// Serve `DashboardStream` on `addr` until `shutdown` completes
+pub async fn serve(
+    addr: SocketAddr,
+    broadcast_service: Arc<BroadcastService>,
+    shutdown: impl Future<Output = ()>,
+) -> anyhow::Result<()> {
+    info!("📡 gRPC dashboard stream listening on {}", addr);
+    tonic::transport::Server::builder()
+        .add_service(DashboardStreamServer::new(DashboardPush::new(broadcast_service)))
+        .serve_with_shutdown(addr, shutdown)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    /// `(message, type, field, number)` for every field declared in `proto/dashboard.proto`
+    fn proto_fields() -> Vec<(String, String, String, u32)> {
+        let proto = include_str!("../../../proto/dashboard.proto");
+        let mut fields = Vec::new();
+        let mut message = None;
+        for line in proto.lines().map(str::trim).filter(|line| !line.starts_with("//")) {
+            if let Some(name) = line.strip_prefix("message ") {
+                message = Some(name.trim_end_matches('{').trim().to_string());
+            } else if line == "}" {
+                message = None;
+            } else if let (Some(message), Some((declaration, number))) = (&message, line.split_once('=')) {
+                let mut words = declaration.split_whitespace();
+                let (kind, name) = (words.next().unwrap(), words.next().unwrap());
+                let number = number.trim().trim_end_matches(';').parse().unwrap();
+                fields.push((message.clone(), kind.to_string(), name.to_string(), number));
+            }
+        }
+        fields
+    }
+
+    #[test]
+    fn test_message_structs_match_the_proto() {
+        use prost::Message;
+
+        let declared = |message: &str, kind: &str, field: &str, number: u32| {
+            (message.to_string(), kind.to_string(), field.to_string(), number)
+        };
+        assert_eq!(
+            proto_fields(),
+            vec![declared("SubscribeRequest", "string", "consumer", 1), declared("DashboardUpdate", "string", "json", 1)]
+        );
+        let proto = include_str!("../../../proto/dashboard.proto");
+        assert!(proto.contains("rpc Subscribe(SubscribeRequest) returns (stream DashboardUpdate);"));
+
+        // Field 1, length-delimited: the string is encoded under the declared number
+        let request = SubscribeRequest { consumer: "report".to_string() }.encode_to_vec();
+        assert_eq!(request[..2], [1 << 3 | 2, 6]);
+        assert_eq!(SubscribeRequest::decode(request.as_slice()).unwrap().consumer, "report");
+        let update = DashboardUpdate { json: "{}".to_string() }.encode_to_vec();
+        assert_eq!(update, [1 << 3 | 2, 2, b'{', b'}']);
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_dashboard_broadcasts() {
+        let broadcast_service = Arc::new(BroadcastService::new());
+        let push = DashboardPush::new(Arc::clone(&broadcast_service));
+        let mut updates = push
+            .subscribe(Request::new(SubscribeRequest { consumer: "report".to_string() }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let dashboard = serde_json::json!({
+            "type": "DashboardUpdate",
+            "payload": { "data": { "btcPriceUsd": 96000.0 } },
+        });
+        assert_eq!(broadcast_service.broadcast_dashboard(dashboard.clone(), None).await.unwrap(), 1);
+
+        let update = updates.next().await.unwrap().unwrap();
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&update.json).unwrap(), dashboard);
+    }
+}
@@ -1,3 +1,4 @@
 pub mod websocket_service;
+pub mod grpc_push;
 
 pub use websocket_service::WebSocketServiceIsland;
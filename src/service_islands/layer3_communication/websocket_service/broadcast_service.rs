@@ -1,37 +1,235 @@
 //! Broadcast Service Component
 //!
 //! This component handles message broadcasting and real-time updates.
+//!
+//! Messages are published to named topics, each backed by its own broadcast
+//! channel: `dashboard` (full updates), `dashboard:delta`, `symbol:<SYMBOL>`
+//! (one coin's `MarketUpdate`s) and `system` (notices for every connection).
+//! Connection tasks subscribe only to the topics they need, so a BTC-only client
+//! isn't woken for full dashboards.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::Value;
+use tokio::sync::{broadcast, Mutex};
+use tracing::warn;
+
+use crate::dto::{DashboardData, DashboardDeltaPayload, ServerMessage};
+use crate::service_islands::layer2_external_services::external_apis_island::market_data_api::DASHBOARD_SYMBOLS;
+use super::serialization_pool::{PreparedMessage, SerializationPool, TOPIC_DASHBOARD};
+use super::transforms::OutputCase;
+
+/// Payload carried on the broadcast channels: serialized once, shared by every receiver
+pub type BroadcastMessage = Arc<PreparedMessage>;
+
+/// Delta-mode dashboard topic: sparse `DashboardDelta` messages plus a periodic full snapshot
+pub const TOPIC_DASHBOARD_DELTA: &str = "dashboard:delta";
+
+/// Non-dashboard messages (notices) every connection listens to
+pub const TOPIC_SYSTEM: &str = "system";
 
-use tokio::sync::broadcast;
+/// Buffered messages per topic channel
+const TOPIC_CAPACITY: usize = 1000;
+const SYSTEM_CAPACITY: usize = 100;
+
+/// Topic carrying `symbol`'s `MarketUpdate`s, e.g. `symbol:BTC`
+pub fn symbol_topic(symbol: &str) -> String {
+    format!("symbol:{}", symbol)
+}
+
+/// Default number of delta updates between full dashboard snapshots
+const DEFAULT_DELTA_FULL_SNAPSHOT_EVERY: u32 = 12;
+
+/// What the delta channel gets for one dashboard update
+enum DeltaOutput {
+    /// Nothing changed since the last update
+    Skip,
+    /// Resend the full dashboard message (resync or diff unavailable)
+    Full,
+    /// `DashboardDelta` message with only the changed fields
+    Delta(Value),
+}
+
+/// Last dashboard sent on the delta channel (shared by all delta clients)
+struct DeltaState {
+    last: Option<DashboardData>,
+    /// Full message of the latest update (see `latest_dashboard`)
+    last_full: Option<BroadcastMessage>,
+    updates_since_full: u32,
+}
 
 /// Broadcast Service
 ///
 /// Manages message broadcasting to multiple WebSocket clients.
 /// Handles real-time updates, background tasks, and message distribution.
 pub struct BroadcastService {
-    /// Broadcast channel sender
-    pub broadcast_tx: broadcast::Sender<String>,
+    /// Channel per topic, created on first subscription
+    topics: parking_lot::RwLock<HashMap<String, broadcast::Sender<BroadcastMessage>>>,
+    delta_state: Mutex<DeltaState>,
+    serialization_pool: SerializationPool,
+    /// Send a full snapshot on the delta channel every N dashboard updates
+    full_snapshot_every: u32,
+    /// Key casing of dashboard messages for connections that didn't choose one (`OUTPUT_CASE`)
+    output_case: OutputCase,
+}
+
+impl Default for BroadcastService {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BroadcastService {
     /// Create a new BroadcastService with a broadcast channel
     pub fn new() -> Self {
-        let (broadcast_tx, _) = broadcast::channel(1000);
+        Self::with_full_snapshot_every(DEFAULT_DELTA_FULL_SNAPSHOT_EVERY)
+    }
+
+    /// Create a BroadcastService sending delta clients a full snapshot every N updates
+    pub fn with_full_snapshot_every(full_snapshot_every: u32) -> Self {
         Self {
-            broadcast_tx,
+            topics: parking_lot::RwLock::new(HashMap::new()),
+            delta_state: Mutex::new(DeltaState {
+                last: None,
+                last_full: None,
+                updates_since_full: 0,
+            }),
+            full_snapshot_every: full_snapshot_every.max(1),
+            serialization_pool: SerializationPool::new(),
+            output_case: OutputCase::default(),
+        }
+    }
+
+    /// Send dashboard messages in `output_case` by default
+    pub fn with_output_case(mut self, output_case: OutputCase) -> Self {
+        self.output_case = output_case;
+        self
+    }
+
+    /// Publish to one topic
+    ///
+    /// Returns the number of receivers it was queued for (0 when nobody is subscribed).
+    pub fn broadcast_to_topic(&self, topic: &str, message: BroadcastMessage) -> usize {
+        match self.topics.read().get(topic) {
+            // `send` only errs when there are no receivers
+            Some(sender) => sender.send(message).unwrap_or(0),
+            None => 0,
         }
     }
 
-    /// Broadcast a message to all connected WebSocket clients
-    pub async fn broadcast(&self, message: String) {
-        // Send to all subscribers
-        // Errors are ignored as some receivers might have been dropped
-        let _ = self.broadcast_tx.send(message);
+    /// Receiver for one topic, creating its channel if needed
+    pub fn subscribe_topic(&self, topic: &str) -> broadcast::Receiver<BroadcastMessage> {
+        if let Some(sender) = self.topics.read().get(topic) {
+            return sender.subscribe();
+        }
+        let capacity = if topic == TOPIC_SYSTEM { SYSTEM_CAPACITY } else { TOPIC_CAPACITY };
+        self.topics
+            .write()
+            .entry(topic.to_string())
+            .or_insert_with(|| broadcast::channel(capacity).0)
+            .subscribe()
+    }
+
+    /// Broadcast pre-serialized text to full-dashboard clients
+    ///
+    /// Returns the number of receivers it was queued for (0 when nobody is subscribed).
+    pub async fn broadcast(&self, message: String) -> usize {
+        self.broadcast_to_topic(TOPIC_DASHBOARD, Arc::new(PreparedMessage::from_text(message)))
+    }
+
+    /// Broadcast a non-dashboard message to every connection (the `system` topic)
+    ///
+    /// Returns the number of receivers it was queued for.
+    pub async fn broadcast_all(&self, message: &ServerMessage) -> anyhow::Result<usize> {
+        let prepared = Arc::new(self.serialization_pool.prepare(serde_json::to_value(message)?).await?);
+        Ok(self.broadcast_to_topic(TOPIC_SYSTEM, prepared))
+    }
+
+    /// Broadcast a dashboard update to full-mode, delta-mode and per-symbol clients
+    ///
+    /// Full-mode clients always get `full_message`. Delta-mode clients get only the
+    /// fields that changed since the last update, except every `full_snapshot_every`
+    /// updates (or when `data` couldn't be typed) they get `full_message` to resync.
+    /// Each `symbol:<SYMBOL>` topic gets that coin's `MarketUpdate` (JSON text).
+    /// Each message is serialized on the serialization pool in `OUTPUT_CASE`
+    /// (`full_message` is expected in camelCase); another casing is serialized once,
+    /// when the first connection that chose it frames the message.
+    ///
+    /// Returns the number of receivers (over all topics) the update was queued for;
+    /// delta clients are not counted when nothing changed for them.
+    pub async fn broadcast_dashboard(&self, full_message: Value, data: Option<DashboardData>) -> anyhow::Result<usize> {
+        let full_message = Arc::new(self.serialization_pool.prepare_cased(full_message, self.output_case).await?);
+
+        let delta_output = {
+            let mut state = self.delta_state.lock().await;
+            let due_for_full = state.updates_since_full + 1 >= self.full_snapshot_every;
+            state.last_full = Some(full_message.clone());
+
+            match (data, state.last.as_ref()) {
+                (Some(data), Some(previous)) if !due_for_full => {
+                    let output = match data.diff(previous) {
+                        Ok(changes) if changes.is_empty() => DeltaOutput::Skip,
+                        Ok(changes) => match serde_json::to_value(ServerMessage::DashboardDelta(DashboardDeltaPayload::new(changes))) {
+                            Ok(delta) => DeltaOutput::Delta(delta),
+                            Err(e) => {
+                                warn!("Failed to serialize dashboard delta: {}", e);
+                                DeltaOutput::Skip
+                            }
+                        },
+                        Err(e) => {
+                            warn!("Failed to diff dashboard data: {}", e);
+                            DeltaOutput::Full
+                        }
+                    };
+                    state.updates_since_full += 1;
+                    state.last = Some(data);
+                    output
+                }
+                (data, _) => {
+                    state.updates_since_full = 0;
+                    state.last = data;
+                    DeltaOutput::Full
+                }
+            }
+        };
+
+        let delta_receivers = match delta_output {
+            DeltaOutput::Delta(delta) => {
+                let delta = self.serialization_pool.prepare_cased(delta, self.output_case).await?;
+                self.broadcast_to_topic(TOPIC_DASHBOARD_DELTA, Arc::new(delta))
+            }
+            DeltaOutput::Full => self.broadcast_to_topic(TOPIC_DASHBOARD_DELTA, full_message.clone()),
+            DeltaOutput::Skip => 0,
+        };
+
+        let symbol_receivers: usize = DASHBOARD_SYMBOLS
+            .iter()
+            .filter_map(|&symbol| Some((symbol, full_message.by_topic.get(symbol)?)))
+            .map(|(symbol, update)| {
+                self.broadcast_to_topic(&symbol_topic(symbol), Arc::new(PreparedMessage::from_json(update.clone())))
+            })
+            .sum();
+
+        let full_receivers = self.broadcast_to_topic(TOPIC_DASHBOARD, full_message);
+        Ok(full_receivers + delta_receivers + symbol_receivers)
+    }
+
+    /// Latest full dashboard message, which later deltas are diffed against
+    ///
+    /// Sent to new connections (identical updates aren't rebroadcast, so they could
+    /// otherwise wait long for a first dashboard) and to a connection enabling delta
+    /// mode, so it has a dashboard to apply the first `DashboardDelta` to. None before
+    /// the first broadcast.
+    pub async fn latest_dashboard(&self) -> Option<BroadcastMessage> {
+        self.delta_state.lock().await.last_full.clone()
     }
 
-    /// Get a receiver for the broadcast channel
-    pub fn subscribe(&self) -> broadcast::Receiver<String> {
-        self.broadcast_tx.subscribe()
+    /// Receivers for each of `topics`, merged into one stream of messages
+    pub fn subscribe_topics<'a>(&self, topics: impl IntoIterator<Item = &'a str>) -> TopicReceivers {
+        TopicReceivers {
+            receivers: topics.into_iter().map(|topic| self.subscribe_topic(topic)).collect(),
+        }
     }
 
     /// Health check for broadcast service
@@ -41,3 +239,65 @@ impl BroadcastService {
         true
     }
 }
+
+/// One connection's topic subscriptions, received as a single stream
+#[derive(Default)]
+pub struct TopicReceivers {
+    receivers: Vec<broadcast::Receiver<BroadcastMessage>>,
+}
+
+impl TopicReceivers {
+    /// Next message from any of the topics (never resolves when subscribed to none)
+    ///
+    /// Cancel-safe: dropping the future loses no message.
+    pub async fn recv(&mut self) -> Result<BroadcastMessage, broadcast::error::RecvError> {
+        if self.receivers.is_empty() {
+            return std::future::pending().await;
+        }
+        let (message, _, _) = futures::future::select_all(
+            self.receivers.iter_mut().map(|receiver| Box::pin(receiver.recv())),
+        ).await;
+        message
+    }
+
+    /// Number of topics subscribed to
+    pub fn len(&self) -> usize {
+        self.receivers.len()
+    }
+
+    /// Whether no topic is subscribed
+    pub fn is_empty(&self) -> bool {
+        self.receivers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_topics_reach_only_their_subscribers() {
+        let service = BroadcastService::new();
+        let mut btc = service.subscribe_topics([symbol_topic("BTC").as_str()]);
+        let mut full = service.subscribe_topics([TOPIC_DASHBOARD]);
+        let mut system = service.subscribe_topic(TOPIC_SYSTEM);
+
+        let dashboard = serde_json::json!({
+            "type": "DashboardUpdate",
+            "payload": { "data": { "btcPriceUsd": 96000.0, "ethPriceUsd": 3500.0 } },
+        });
+        assert!(service.latest_dashboard().await.is_none());
+        // Full, BTC and ETH topics; nobody listens to ETH or the delta topic
+        assert_eq!(service.broadcast_dashboard(dashboard, None).await.unwrap(), 2);
+        assert!(service.latest_dashboard().await.unwrap().json.contains("DashboardUpdate"));
+
+        let update: Value = serde_json::from_str(&btc.recv().await.unwrap().json).unwrap();
+        assert_eq!((update["type"].as_str(), update["payload"]["symbol"].as_str()), (Some("MarketUpdate"), Some("BTC")));
+        assert!(full.recv().await.unwrap().json.contains("DashboardUpdate"));
+
+        let notice = ServerMessage::new_error("X", "system-wide");
+        assert_eq!(service.broadcast_all(&notice).await.unwrap(), 1);
+        assert!(system.recv().await.unwrap().json.contains("system-wide"));
+        assert!(btc.receivers[0].is_empty() && full.receivers[0].is_empty());
+    }
+}
@@ -2,18 +2,310 @@
 //!
 //! This component handles WebSocket connection pooling and lifecycle management.
 
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::ws::{close_code, CloseFrame, Message};
+use tokio::sync::{watch, RwLock};
+
+use crate::dto::websocket::FEATURE_MSGPACK;
+use crate::service_islands::layer2_external_services::external_apis_island::market_data_api::DASHBOARD_SYMBOLS;
+use super::broadcast_service::{symbol_topic, TOPIC_DASHBOARD_DELTA};
+use super::metrics::ConnectionTraffic;
+use super::serialization_pool::{PreparedMessage, TOPIC_CRYPTO, TOPIC_DASHBOARD, TOPIC_MARKET};
+use super::transforms::OutputCase;
+
+/// Identifier assigned to each WebSocket connection by `ConnectionManager::register`
+pub type ConnectionId = u64;
+
+/// Default time a dropped connection's subscriptions stay resumable
+const DEFAULT_RESUME_GRACE: Duration = Duration::from_secs(60);
+
+/// Suggested reconnect delay with no other connections
+const MIN_RECONNECT_DELAY_MS: u64 = 1_000;
+
+/// Suggested reconnect delay at the connection limit
+const MAX_RECONNECT_DELAY_MS: u64 = 30_000;
+
+/// Per-connection subscription state
+#[derive(Debug, Clone)]
+pub struct ConnectionState {
+    /// When the connection was registered
+    pub connected_at: Instant,
+    /// Topics the client has subscribed to
+    pub topics: HashSet<String>,
+    /// Whether the client receives dashboard deltas instead of full updates
+    pub delta: bool,
+    /// Whether the client receives its symbols' `MarketUpdate`s as one `Batch` per broadcast
+    pub batch: bool,
+    /// Key casing of dashboard data chosen with `Subscribe { case }` (None: `OUTPUT_CASE`)
+    pub case: Option<OutputCase>,
+    /// Client version reported in `Hello`
+    pub client_version: Option<String>,
+    /// Optional features granted in the `Hello` handshake
+    pub features: HashSet<String>,
+    /// Bytes exchanged so far, counted by the connection's socket task
+    pub traffic: Arc<ConnectionTraffic>,
+}
+
+impl Default for ConnectionState {
+    fn default() -> Self {
+        Self {
+            connected_at: Instant::now(),
+            topics: HashSet::new(),
+            delta: false,
+            batch: false,
+            case: None,
+            client_version: None,
+            features: HashSet::new(),
+            traffic: Arc::new(ConnectionTraffic::default()),
+        }
+    }
+}
+
+/// Topics a client can usefully subscribe to, as advertised in `Welcome`
+///
+/// The full dashboard and its field groups, then each dashboard coin.
+pub fn available_topics() -> Vec<String> {
+    [TOPIC_DASHBOARD, TOPIC_CRYPTO, TOPIC_MARKET]
+        .iter()
+        .chain(DASHBOARD_SYMBOLS)
+        .map(|topic| topic.to_string())
+        .collect()
+}
+
+impl ConnectionState {
+    /// Whether any subscribed topic is carried by dashboard broadcasts
+    ///
+    /// No topics means everything (the pre-subscription default). Clients subscribed
+    /// only to other topics (e.g. `SystemHealth`) don't receive dashboard traffic.
+    pub fn wants_dashboard(&self) -> bool {
+        self.topics.is_empty()
+            || self.topics.iter().any(|topic| {
+                [TOPIC_DASHBOARD, TOPIC_CRYPTO, TOPIC_MARKET].contains(&topic.as_str())
+                    || DASHBOARD_SYMBOLS.contains(&topic.as_str())
+            })
+    }
+
+    /// Broadcast topics this connection's task subscribes to
+    ///
+    /// Clients subscribed only to symbols get those symbols' `symbol:<SYMBOL>` topics.
+    /// Everyone else needing dashboards gets the full (or delta) dashboard topic, as do
+    /// batch and MessagePack clients, whose frames are built from the full broadcast.
+    pub fn broadcast_topics(&self) -> Vec<String> {
+        if !self.wants_dashboard() {
+            return Vec::new();
+        }
+
+        let symbols_only = !self.topics.is_empty()
+            && !self.delta
+            && !self.batch
+            && !self.features.contains(FEATURE_MSGPACK)
+            && self.topics.iter().all(|topic| {
+                ![TOPIC_DASHBOARD, TOPIC_CRYPTO, TOPIC_MARKET].contains(&topic.as_str())
+            });
+        if symbols_only {
+            return DASHBOARD_SYMBOLS
+                .iter()
+                .filter(|symbol| self.topics.contains(**symbol))
+                .map(|symbol| symbol_topic(symbol))
+                .collect();
+        }
+
+        let topic = if self.delta { TOPIC_DASHBOARD_DELTA } else { TOPIC_DASHBOARD };
+        vec![topic.to_string()]
+    }
+
+    /// Frame to send this connection for a pre-serialized broadcast
+    ///
+    /// In the connection's `case` when it chose one; then MessagePack if granted, a
+    /// `Batch` of `MarketUpdate`s in batch mode, the topic-filtered JSON for single-topic
+    /// subscribers, otherwise the full JSON text.
+    /// Returned with the `ServerMessage` type the frame carries, for `MessageStats`.
+    pub fn frame_for(&self, message: &PreparedMessage) -> (Message, Option<&'static str>) {
+        let message = message.for_case(self.case);
+        if self.features.contains(FEATURE_MSGPACK) {
+            if let Some(bytes) = &message.msgpack {
+                return (Message::Binary(bytes.to_vec()), message.message_type);
+            }
+        }
+
+        if self.batch {
+            if let Some(batch) = message.symbol_batch_json(self.topics.iter().map(String::as_str)) {
+                return (Message::Text(batch), Some("Batch"));
+            }
+        }
+
+        match self.topics.iter().next() {
+            Some(topic) if self.topics.len() == 1 => (
+                Message::Text(message.json_for_topic(topic).to_string()),
+                message.message_type_for_topic(topic),
+            ),
+            _ => (Message::Text(message.json.to_string()), message.message_type),
+        }
+    }
+}
+
+/// Subscriptions of a dropped connection, kept until its resume token expires
+struct ParkedSubscriptions {
+    topics: HashSet<String>,
+    delta: bool,
+    batch: bool,
+    case: Option<OutputCase>,
+    expires_at: Instant,
+}
+
 /// Connection Manager
 ///
 /// Manages WebSocket connection pooling and lifecycle operations.
 /// Handles connection establishment, maintenance, and cleanup.
 pub struct ConnectionManager {
-    // Component state can be added here as features are implemented
+    /// Flipped to `true` when the server starts draining connections
+    drain_tx: watch::Sender<bool>,
+    next_id: AtomicU64,
+    connections: RwLock<HashMap<ConnectionId, ConnectionState>>,
+    /// Resume token issued to each live connection
+    resume_tokens: RwLock<HashMap<ConnectionId, String>>,
+    /// Dropped connections' subscriptions by resume token
+    parked: RwLock<HashMap<String, ParkedSubscriptions>>,
+    /// How long parked subscriptions stay resumable (zero disables resume)
+    resume_grace: Duration,
+}
+
+impl Default for ConnectionManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ConnectionManager {
     /// Create a new ConnectionManager
     pub fn new() -> Self {
-        Self {}
+        Self::with_resume_grace(DEFAULT_RESUME_GRACE)
+    }
+
+    /// Create a ConnectionManager keeping dropped connections resumable for `resume_grace`
+    pub fn with_resume_grace(resume_grace: Duration) -> Self {
+        let (drain_tx, _) = watch::channel(false);
+        Self {
+            drain_tx,
+            next_id: AtomicU64::new(1),
+            connections: RwLock::new(HashMap::new()),
+            resume_tokens: RwLock::new(HashMap::new()),
+            parked: RwLock::new(HashMap::new()),
+            resume_grace,
+        }
+    }
+
+    /// Register a new connection and return its id
+    pub async fn register(&self) -> ConnectionId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.connections.write().await.insert(id, ConnectionState::default());
+        if !self.resume_grace.is_zero() {
+            let token = format!("{:032x}", rand::random::<u128>());
+            self.resume_tokens.write().await.insert(id, token);
+        }
+        id
+    }
+
+    /// Forget a closed connection, keeping its subscriptions resumable for the grace window
+    pub async fn unregister(&self, id: ConnectionId) {
+        let state = self.connections.write().await.remove(&id);
+        let token = self.resume_tokens.write().await.remove(&id);
+
+        let now = Instant::now();
+        let mut parked = self.parked.write().await;
+        parked.retain(|_, entry| entry.expires_at > now);
+        if let (Some(state), Some(token)) = (state, token) {
+            if !state.topics.is_empty() || state.delta || state.case.is_some() {
+                parked.insert(token, ParkedSubscriptions {
+                    topics: state.topics,
+                    delta: state.delta,
+                    batch: state.batch,
+                    case: state.case,
+                    expires_at: now + self.resume_grace,
+                });
+            }
+        }
+    }
+
+    /// Resume token issued to a live connection (None when resume is disabled)
+    pub async fn resume_token(&self, id: ConnectionId) -> Option<String> {
+        self.resume_tokens.read().await.get(&id).cloned()
+    }
+
+    /// Restore a dropped connection's subscriptions onto `id`
+    ///
+    /// Tokens are single-use. Returns the restored state, or None if the token is
+    /// unknown or expired, or `id` isn't registered.
+    pub async fn resume(&self, id: ConnectionId, token: &str) -> Option<ConnectionState> {
+        let parked = self.parked.write().await.remove(token)?;
+        if parked.expires_at <= Instant::now() {
+            return None;
+        }
+
+        let mut connections = self.connections.write().await;
+        let state = connections.get_mut(&id)?;
+        state.topics.extend(parked.topics);
+        state.delta = parked.delta;
+        state.batch = parked.batch;
+        state.case = parked.case;
+        Some(state.clone())
+    }
+
+    /// Snapshot of a connection's state (None if not registered)
+    pub async fn state(&self, id: ConnectionId) -> Option<ConnectionState> {
+        self.connections.read().await.get(&id).cloned()
+    }
+
+    /// Ages of the oldest and newest live connections (None when there are none)
+    pub async fn connection_ages(&self) -> Option<(Duration, Duration)> {
+        let connections = self.connections.read().await;
+        let oldest = connections.values().map(|state| state.connected_at).min()?;
+        let newest = connections.values().map(|state| state.connected_at).max()?;
+        let now = Instant::now();
+        Some((now - oldest, now - newest))
+    }
+
+    /// Up to `limit` live connections, most bytes exchanged first
+    pub async fn heaviest(&self, limit: usize) -> Vec<(ConnectionId, ConnectionState)> {
+        let mut connections: Vec<_> = self
+            .connections
+            .read()
+            .await
+            .iter()
+            .map(|(id, state)| (*id, state.clone()))
+            .collect();
+        connections.sort_by_key(|(id, state)| (std::cmp::Reverse(state.traffic.total_bytes()), *id));
+        connections.truncate(limit);
+        connections
+    }
+
+    /// Apply `update` to a connection's state; returns false if not registered
+    pub async fn update<F>(&self, id: ConnectionId, update: F) -> bool
+    where
+        F: FnOnce(&mut ConnectionState),
+    {
+        match self.connections.write().await.get_mut(&id) {
+            Some(state) => {
+                update(state);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Ask every open connection to close with `CloseReason::GoingAway`
+    pub fn begin_drain(&self) {
+        self.drain_tx.send_replace(true);
+    }
+
+    /// Receiver that changes once `begin_drain` has been called
+    pub fn subscribe_drain(&self) -> watch::Receiver<bool> {
+        self.drain_tx.subscribe()
     }
 
     /// Health check for connection manager
@@ -22,3 +314,196 @@ impl ConnectionManager {
         true
     }
 }
+
+/// Why the server is closing a WebSocket connection
+///
+/// Sent as the close frame so clients can tell an intentional disconnect from a
+/// network failure (1006) and decide whether to reconnect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseReason {
+    /// 1000: the broadcast source shut down normally
+    Normal,
+    /// 1001: the server is draining connections (restart/deploy)
+    GoingAway,
+    /// 1008: the client fell too far behind the broadcast stream
+    PolicyViolation,
+    /// 1009: the client sent a message larger than `WS_MAX_MESSAGE_BYTES`
+    MessageTooBig,
+}
+
+impl CloseReason {
+    /// WebSocket close code for this reason
+    pub fn code(self) -> u16 {
+        match self {
+            CloseReason::Normal => close_code::NORMAL,
+            CloseReason::GoingAway => close_code::AWAY,
+            CloseReason::PolicyViolation => close_code::POLICY,
+            CloseReason::MessageTooBig => close_code::SIZE,
+        }
+    }
+
+    /// Human-readable close reason sent to the client
+    pub fn reason(self) -> &'static str {
+        match self {
+            CloseReason::Normal => "Server shutting down",
+            CloseReason::GoingAway => "Server draining, please reconnect",
+            CloseReason::PolicyViolation => "Client too slow, messages dropped",
+            CloseReason::MessageTooBig => "Message too big",
+        }
+    }
+
+    /// Close frame to send for this reason
+    pub fn close_frame(self) -> CloseFrame<'static> {
+        CloseFrame {
+            code: self.code(),
+            reason: Cow::Borrowed(self.reason()),
+        }
+    }
+
+    /// Whether the client should be told when to reconnect (a `Reconnect` before the close)
+    ///
+    /// Not for oversized messages: reconnecting and resending would fail the same way.
+    pub fn suggests_reconnect(self) -> bool {
+        !matches!(self, CloseReason::MessageTooBig)
+    }
+}
+
+/// Server-suggested reconnect delay, scaled with the current load
+///
+/// Grows linearly from 1s with no connections to 30s at the connection limit, so
+/// clients turned away or disconnected under load back off further. Clients should
+/// wait `after_ms` plus a random share of `jitter_ms`, which spreads their reconnects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectAdvice {
+    pub after_ms: u64,
+    pub jitter_ms: u64,
+}
+
+impl ReconnectAdvice {
+    /// Advice with `active` of `max` connections in use
+    pub fn for_load(active: usize, max: usize) -> Self {
+        let load = if max == 0 { 1.0 } else { (active as f64 / max as f64).min(1.0) };
+        let after_ms = MIN_RECONNECT_DELAY_MS
+            + ((MAX_RECONNECT_DELAY_MS - MIN_RECONNECT_DELAY_MS) as f64 * load).round() as u64;
+        Self {
+            after_ms,
+            jitter_ms: after_ms / 2,
+        }
+    }
+
+    /// `after_ms` rounded up to whole seconds, for a `Retry-After` header
+    pub fn retry_after_secs(self) -> u64 {
+        self.after_ms.div_ceil(1000)
+    }
+}
+
+/// A reserved slot in the active connection count
+///
+/// Reserved before the WebSocket upgrade so the limit check and the increment are
+/// one atomic step. The slot is released on drop, which also covers upgrades that
+/// never complete (the `on_upgrade` callback holding it is dropped unrun).
+pub struct ConnectionSlot {
+    counter: Arc<AtomicUsize>,
+}
+
+impl ConnectionSlot {
+    /// Reserve a slot if fewer than `max` connections are active
+    pub fn try_reserve(counter: &Arc<AtomicUsize>, max: usize) -> Option<Self> {
+        counter
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+                (current < max).then_some(current + 1)
+            })
+            .ok()
+            .map(|_| Self {
+                counter: Arc::clone(counter),
+            })
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.counter.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_slot_limit_and_release() {
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        let first = ConnectionSlot::try_reserve(&counter, 2).unwrap();
+        let _second = ConnectionSlot::try_reserve(&counter, 2).unwrap();
+        assert!(ConnectionSlot::try_reserve(&counter, 2).is_none());
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+        drop(first);
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+        assert!(ConnectionSlot::try_reserve(&counter, 2).is_some());
+    }
+
+    #[test]
+    fn test_reconnect_delay_grows_with_load() {
+        let idle = ReconnectAdvice::for_load(0, 1000);
+        let half = ReconnectAdvice::for_load(500, 1000);
+        let full = ReconnectAdvice::for_load(1000, 1000);
+        assert_eq!((idle.after_ms, idle.jitter_ms), (1_000, 500));
+        assert_eq!(half.after_ms, 15_500);
+        assert_eq!((full.after_ms, full.retry_after_secs()), (30_000, 30));
+        assert_eq!(ReconnectAdvice::for_load(2000, 1000), full);
+
+        assert!(CloseReason::PolicyViolation.suggests_reconnect());
+        assert!(!CloseReason::MessageTooBig.suggests_reconnect());
+    }
+
+    #[test]
+    fn test_available_topics_all_carry_dashboard_data() {
+        let topics = available_topics();
+        assert_eq!(&topics[..4], ["dashboard", "crypto", "market", "BTC"]);
+        for topic in topics {
+            let state = ConnectionState { topics: HashSet::from([topic.clone()]), ..Default::default() };
+            assert!(state.wants_dashboard(), "{}", topic);
+        }
+    }
+
+    #[test]
+    fn test_wants_dashboard_only_for_dashboard_topics() {
+        let mut state = ConnectionState::default();
+        assert!(state.wants_dashboard());
+
+        state.topics.insert("SystemHealth".to_string());
+        assert!(!state.wants_dashboard());
+
+        state.topics.insert("BTC".to_string());
+        assert!(state.wants_dashboard());
+    }
+
+    #[tokio::test]
+    async fn test_resume_restores_subscriptions_once() {
+        let manager = ConnectionManager::new();
+        let dropped = manager.register().await;
+        let token = manager.resume_token(dropped).await.unwrap();
+        manager.update(dropped, |state| {
+            state.topics.insert("BTC".to_string());
+            state.delta = true;
+            state.case = Some(OutputCase::Snake);
+        }).await;
+        manager.unregister(dropped).await;
+
+        let reconnected = manager.register().await;
+        let state = manager.resume(reconnected, &token).await.unwrap();
+        assert!(state.delta);
+        assert_eq!(state.case, Some(OutputCase::Snake));
+        assert!(state.topics.contains("BTC"));
+        assert!(manager.resume(reconnected, &token).await.is_none());
+
+        let (oldest, newest) = manager.connection_ages().await.unwrap();
+        assert!(oldest >= newest);
+
+        let disabled = ConnectionManager::with_resume_grace(Duration::ZERO);
+        let id = disabled.register().await;
+        assert!(disabled.resume_token(id).await.is_none());
+    }
+}
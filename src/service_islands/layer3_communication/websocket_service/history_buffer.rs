@@ -0,0 +1,88 @@
+//! History Buffer Component
+//!
+//! Keeps the last N broadcast dashboards in process memory so `GetHistory`
+//! requests are answered without a Redis round trip. Filled from the broadcast
+//! path, so followers have the same history as the leader.
+
+use std::collections::VecDeque;
+
+use parking_lot::Mutex;
+
+use crate::dto::DashboardData;
+
+/// Default `HISTORY_BUFFER_SIZE`
+pub const DEFAULT_HISTORY_BUFFER_SIZE: usize = 60;
+
+/// Bounded ring buffer of recent dashboards, oldest first
+pub struct HistoryBuffer {
+    capacity: usize,
+    entries: Mutex<VecDeque<DashboardData>>,
+}
+
+impl Default for HistoryBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_BUFFER_SIZE)
+    }
+}
+
+impl HistoryBuffer {
+    /// Create a buffer keeping at most `capacity` dashboards (0 keeps none)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Record a broadcast dashboard, evicting the oldest when full
+    pub fn push(&self, data: DashboardData) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock();
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(data);
+    }
+
+    /// Up to `n` most recent dashboards, oldest first
+    pub fn recent_history(&self, n: usize) -> Vec<DashboardData> {
+        let entries = self.entries.lock();
+        let skip = entries.len().saturating_sub(n);
+        entries.iter().skip(skip).cloned().collect()
+    }
+
+    /// Maximum number of dashboards kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dashboard(btc_price_usd: f64) -> DashboardData {
+        let mut json = crate::dto::websocket::zeroed_dashboard_json();
+        json["btc_price_usd"] = serde_json::json!(btc_price_usd);
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_keeps_most_recent_oldest_first() {
+        let buffer = HistoryBuffer::new(3);
+        for price in [1.0, 2.0, 3.0, 4.0] {
+            buffer.push(dashboard(price));
+        }
+
+        let prices = |entries: Vec<DashboardData>| entries.iter().map(|d| d.btc_price_usd).collect::<Vec<_>>();
+        assert_eq!(prices(buffer.recent_history(10)), vec![2.0, 3.0, 4.0]);
+        assert_eq!(prices(buffer.recent_history(2)), vec![3.0, 4.0]);
+
+        let disabled = HistoryBuffer::new(0);
+        disabled.push(dashboard(1.0));
+        assert!(disabled.recent_history(5).is_empty());
+    }
+}
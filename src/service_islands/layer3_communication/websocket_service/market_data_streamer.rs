@@ -7,6 +7,7 @@ use std::sync::Arc;
 use tracing::{info, warn, error};
 
 use crate::service_islands::layer2_external_services::external_apis_island::ExternalApisIsland;
+use crate::service_islands::layer2_external_services::external_apis_island::errors::ExternalApiError;
 
 /// Market Data Streamer
 ///
@@ -17,6 +18,12 @@ pub struct MarketDataStreamer {
     external_apis: Option<Arc<ExternalApisIsland>>,
 }
 
+impl Default for MarketDataStreamer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MarketDataStreamer {
     /// Create new Market Data Streamer without External APIs dependency
     pub fn new() -> Self {
@@ -37,8 +44,7 @@ impl MarketDataStreamer {
                 }
                 Err(e) => {
                     // Check if this is just a rate limit or circuit breaker issue
-                    let error_msg = e.to_string();
-                    if error_msg.contains("429") || error_msg.contains("Circuit breaker") || error_msg.contains("rate limit") {
+                    if ExternalApiError::find(&e).is_some_and(ExternalApiError::is_transient) {
                         warn!("Market Data Streamer - External APIs rate limited (still functional)");
                         true // Consider rate limiting as "healthy" since it's temporary
                     } else {
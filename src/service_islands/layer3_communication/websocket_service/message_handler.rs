@@ -1,27 +1,367 @@
 //! Message Handler Component
-//! 
+//!
 //! This component handles real-time message processing for WebSocket communications.
 
+use std::sync::Arc;
+
+use crate::dto::websocket::{
+    ErrorPayload, ERROR_CODE_INVALID_MESSAGE, ERROR_CODE_INVALID_TOPIC, ERROR_CODE_SUBSCRIPTION_FAILED,
+    ERROR_CODE_RESUME_FAILED, ERROR_CODE_UNSUBSCRIBE_FAILED,
+};
+use crate::dto::{ClientMessage, ServerMessage};
+use crate::dto::websocket::{HistoryPayload, PongPayload, SubscriptionsPayload};
+use crate::service_islands::layer2_external_services::external_apis_island::ExternalApisIsland;
+use super::broadcast_service::{BroadcastMessage, BroadcastService, TopicReceivers, TOPIC_SYSTEM};
+use super::connection_manager::{ConnectionId, ConnectionManager};
+use super::history_buffer::HistoryBuffer;
+use super::message_stats::MessageStats;
+use super::serialization_pool::TOPIC_DASHBOARD;
+
+/// Maximum number of topics accepted in a single Subscribe/Unsubscribe
+const MAX_TOPICS_PER_MESSAGE: usize = 64;
+
+/// Maximum length of a single topic name
+const MAX_TOPIC_LEN: usize = 64;
+
+/// Check run on every client message before it is handled
+///
+/// Returning `Err` rejects the message; the error is sent back to the client.
+pub trait MessageValidator: Send + Sync {
+    fn validate(&self, conn_id: ConnectionId, message: &ClientMessage) -> Result<(), ErrorPayload>;
+}
+
+/// Rejects empty, oversized or too many topics in Subscribe/Unsubscribe
+pub struct TopicValidator;
+
+impl MessageValidator for TopicValidator {
+    fn validate(&self, _conn_id: ConnectionId, message: &ClientMessage) -> Result<(), ErrorPayload> {
+        let topics = match message {
+            ClientMessage::Subscribe(payload) => &payload.topics,
+            ClientMessage::Unsubscribe(payload) => &payload.topics,
+            _ => return Ok(()),
+        };
+
+        if topics.len() > MAX_TOPICS_PER_MESSAGE {
+            return Err(ErrorPayload::new(
+                ERROR_CODE_INVALID_TOPIC,
+                &format!("At most {} topics per message", MAX_TOPICS_PER_MESSAGE),
+            ));
+        }
+        if let Some(topic) = topics.iter().find(|t| t.trim().is_empty() || t.len() > MAX_TOPIC_LEN) {
+            return Err(ErrorPayload::new(
+                ERROR_CODE_INVALID_TOPIC,
+                &format!("Invalid topic '{}'", topic),
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Message Handler
-/// 
+///
 /// Manages real-time message processing and WebSocket message handling.
 /// Processes incoming messages, formats outgoing messages, and handles message routing.
 pub struct MessageHandler {
-    // Component state will be added here
+    connection_manager: Arc<ConnectionManager>,
+    broadcast_service: Arc<BroadcastService>,
+    validators: Vec<Box<dyn MessageValidator>>,
+    /// Answers per-symbol snapshots on Subscribe (none in tests)
+    price_source: Option<Arc<ExternalApisIsland>>,
+    /// Answers `GetHistory` (empty history when unset)
+    history: Option<Arc<HistoryBuffer>>,
+    /// Received client messages by type
+    stats: Arc<MessageStats>,
 }
 
 impl MessageHandler {
-    /// Create a new MessageHandler
-    pub fn new() -> Self {
+    /// Create a new MessageHandler with the default validators
+    pub fn new(connection_manager: Arc<ConnectionManager>, broadcast_service: Arc<BroadcastService>) -> Self {
         Self {
-            // Initialize component
+            connection_manager,
+            broadcast_service,
+            validators: vec![Box::new(TopicValidator)],
+            price_source: None,
+            history: None,
+            stats: Arc::new(MessageStats::new()),
         }
     }
-    
+
+    /// Count received messages in `stats` (shared with the socket loop, which counts sends)
+    pub fn with_stats(mut self, stats: Arc<MessageStats>) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Serve `GetHistory` from this buffer
+    pub fn with_history(mut self, history: Arc<HistoryBuffer>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Send cached prices for newly subscribed symbols right after the Ack
+    pub fn with_price_source(mut self, external_apis: Arc<ExternalApisIsland>) -> Self {
+        self.price_source = Some(external_apis);
+        self
+    }
+
+    /// Add a validator run (in order) after the existing ones
+    pub fn with_validator(mut self, validator: Box<dyn MessageValidator>) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    /// Parse and handle a raw text frame, returning the messages to send back
+    pub async fn handle_text(&self, conn_id: ConnectionId, text: &str) -> Vec<ServerMessage> {
+        match ClientMessage::from_json_str(text) {
+            Ok(message) => self.handle(conn_id, message).await,
+            Err(e) => {
+                self.stats.record_invalid();
+                vec![ServerMessage::new_connection_error(
+                    conn_id,
+                    ERROR_CODE_INVALID_MESSAGE,
+                    &format!("Invalid message: {}", e),
+                )]
+            }
+        }
+    }
+
+    /// Handle a client message, returning the messages to send back
+    ///
+    /// Subscription state (topics, delta mode) is recorded on the connection manager;
+    /// the socket loop reads it back to pick the broadcast channel.
+    ///
+    /// Every `Error` in the reply carries `conn_id`, so clients can quote it in bug reports.
+    pub async fn handle(&self, conn_id: ConnectionId, message: ClientMessage) -> Vec<ServerMessage> {
+        self.stats.record_received(&message);
+        let mut responses = self.dispatch(conn_id, message).await;
+        for response in &mut responses {
+            if let ServerMessage::Error(error) = response {
+                error.connection_id.get_or_insert_with(|| conn_id.to_string());
+            }
+        }
+        responses
+    }
+
+    async fn dispatch(&self, conn_id: ConnectionId, message: ClientMessage) -> Vec<ServerMessage> {
+        for validator in &self.validators {
+            if let Err(error) = validator.validate(conn_id, &message) {
+                return vec![ServerMessage::Error(error)];
+            }
+        }
+
+        match message {
+            ClientMessage::Subscribe(payload) => {
+                // An empty list is the default subscription: full dashboard updates
+                let topics = if payload.topics.is_empty() {
+                    vec![TOPIC_DASHBOARD.to_string()]
+                } else {
+                    payload.topics
+                };
+                // Modes left out of the payload keep their current setting
+                let mut batch = false;
+                let updated = self.connection_manager.update(conn_id, |state| {
+                    state.topics.extend(topics.iter().cloned());
+                    state.delta = payload.delta.unwrap_or(state.delta);
+                    state.batch = payload.batch.unwrap_or(state.batch);
+                    state.case = payload.case.or(state.case);
+                    batch = state.batch;
+                }).await;
+
+                if !updated {
+                    return vec![ServerMessage::new_error(ERROR_CODE_SUBSCRIPTION_FAILED, "Unknown connection")];
+                }
+
+                let snapshots = self.symbol_snapshots(&topics).await;
+                let mut responses = vec![ServerMessage::new_ack("subscribe", topics)];
+                if batch && !snapshots.is_empty() {
+                    responses.push(ServerMessage::new_batch(snapshots));
+                } else {
+                    responses.extend(snapshots);
+                }
+                responses
+            }
+            ClientMessage::Unsubscribe(payload) => {
+                let topics = payload.topics.clone();
+                let updated = self.connection_manager.update(conn_id, |state| {
+                    for topic in &payload.topics {
+                        state.topics.remove(topic);
+                    }
+                }).await;
+
+                if updated {
+                    vec![ServerMessage::new_ack("unsubscribe", topics)]
+                } else {
+                    vec![ServerMessage::new_error(ERROR_CODE_UNSUBSCRIBE_FAILED, "Unknown connection")]
+                }
+            }
+            ClientMessage::Heartbeat => vec![ServerMessage::new_ack("heartbeat", Vec::new())],
+            ClientMessage::Ping(payload) => vec![ServerMessage::Pong(PongPayload {
+                client_time: payload.client_time,
+                server_time: chrono::Utc::now().timestamp_millis(),
+            })],
+            ClientMessage::Hello(payload) => {
+                let capabilities = ServerMessage::new_capabilities(&payload.requested_features);
+                if let ServerMessage::Capabilities(granted) = &capabilities {
+                    let features = granted.features.clone();
+                    self.connection_manager.update(conn_id, |state| {
+                        state.client_version = payload.client_version;
+                        state.features = features.into_iter().collect();
+                    }).await;
+                }
+                vec![capabilities]
+            }
+            ClientMessage::GetHistory(payload) => {
+                let entries = self.history
+                    .as_ref()
+                    .map(|history| history.recent_history(payload.limit.unwrap_or(history.capacity())))
+                    .unwrap_or_default();
+                vec![ServerMessage::History(HistoryPayload { entries })]
+            }
+            ClientMessage::GetSubscriptions => {
+                let state = self.connection_manager.state(conn_id).await.unwrap_or_default();
+                let mut topics: Vec<String> = state.topics.into_iter().collect();
+                topics.sort();
+                vec![ServerMessage::Subscriptions(SubscriptionsPayload { topics })]
+            }
+            ClientMessage::Resume(payload) => {
+                let Some(state) = self.connection_manager.resume(conn_id, &payload.token).await else {
+                    return vec![ServerMessage::new_error(ERROR_CODE_RESUME_FAILED, "Unknown or expired resume token")];
+                };
+
+                let mut topics: Vec<String> = state.topics.into_iter().collect();
+                topics.sort();
+                let snapshots = self.symbol_snapshots(&topics).await;
+                let mut responses = vec![ServerMessage::new_ack("resume", topics)];
+                responses.extend(snapshots);
+                responses
+            }
+        }
+    }
+
+    /// `MarketUpdate` for each topic that is a symbol with a cached price
+    async fn symbol_snapshots(&self, topics: &[String]) -> Vec<ServerMessage> {
+        let Some(price_source) = &self.price_source else {
+            return Vec::new();
+        };
+
+        let mut snapshots = Vec::new();
+        for topic in topics {
+            if let Some(update) = price_source.get_symbol_price(topic).await {
+                snapshots.push(ServerMessage::MarketUpdate(update));
+            }
+        }
+        snapshots
+    }
+
+    /// Receivers for the broadcast topics matching the connection's subscriptions
+    ///
+    /// Empty when none of the connection's topics are carried by dashboard broadcasts,
+    /// so its task isn't woken by every price update.
+    pub async fn receivers_for(&self, conn_id: ConnectionId) -> TopicReceivers {
+        let state = self.connection_manager.state(conn_id).await.unwrap_or_default();
+        self.broadcast_service
+            .subscribe_topics(state.broadcast_topics().iter().map(String::as_str))
+    }
+
+    /// Receiver for system-wide messages such as notices
+    pub fn system_receiver(&self) -> tokio::sync::broadcast::Receiver<BroadcastMessage> {
+        self.broadcast_service.subscribe_topic(TOPIC_SYSTEM)
+    }
+
     /// Health check for message handler
     pub async fn health_check(&self) -> bool {
-        // Stateless component - always healthy
-        // TODO: Add actual validation when state is added
+        // No failure modes of its own; state lives in the connection manager
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::websocket::SubscribePayload;
+    use super::super::transforms::OutputCase;
+
+    fn handler() -> (MessageHandler, Arc<ConnectionManager>) {
+        let connection_manager = Arc::new(ConnectionManager::new());
+        let handler = MessageHandler::new(connection_manager.clone(), Arc::new(BroadcastService::new()));
+        (handler, connection_manager)
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_records_state_and_acks() {
+        let (handler, connection_manager) = handler();
+        let conn_id = connection_manager.register().await;
+
+        let responses = handler.handle(conn_id, ClientMessage::Subscribe(SubscribePayload {
+            topics: vec!["BTC".to_string()],
+            delta: Some(true),
+            batch: None,
+            case: None,
+        })).await;
+
+        assert!(matches!(responses.as_slice(), [ServerMessage::Ack(ack)] if ack.action == "subscribe"));
+        let state = connection_manager.state(conn_id).await.unwrap();
+        assert!(state.delta);
+        assert!(state.topics.contains("BTC"));
+
+        // Omitting `delta` keeps delta mode; `false` turns it off
+        handler.handle_text(conn_id, r#"{"type":"Subscribe","payload":{"topics":["ETH","BTC"]}}"#).await;
+        assert!(connection_manager.state(conn_id).await.unwrap().delta);
+        handler.handle_text(conn_id, r#"{"type":"Subscribe","payload":{"topics":["ETH"],"delta":false,"case":"snake"}}"#).await;
+        let state = connection_manager.state(conn_id).await.unwrap();
+        assert!(!state.delta);
+        assert_eq!(state.case, Some(OutputCase::Snake));
+        handler.handle_text(conn_id, r#"{"type":"Subscribe","payload":{"topics":["ETH"]}}"#).await;
+        assert_eq!(connection_manager.state(conn_id).await.unwrap().case, Some(OutputCase::Snake));
+        handler.handle_text(conn_id, r#"{"type":"Unsubscribe","payload":{"topics":["BTC"]}}"#).await;
+        let responses = handler.handle_text(conn_id, r#"{"type":"GetSubscriptions"}"#).await;
+        assert!(matches!(responses.as_slice(),
+            [ServerMessage::Subscriptions(s)] if s.topics == vec!["ETH".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_empty_subscribe_means_dashboard() {
+        let (handler, connection_manager) = handler();
+        let conn_id = connection_manager.register().await;
+        connection_manager.update(conn_id, |state| { state.topics.insert("SystemHealth".to_string()); }).await;
+        assert!(!connection_manager.state(conn_id).await.unwrap().wants_dashboard());
+
+        let responses = handler.handle_text(conn_id, r#"{"type":"Subscribe","payload":{"topics":[]}}"#).await;
+
+        assert!(matches!(responses.as_slice(),
+            [ServerMessage::Ack(ack)] if ack.topics == vec![TOPIC_DASHBOARD.to_string()]));
+        let state = connection_manager.state(conn_id).await.unwrap();
+        assert!(state.topics.contains(TOPIC_DASHBOARD));
+        assert!(state.wants_dashboard());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_input_returns_errors() {
+        let (handler, connection_manager) = handler();
+        let conn_id = connection_manager.register().await;
+
+        let responses = handler.handle_text(conn_id, "not json").await;
+        assert!(matches!(responses.as_slice(), [ServerMessage::Error(e)] if e.code == ERROR_CODE_INVALID_MESSAGE));
+        let expected_id = conn_id.to_string();
+        assert!(matches!(responses.as_slice(), [ServerMessage::Error(e)] if e.connection_id.as_ref() == Some(&expected_id)));
+
+        let responses = handler.handle(conn_id, ClientMessage::Subscribe(SubscribePayload {
+            topics: vec!["  ".to_string()],
+            delta: None,
+            batch: None,
+            case: None,
+        })).await;
+        assert!(matches!(responses.as_slice(),
+            [ServerMessage::Error(e)] if e.code == ERROR_CODE_INVALID_TOPIC && e.connection_id.as_ref() == Some(&expected_id)));
+    }
+
+    #[tokio::test]
+    async fn test_ping_echoes_client_time() {
+        let (handler, connection_manager) = handler();
+        let conn_id = connection_manager.register().await;
+
+        let responses = handler.handle_text(conn_id, r#"{"type":"Ping","payload":{"clientTime":1700000000000}}"#).await;
+        assert!(matches!(responses.as_slice(),
+            [ServerMessage::Pong(pong)] if pong.client_time == 1_700_000_000_000 && pong.server_time > 0));
+    }
+}
@@ -0,0 +1,104 @@
+//! Message Stats Component
+//!
+//! Per-type counts of client messages received and server messages sent, served
+//! on `/metrics` to show how clients actually use the protocol.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::Serialize;
+
+use crate::dto::websocket::{CLIENT_MESSAGE_TYPES, SUPPORTED_SERVER_MESSAGES};
+use crate::dto::ClientMessage;
+
+/// Counter name for client text frames that didn't parse as a `ClientMessage`
+pub const INVALID_CLIENT_MESSAGE: &str = "Invalid";
+
+/// Message counters shared by every connection
+#[derive(Debug)]
+pub struct MessageStats {
+    /// One per `CLIENT_MESSAGE_TYPES` entry, then one for invalid frames
+    received: Vec<AtomicU64>,
+    /// One per `SUPPORTED_SERVER_MESSAGES` entry
+    sent: Vec<AtomicU64>,
+}
+
+/// Point-in-time copy of `MessageStats`; every type is listed, including zeros
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageStatsSnapshot {
+    pub received: BTreeMap<&'static str, u64>,
+    pub sent: BTreeMap<&'static str, u64>,
+}
+
+impl Default for MessageStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageStats {
+    /// Create zeroed counters
+    pub fn new() -> Self {
+        let zeroed = |len: usize| (0..len).map(|_| AtomicU64::new(0)).collect();
+        Self {
+            received: zeroed(CLIENT_MESSAGE_TYPES.len() + 1),
+            sent: zeroed(SUPPORTED_SERVER_MESSAGES.len()),
+        }
+    }
+
+    /// Record a parsed client message
+    pub fn record_received(&self, message: &ClientMessage) {
+        if let Some(index) = CLIENT_MESSAGE_TYPES.iter().position(|name| *name == message.type_name()) {
+            self.received[index].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record a client text frame that wasn't a valid `ClientMessage`
+    pub fn record_invalid(&self) {
+        self.received[CLIENT_MESSAGE_TYPES.len()].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a server message of `message_type` written to a client; unknown types are ignored
+    pub fn record_sent(&self, message_type: &str) {
+        if let Some(index) = SUPPORTED_SERVER_MESSAGES.iter().position(|name| *name == message_type) {
+            self.sent[index].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Current counter values
+    pub fn snapshot(&self) -> MessageStatsSnapshot {
+        let load = |names: &[&'static str], counters: &[AtomicU64]| -> BTreeMap<&'static str, u64> {
+            names.iter().copied().zip(counters.iter().map(|count| count.load(Ordering::Relaxed))).collect()
+        };
+
+        let mut received = load(CLIENT_MESSAGE_TYPES, &self.received);
+        received.insert(INVALID_CLIENT_MESSAGE, self.received[CLIENT_MESSAGE_TYPES.len()].load(Ordering::Relaxed));
+        MessageStatsSnapshot {
+            received,
+            sent: load(SUPPORTED_SERVER_MESSAGES, &self.sent),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_by_type_including_zeros() {
+        let stats = MessageStats::new();
+        stats.record_received(&ClientMessage::Heartbeat);
+        stats.record_received(&ClientMessage::Heartbeat);
+        stats.record_invalid();
+        stats.record_sent("Ack");
+        stats.record_sent("NotAMessage");
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.received["Heartbeat"], 2);
+        assert_eq!(snapshot.received["Unsubscribe"], 0);
+        assert_eq!(snapshot.received[INVALID_CLIENT_MESSAGE], 1);
+        assert_eq!(snapshot.received.len(), CLIENT_MESSAGE_TYPES.len() + 1);
+        assert_eq!(snapshot.sent["Ack"], 1);
+        assert_eq!(snapshot.sent.values().sum::<u64>(), 1);
+    }
+}
@@ -0,0 +1,300 @@
+//! WebSocket Metrics Component
+//!
+//! Process-wide WebSocket counters served on `/metrics`, plus per-connection
+//! slow-client detection and byte counts used by the socket loop.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::ws::Message;
+use serde::Serialize;
+
+/// A single send taking at least this long counts as a blocked send
+const SLOW_SEND_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// Default `WS_SLOW_CLIENT_LAG_LIMIT`
+pub const DEFAULT_SLOW_CLIENT_LAG_LIMIT: u64 = 100;
+
+/// Inclusive upper bounds of the frame size buckets; larger frames land in the last bucket
+const FRAME_SIZE_BOUNDS: [usize; 3] = [1024, 16 * 1024, 128 * 1024];
+
+/// WebSocket counters shared by every connection
+#[derive(Debug, Default)]
+pub struct WebSocketMetrics {
+    slow_clients_kicked: AtomicU64,
+    lagged_messages: AtomicU64,
+    slow_sends: AtomicU64,
+    send_timeouts: AtomicU64,
+    last_broadcast_receiver_count: AtomicU64,
+    serialization_errors: AtomicU64,
+    upgrades_accepted: AtomicU64,
+    upgrades_rejected: AtomicU64,
+    upgrades_failed: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    sent_frame_sizes: [AtomicU64; FRAME_SIZE_BOUNDS.len() + 1],
+    received_frame_sizes: [AtomicU64; FRAME_SIZE_BOUNDS.len() + 1],
+}
+
+/// Frame counts by payload size
+#[derive(Debug, Clone, Serialize)]
+pub struct FrameSizeCounts {
+    pub le_1kb: u64,
+    pub le_16kb: u64,
+    pub le_128kb: u64,
+    pub over_128kb: u64,
+}
+
+impl FrameSizeCounts {
+    fn load(buckets: &[AtomicU64; FRAME_SIZE_BOUNDS.len() + 1]) -> Self {
+        let [le_1kb, le_16kb, le_128kb, over_128kb] = buckets.each_ref().map(|count| count.load(Ordering::Relaxed));
+        Self { le_1kb, le_16kb, le_128kb, over_128kb }
+    }
+}
+
+/// Point-in-time copy of `WebSocketMetrics`
+#[derive(Debug, Clone, Serialize)]
+pub struct WebSocketMetricsSnapshot {
+    /// Connections closed with 1008 for falling behind
+    pub slow_clients_kicked: u64,
+    /// Broadcast messages skipped by lagging receivers
+    pub lagged_messages: u64,
+    /// Sends that took at least `SLOW_SEND_THRESHOLD`
+    pub slow_sends: u64,
+    /// Connections dropped because a send exceeded `WS_SEND_TIMEOUT_MS`
+    pub send_timeouts: u64,
+    /// Receivers the most recent dashboard broadcast was queued for
+    pub last_broadcast_receiver_count: u64,
+    /// Dashboard broadcasts and stream publishes skipped because encoding failed
+    pub serialization_errors: u64,
+    /// WebSocket handshakes that completed
+    pub upgrades_accepted: u64,
+    /// Upgrade requests answered with an error (bad headers, disallowed origin, connection limit)
+    pub upgrades_rejected: u64,
+    /// Upgrades accepted with 101 whose handshake then failed (e.g. client aborted)
+    pub upgrades_failed: u64,
+    /// Payload bytes written to all WebSocket connections
+    pub bytes_sent: u64,
+    /// Payload bytes read from all WebSocket connections
+    pub bytes_received: u64,
+    /// Sent frames by payload size
+    pub sent_frame_sizes: FrameSizeCounts,
+    /// Received frames by payload size
+    pub received_frame_sizes: FrameSizeCounts,
+}
+
+/// Bytes one connection has exchanged
+///
+/// Shared between the socket task, which counts its frames, and `ConnectionManager`,
+/// which reports it on `/debug/connections`.
+#[derive(Debug, Default)]
+pub struct ConnectionTraffic {
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+}
+
+impl ConnectionTraffic {
+    /// Payload bytes written to the client
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent.load(Ordering::Relaxed)
+    }
+
+    /// Payload bytes read from the client
+    pub fn bytes_received(&self) -> u64 {
+        self.bytes_received.load(Ordering::Relaxed)
+    }
+
+    /// Bytes exchanged in both directions
+    pub fn total_bytes(&self) -> u64 {
+        self.bytes_sent().saturating_add(self.bytes_received())
+    }
+}
+
+/// Payload size of a WebSocket frame
+pub fn frame_len(message: &Message) -> usize {
+    match message {
+        Message::Text(text) => text.len(),
+        Message::Binary(bytes) | Message::Ping(bytes) | Message::Pong(bytes) => bytes.len(),
+        Message::Close(frame) => frame.as_ref().map_or(0, |frame| 2 + frame.reason.len()),
+    }
+}
+
+/// Index of the size bucket a `bytes`-long frame falls in
+fn frame_size_bucket(bytes: usize) -> usize {
+    FRAME_SIZE_BOUNDS
+        .iter()
+        .position(|bound| bytes <= *bound)
+        .unwrap_or(FRAME_SIZE_BOUNDS.len())
+}
+
+impl WebSocketMetrics {
+    /// Create zeroed counters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current counter values
+    pub fn snapshot(&self) -> WebSocketMetricsSnapshot {
+        WebSocketMetricsSnapshot {
+            slow_clients_kicked: self.slow_clients_kicked.load(Ordering::Relaxed),
+            lagged_messages: self.lagged_messages.load(Ordering::Relaxed),
+            slow_sends: self.slow_sends.load(Ordering::Relaxed),
+            send_timeouts: self.send_timeouts.load(Ordering::Relaxed),
+            last_broadcast_receiver_count: self.last_broadcast_receiver_count.load(Ordering::Relaxed),
+            serialization_errors: self.serialization_errors.load(Ordering::Relaxed),
+            upgrades_accepted: self.upgrades_accepted.load(Ordering::Relaxed),
+            upgrades_rejected: self.upgrades_rejected.load(Ordering::Relaxed),
+            upgrades_failed: self.upgrades_failed.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            sent_frame_sizes: FrameSizeCounts::load(&self.sent_frame_sizes),
+            received_frame_sizes: FrameSizeCounts::load(&self.received_frame_sizes),
+        }
+    }
+
+    /// Record a frame of `bytes` sent on `connection`
+    pub fn record_sent(&self, connection: &ConnectionTraffic, bytes: usize) {
+        connection.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+        self.sent_frame_sizes[frame_size_bucket(bytes)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a frame of `bytes` received on `connection`
+    pub fn record_received(&self, connection: &ConnectionTraffic, bytes: usize) {
+        connection.bytes_received.fetch_add(bytes as u64, Ordering::Relaxed);
+        self.bytes_received.fetch_add(bytes as u64, Ordering::Relaxed);
+        self.received_frame_sizes[frame_size_bucket(bytes)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a connection dropped for a send that never completed
+    pub fn record_send_timeout(&self) {
+        self.send_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a broadcast or publish dropped because it couldn't be serialized
+    pub fn record_serialization_error(&self) {
+        self.serialization_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a completed WebSocket handshake
+    pub fn record_upgrade_accepted(&self) {
+        self.upgrades_accepted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record an upgrade request answered with an error status
+    pub fn record_upgrade_rejected(&self) {
+        self.upgrades_rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record an upgrade whose handshake failed after the 101 response
+    pub fn record_upgrade_failed(&self) {
+        self.upgrades_failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record how many receivers a dashboard broadcast reached
+    pub fn record_broadcast(&self, receivers: usize) {
+        self.last_broadcast_receiver_count.store(receivers as u64, Ordering::Relaxed);
+    }
+}
+
+/// Slow-client bookkeeping for one connection
+///
+/// Each broadcast message the receiver skipped and each blocked send is a strike;
+/// the client should be disconnected once strikes reach `lag_limit` (0 never kicks).
+pub struct SlowClientDetector {
+    lag_limit: u64,
+    strikes: u64,
+    metrics: Arc<WebSocketMetrics>,
+}
+
+impl SlowClientDetector {
+    /// Create a detector for a new connection
+    pub fn new(lag_limit: u64, metrics: Arc<WebSocketMetrics>) -> Self {
+        Self {
+            lag_limit,
+            strikes: 0,
+            metrics,
+        }
+    }
+
+    /// Record `skipped` broadcast messages; true if the client should be kicked
+    pub fn record_lag(&mut self, skipped: u64) -> bool {
+        self.metrics.lagged_messages.fetch_add(skipped, Ordering::Relaxed);
+        self.add_strikes(skipped)
+    }
+
+    /// Record how long a send took; true if the client should be kicked
+    pub fn record_send(&mut self, elapsed: Duration) -> bool {
+        if elapsed < SLOW_SEND_THRESHOLD {
+            return false;
+        }
+        self.metrics.slow_sends.fetch_add(1, Ordering::Relaxed);
+        self.add_strikes(1)
+    }
+
+    /// Strikes recorded so far
+    pub fn strikes(&self) -> u64 {
+        self.strikes
+    }
+
+    fn add_strikes(&mut self, count: u64) -> bool {
+        self.strikes = self.strikes.saturating_add(count);
+        let kick = self.lag_limit > 0 && self.strikes >= self.lag_limit;
+        if kick {
+            self.metrics.slow_clients_kicked.fetch_add(1, Ordering::Relaxed);
+        }
+        kick
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kicks_once_strikes_reach_limit() {
+        let metrics = Arc::new(WebSocketMetrics::new());
+        let mut detector = SlowClientDetector::new(3, metrics.clone());
+
+        assert!(!detector.record_send(Duration::from_millis(10)));
+        assert!(!detector.record_lag(1));
+        assert!(!detector.record_send(SLOW_SEND_THRESHOLD));
+        assert!(detector.record_lag(1));
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.slow_clients_kicked, 1);
+        assert_eq!(snapshot.lagged_messages, 2);
+        assert_eq!(snapshot.slow_sends, 1);
+
+        metrics.record_broadcast(42);
+        assert_eq!(metrics.snapshot().last_broadcast_receiver_count, 42);
+
+        let mut never = SlowClientDetector::new(0, metrics);
+        assert!(!never.record_lag(10_000));
+    }
+
+    #[test]
+    fn test_byte_counts_per_connection_and_bucket() {
+        let metrics = WebSocketMetrics::new();
+        let first = ConnectionTraffic::default();
+        let second = ConnectionTraffic::default();
+
+        metrics.record_sent(&first, 1024);
+        metrics.record_sent(&first, 1025);
+        metrics.record_sent(&second, 200_000);
+        metrics.record_received(&second, 40);
+
+        assert_eq!((first.bytes_sent(), first.bytes_received()), (2049, 0));
+        assert_eq!(second.total_bytes(), 200_040);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.bytes_sent, 202_049);
+        assert_eq!(snapshot.bytes_received, 40);
+        assert_eq!(snapshot.sent_frame_sizes.le_1kb, 1);
+        assert_eq!(snapshot.sent_frame_sizes.le_16kb, 1);
+        assert_eq!(snapshot.sent_frame_sizes.le_128kb, 0);
+        assert_eq!(snapshot.sent_frame_sizes.over_128kb, 1);
+        assert_eq!(snapshot.received_frame_sizes.le_1kb, 1);
+    }
+}
@@ -11,18 +11,36 @@ pub mod message_handler;
 pub mod broadcast_service;
 pub mod handlers;
 pub mod market_data_streamer;
+pub mod serialization_pool;
+pub mod metrics;
+pub mod message_stats;
+pub mod history_buffer;
+pub mod transforms;
+pub mod rate_limiter;
+pub mod outbound;
 
 use anyhow::Result;
+use std::future::Future;
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, Semaphore, SemaphorePermit};
 use tracing::{info, warn, debug};
 
-use connection_manager::ConnectionManager;
+use axum::extract::ws::Message;
+use connection_manager::{ConnectionManager, ConnectionState};
 use message_handler::MessageHandler;
 use broadcast_service::BroadcastService;
 use handlers::WebSocketHandlers;
 use market_data_streamer::MarketDataStreamer;
+use history_buffer::{HistoryBuffer, DEFAULT_HISTORY_BUFFER_SIZE};
+use metrics::{SlowClientDetector, WebSocketMetrics, DEFAULT_SLOW_CLIENT_LAG_LIMIT};
+use message_stats::MessageStats;
+use rate_limiter::{ClientRateLimiter, DEFAULT_MAX_CLIENT_MESSAGES_PER_SECOND};
+use outbound::DEFAULT_SEND_TIMEOUT;
+use serialization_pool::PreparedMessage;
+use transforms::{recase_message, OutputCase};
+use crate::dto::ServerMessage;
 use crate::service_islands::layer2_external_services::external_apis_island::ExternalApisIsland;
+use crate::config::Config;
 // use crate::service_islands::layer3_communication::layer2_adapters::Layer2AdaptersHub;  // Removed - using external_apis directly
 
 /// WebSocket Service Island
@@ -41,6 +59,22 @@ pub struct WebSocketServiceIsland {
     pub handlers: Arc<WebSocketHandlers>,
     /// Market data streaming component
     pub market_data_streamer: Arc<MarketDataStreamer>,
+    /// Counters served on `/metrics`
+    pub metrics: Arc<WebSocketMetrics>,
+    /// Client messages received and server messages sent, by type (also on `/metrics`)
+    pub message_stats: Arc<MessageStats>,
+    /// Recent broadcast dashboards for `GetHistory`
+    pub history: Arc<HistoryBuffer>,
+    /// Strikes (skipped messages + blocked sends) before a slow client is kicked; 0 never kicks
+    pub slow_client_lag_limit: u64,
+    /// Client messages accepted per connection per second; 0 disables the limit
+    pub max_client_messages_per_second: u32,
+    /// Longest a single outbound frame may block before the connection is dropped
+    pub send_timeout: std::time::Duration,
+    /// Key casing of dashboard data for connections that didn't choose one (`OUTPUT_CASE`)
+    pub output_case: OutputCase,
+    /// Bounds how many connections frame and send a broadcast at once; None is unlimited
+    broadcast_permits: Option<Semaphore>,
     /// Broadcast transmitter for real-time updates
     /// Note: Used by broadcast_service for WebSocket message broadcasting
     pub broadcast_tx: broadcast::Sender<String>,
@@ -51,15 +85,28 @@ impl WebSocketServiceIsland {
     /// 
     /// Creates all components and establishes communication channels with Layer 2 and cache optimization.
     pub async fn with_external_apis_and_cache(
-        _external_apis: Arc<ExternalApisIsland>,
-        _cache_system: Arc<crate::service_islands::layer1_infrastructure::cache_system_island::CacheSystemIsland>
+        external_apis: Arc<ExternalApisIsland>,
+        _cache_system: Arc<crate::service_islands::layer1_infrastructure::cache_system_island::CacheSystemIsland>,
+        config: &Config,
     ) -> Result<Self> {
         info!("Initializing WebSocket Service Island with External APIs and Cache");
 
         // Initialize components
-        let connection_manager = Arc::new(ConnectionManager::new());
-        let message_handler = Arc::new(MessageHandler::new());
-        let broadcast_service = Arc::new(BroadcastService::new());
+        let connection_manager = Arc::new(ConnectionManager::with_resume_grace(
+            std::time::Duration::from_secs(config.resume_grace_seconds),
+        ));
+        let broadcast_service = Arc::new(
+            BroadcastService::with_full_snapshot_every(config.delta_full_snapshot_every)
+                .with_output_case(config.output_case),
+        );
+        let history = Arc::new(HistoryBuffer::new(config.history_buffer_size));
+        let message_stats = Arc::new(MessageStats::new());
+        let message_handler = Arc::new(
+            MessageHandler::new(connection_manager.clone(), broadcast_service.clone())
+                .with_price_source(external_apis)
+                .with_history(history.clone())
+                .with_stats(message_stats.clone()),
+        );
         let handlers = Arc::new(WebSocketHandlers::new());
         
         // Initialize market data streamer WITHOUT external APIs dependency
@@ -78,6 +125,15 @@ impl WebSocketServiceIsland {
             broadcast_service,
             handlers,
             market_data_streamer,
+            metrics: Arc::new(WebSocketMetrics::new()),
+            message_stats,
+            history,
+            slow_client_lag_limit: config.ws_slow_client_lag_limit,
+            max_client_messages_per_second: config.ws_max_client_messages_per_second,
+            send_timeout: std::time::Duration::from_millis(config.ws_send_timeout_ms),
+            output_case: config.output_case,
+            broadcast_permits: (config.ws_broadcast_concurrency > 0)
+                .then(|| Semaphore::new(config.ws_broadcast_concurrency)),
             broadcast_tx,
         })
     }
@@ -94,8 +150,14 @@ impl WebSocketServiceIsland {
 
         // Initialize components
         let connection_manager = Arc::new(ConnectionManager::new());
-        let message_handler = Arc::new(MessageHandler::new());
         let broadcast_service = Arc::new(BroadcastService::new());
+        let history = Arc::new(HistoryBuffer::new(DEFAULT_HISTORY_BUFFER_SIZE));
+        let message_stats = Arc::new(MessageStats::new());
+        let message_handler = Arc::new(
+            MessageHandler::new(connection_manager.clone(), broadcast_service.clone())
+                .with_history(history.clone())
+                .with_stats(message_stats.clone()),
+        );
         let handlers = Arc::new(WebSocketHandlers::new());
 
         // Initialize market data streamer
@@ -112,6 +174,14 @@ impl WebSocketServiceIsland {
             broadcast_service,
             handlers,
             market_data_streamer,
+            metrics: Arc::new(WebSocketMetrics::new()),
+            message_stats,
+            history,
+            slow_client_lag_limit: DEFAULT_SLOW_CLIENT_LAG_LIMIT,
+            max_client_messages_per_second: DEFAULT_MAX_CLIENT_MESSAGES_PER_SECOND,
+            send_timeout: DEFAULT_SEND_TIMEOUT,
+            output_case: OutputCase::default(),
+            broadcast_permits: None,
             broadcast_tx,
         })
     }
@@ -131,24 +201,68 @@ impl WebSocketServiceIsland {
             ("Market Data Streamer", self.market_data_streamer.health_check().await),
         ];
         
-        let mut all_healthy = true;
+        let mut unhealthy = Vec::new();
         for (component, healthy) in checks {
             if healthy {
                 debug!("{} - Healthy", component);
             } else {
                 warn!("{} - Unhealthy", component);
-                all_healthy = false;
+                unhealthy.push(component);
             }
         }
         
-        if all_healthy {
+        if unhealthy.is_empty() {
             info!("WebSocket Service Island - All components healthy");
             Ok(())
         } else {
-            Err(anyhow::anyhow!("WebSocket Service Island - Some components unhealthy"))
+            Err(anyhow::anyhow!("Unhealthy components: {}", unhealthy.join(", ")))
         }
     }
 
+    /// Slow-client detector for a new connection
+    pub fn slow_client_detector(&self) -> SlowClientDetector {
+        SlowClientDetector::new(self.slow_client_lag_limit, Arc::clone(&self.metrics))
+    }
+
+    /// Wait for a turn to send a broadcast (`WS_BROADCAST_CONCURRENCY`)
+    ///
+    /// `None` when the limit is disabled.
+    pub async fn broadcast_permit(&self) -> Option<SemaphorePermit<'_>> {
+        // The semaphore is never closed, so acquiring only fails when there is none
+        self.broadcast_permits.as_ref()?.acquire().await.ok()
+    }
+
+    /// Frame a broadcast for one connection and `send` it, holding a broadcast permit throughout
+    ///
+    /// Every connection wakes on the same broadcast; the permit bounds how many of them
+    /// frame and write it at once, spreading a fan-out to many clients instead of one
+    /// spike. `send` must give up after `send_timeout`, so a stuck client delays the
+    /// others by at most that long.
+    pub async fn send_broadcast<F, Fut>(&self, conn_state: &ConnectionState, prepared: &PreparedMessage, send: F) -> Fut::Output
+    where
+        F: FnOnce((Message, Option<&'static str>)) -> Fut,
+        Fut: Future,
+    {
+        let _permit = self.broadcast_permit().await;
+        send(conn_state.frame_for(prepared)).await
+    }
+
+    /// JSON text of a direct reply or greeting in the connection's key casing
+    ///
+    /// Dashboard-bearing messages (e.g. `History`) are re-keyed like broadcasts; others
+    /// serialize as usual.
+    pub fn encode_for(&self, conn_state: &ConnectionState, message: &ServerMessage) -> serde_json::Result<String> {
+        match conn_state.case.unwrap_or(self.output_case) {
+            OutputCase::Camel => message.to_json_string(),
+            case => serde_json::to_string(&recase_message(serde_json::to_value(message)?, case)),
+        }
+    }
+
+    /// Client message rate limiter for a new connection
+    pub fn client_rate_limiter(&self) -> ClientRateLimiter {
+        ClientRateLimiter::new(self.max_client_messages_per_second)
+    }
+
     /// Fetch market data (DEPRECATED - now handled by top-level ServiceIslands)
     ///
     /// This method is no longer used. Market data fetching is now done by
@@ -158,3 +272,51 @@ impl WebSocketServiceIsland {
         Err(anyhow::anyhow!("fetch_market_data is deprecated - use ServiceIslands.fetch_and_publish_market_data() instead"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use crate::service_islands::ServiceIslands;
+
+    async fn islands_with_broadcast_concurrency(limit: &str) -> ServiceIslands {
+        let config = Config::from_lookup(|name| match name {
+            "CACHE_L2" => Some("none".to_string()),
+            "WS_BROADCAST_CONCURRENCY" => Some(limit.to_string()),
+            _ => None,
+        }).unwrap();
+        ServiceIslands::initialize(config).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_permit_is_released_after_sending() {
+        let islands = islands_with_broadcast_concurrency("1").await;
+        let websocket_service = &islands.websocket_service;
+        let prepared = PreparedMessage::from_text(r#"{"type":"Notice","payload":{}}"#.to_string());
+        let conn_state = ConnectionState::default();
+
+        let first = websocket_service.send_broadcast(&conn_state, &prepared, |(_, kind)| async move { kind }).await;
+        let next = websocket_service.send_broadcast(&conn_state, &prepared, |(_, kind)| async move { kind });
+        let second = tokio::time::timeout(Duration::from_secs(1), next).await.unwrap();
+        assert_eq!((first, second), (Some("Notice"), Some("Notice")));
+    }
+
+    #[tokio::test]
+    async fn test_at_most_the_configured_number_of_broadcast_sends_run_at_once() {
+        let islands = islands_with_broadcast_concurrency("2").await;
+        let websocket_service = &islands.websocket_service;
+        let prepared = PreparedMessage::from_text(r#"{"type":"Notice","payload":{}}"#.to_string());
+        let conn_state = ConnectionState::default();
+        let (sending, most_sending) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        let sends = (0..6).map(|_| websocket_service.send_broadcast(&conn_state, &prepared, |_| async {
+            let now = sending.fetch_add(1, Ordering::SeqCst) + 1;
+            most_sending.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            sending.fetch_sub(1, Ordering::SeqCst);
+        }));
+        futures::future::join_all(sends).await;
+        assert_eq!(most_sending.load(Ordering::SeqCst), 2);
+    }
+}
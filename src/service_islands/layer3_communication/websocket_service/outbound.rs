@@ -0,0 +1,83 @@
+//! Outbound Send Component
+//!
+//! Bounded sends to a client socket. A client whose TCP receive buffer is full
+//! and never reads would otherwise park its connection task on `send` forever,
+//! holding a connection slot.
+
+use std::time::Duration;
+
+use futures::{Sink, SinkExt};
+
+/// Default `WS_SEND_TIMEOUT_MS`
+pub const DEFAULT_SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Result of one bounded send
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendOutcome {
+    Sent,
+    /// The socket is gone
+    Closed,
+    /// The send was still blocked when the timeout elapsed; the connection should be dropped
+    TimedOut,
+}
+
+/// Send `message`, giving up after `timeout`
+pub async fn send_with_timeout<S, M>(sink: &mut S, message: M, timeout: Duration) -> SendOutcome
+where
+    S: Sink<M> + Unpin,
+{
+    match tokio::time::timeout(timeout, sink.send(message)).await {
+        Ok(Ok(())) => SendOutcome::Sent,
+        Ok(Err(_)) => SendOutcome::Closed,
+        Err(_) => SendOutcome::TimedOut,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use futures::StreamExt;
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_tungstenite::tungstenite::Message;
+
+    use super::super::connection_manager::ConnectionSlot;
+
+    #[tokio::test]
+    async fn test_client_that_never_reads_is_dropped() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let active_ws_connections = Arc::new(AtomicUsize::new(0));
+        let slot = ConnectionSlot::try_reserve(&active_ws_connections, 1).unwrap();
+
+        // Server side of the connection: keep sending until a send blocks past the timeout
+        let server = tokio::spawn(async move {
+            let _slot = slot;
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            loop {
+                let frame = Message::Binary(vec![0; 1 << 20]);
+                match send_with_timeout(&mut socket, frame, Duration::from_millis(200)).await {
+                    SendOutcome::Sent => continue,
+                    outcome => return outcome,
+                }
+            }
+        });
+
+        // The client completes the handshake, then never reads
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let (mut client, _) = tokio_tungstenite::client_async(format!("ws://{}/", addr), stream).await.unwrap();
+
+        let outcome = tokio::time::timeout(Duration::from_secs(30), server).await.unwrap().unwrap();
+        assert_eq!(outcome, SendOutcome::TimedOut);
+        assert_eq!(active_ws_connections.load(Ordering::SeqCst), 0);
+
+        // Once it reads again, the client finds the connection closed
+        let disconnected = tokio::time::timeout(Duration::from_secs(30), async {
+            while let Some(Ok(_)) = client.next().await {}
+        });
+        assert!(disconnected.await.is_ok());
+    }
+}
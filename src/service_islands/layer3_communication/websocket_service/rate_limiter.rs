@@ -0,0 +1,67 @@
+//! Client Message Rate Limiter Component
+//!
+//! Per-connection cap on inbound client messages (Subscribe, Ping, Heartbeat, ...),
+//! checked by the socket loop before a message is handled.
+
+use std::time::{Duration, Instant};
+
+/// Default `WS_MAX_CLIENT_MESSAGES_PER_SECOND`
+pub const DEFAULT_MAX_CLIENT_MESSAGES_PER_SECOND: u32 = 20;
+
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Fixed one-second window counter for one connection
+pub struct ClientRateLimiter {
+    /// Messages allowed per window (0 disables the limit)
+    limit: u32,
+    window_start: Instant,
+    count: u32,
+}
+
+impl ClientRateLimiter {
+    /// Create a limiter allowing `limit` messages per second (0 allows everything)
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            window_start: Instant::now(),
+            count: 0,
+        }
+    }
+
+    /// Count a message; false if it exceeds this second's budget
+    pub fn allow(&mut self) -> bool {
+        self.allow_at(Instant::now())
+    }
+
+    fn allow_at(&mut self, now: Instant) -> bool {
+        if self.limit == 0 {
+            return true;
+        }
+        if now.duration_since(self.window_start) >= WINDOW {
+            self.window_start = now;
+            self.count = 0;
+        }
+        self.count += 1;
+        self.count <= self.limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_resets_each_window() {
+        let start = Instant::now();
+        let mut limiter = ClientRateLimiter::new(2);
+        limiter.window_start = start;
+
+        assert!(limiter.allow_at(start));
+        assert!(limiter.allow_at(start));
+        assert!(!limiter.allow_at(start + Duration::from_millis(500)));
+        assert!(limiter.allow_at(start + WINDOW));
+
+        let mut unlimited = ClientRateLimiter::new(0);
+        assert!((0..1000).all(|_| unlimited.allow()));
+    }
+}
@@ -0,0 +1,288 @@
+//! Broadcast Transforms Component
+//!
+//! Optional per-deployment shaping of the dashboard right before it is broadcast
+//! (e.g. rounding prices, or a crypto-only view without US indices). Built-in
+//! transforms are selected from `Config`; custom ones can be installed with
+//! `ServiceIslands::with_transform`. `OutputCase` re-keys the dashboard data
+//! in outgoing messages for consumers that expect snake_case.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::Config;
+
+/// Rewrites the dashboard `data` object before it reaches clients
+pub type DashboardTransform = Box<dyn Fn(Value) -> Value + Send + Sync>;
+
+/// Round every non-integer number (at any depth) to `decimals` places
+pub fn round_numbers(decimals: u32) -> DashboardTransform {
+    let factor = 10f64.powi(decimals as i32);
+    Box::new(move |mut data| {
+        round_in_place(&mut data, factor);
+        data
+    })
+}
+
+fn round_in_place(value: &mut Value, factor: f64) {
+    match value {
+        Value::Number(number) if number.is_f64() => {
+            if let Some(rounded) = number
+                .as_f64()
+                .and_then(|n| serde_json::Number::from_f64((n * factor).round() / factor))
+            {
+                *number = rounded;
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| round_in_place(item, factor)),
+        Value::Object(map) => map.values_mut().for_each(|item| round_in_place(item, factor)),
+        _ => {}
+    }
+}
+
+/// Keep only the listed top-level fields
+///
+/// Clients on the full channel see exactly these fields. Dropping fields required by
+/// `DashboardData` disables delta diffing and `GetHistory` for the stripped broadcasts.
+pub fn field_allowlist(fields: Vec<String>) -> DashboardTransform {
+    Box::new(move |data| match data {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(field, _)| fields.contains(field))
+                .collect(),
+        ),
+        other => other,
+    })
+}
+
+/// Key casing of dashboard data sent to clients (`OUTPUT_CASE`, or per connection
+/// with `Subscribe { case }`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputCase {
+    /// As serialized: camelCase `DashboardData` fields (snake_case for untyped data)
+    #[default]
+    Camel,
+    /// snake_case, as the aggregator and the Redis stream use
+    Snake,
+    /// Every field under both names, for migrating consumers
+    Both,
+}
+
+impl OutputCase {
+    pub const ALL: [OutputCase; 3] = [OutputCase::Camel, OutputCase::Snake, OutputCase::Both];
+}
+
+/// Where a serialized `ServerMessage` of type `kind` carries dashboard data, if it does
+fn dashboard_pointer(kind: &str) -> Option<&'static str> {
+    match kind {
+        "DashboardUpdate" => Some("/payload/data"),
+        "DashboardDelta" => Some("/payload/changes"),
+        "History" => Some("/payload/entries"),
+        _ => None,
+    }
+}
+
+/// Whether a serialized `ServerMessage` carries dashboard data (directly or inside a `Batch`)
+pub fn carries_dashboard(message: &Value) -> bool {
+    match message["type"].as_str() {
+        Some("Batch") => message["payload"].as_array().is_some_and(|items| items.iter().any(carries_dashboard)),
+        Some(kind) => dashboard_pointer(kind).is_some(),
+        None => false,
+    }
+}
+
+/// Re-key the dashboard data in a serialized (camelCase) `ServerMessage` for `case`
+///
+/// Covers `DashboardUpdate` data, `DashboardDelta` changes, `History` entries and the
+/// same inside a `Batch`; other messages are returned unchanged.
+pub fn recase_message(mut message: Value, case: OutputCase) -> Value {
+    if case == OutputCase::Camel {
+        return message;
+    }
+    match message["type"].as_str() {
+        Some("Batch") => {
+            if let Some(Value::Array(items)) = message.get_mut("payload") {
+                for item in items {
+                    *item = recase_message(item.take(), case);
+                }
+            }
+        }
+        Some(kind) => {
+            if let Some(data) = dashboard_pointer(kind).and_then(|pointer| message.pointer_mut(pointer)) {
+                *data = apply_output_case(data.take(), case);
+            }
+        }
+        None => {}
+    }
+    message
+}
+
+/// Re-key every object in `data` (at any depth) for `case`
+///
+/// Acronym keys such as index symbols (`SPY`) are left alone by both conversions.
+pub fn apply_output_case(data: Value, case: OutputCase) -> Value {
+    match (case, data) {
+        (OutputCase::Camel, data) => data,
+        (_, Value::Array(items)) => Value::Array(items.into_iter().map(|item| apply_output_case(item, case)).collect()),
+        (_, Value::Object(map)) => {
+            let mut recased = serde_json::Map::with_capacity(map.len());
+            for (key, value) in map {
+                let value = apply_output_case(value, case);
+                if case == OutputCase::Both {
+                    recased.insert(snake_to_camel(&key), value.clone());
+                }
+                recased.insert(camel_to_snake(&key), value);
+            }
+            Value::Object(recased)
+        }
+        (_, scalar) => scalar,
+    }
+}
+
+/// Top-level fields of `data` under their camelCase names, valued as in `typed`
+///
+/// `typed` is `data` serialized back from `DashboardData`; fields it doesn't know
+/// (e.g. `content_hash`) keep their value, and fields `data` lacks are not added.
+pub fn camel_case_fields(data: Value, typed: &Value) -> Value {
+    match data {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let key = snake_to_camel(&key);
+                    let value = typed.get(&key).cloned().unwrap_or(value);
+                    (key, value)
+                })
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// `btcChange24h` -> `btc_change_24h`: an underscore before each uppercase letter or
+/// digit run that follows a lowercase letter
+fn camel_to_snake(key: &str) -> String {
+    let mut snake = String::with_capacity(key.len() + 4);
+    let mut previous: Option<char> = None;
+    for c in key.chars() {
+        let after_lowercase = previous.is_some_and(|p| p.is_ascii_lowercase());
+        if c.is_ascii_uppercase() && (after_lowercase || previous.is_some_and(|p| p.is_ascii_digit())) {
+            snake.push('_');
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            if c.is_ascii_digit() && after_lowercase {
+                snake.push('_');
+            }
+            snake.push(c);
+        }
+        previous = Some(c);
+    }
+    snake
+}
+
+/// `btc_change_24h` -> `btcChange24h`, as serde's `rename_all = "camelCase"` names it
+fn snake_to_camel(key: &str) -> String {
+    let mut camel = String::with_capacity(key.len());
+    let mut capitalize = false;
+    for c in key.chars() {
+        match c {
+            '_' if !camel.is_empty() => capitalize = true,
+            c if capitalize => {
+                camel.push(c.to_ascii_uppercase());
+                capitalize = false;
+            }
+            c => camel.push(c),
+        }
+    }
+    camel
+}
+
+/// Built-in transforms enabled in the config (allowlist first, then rounding)
+pub fn from_config(config: &Config) -> Option<DashboardTransform> {
+    let mut transforms = Vec::new();
+    if let Some(fields) = &config.broadcast_field_allowlist {
+        transforms.push(field_allowlist(fields.clone()));
+    }
+    if let Some(decimals) = config.broadcast_round_decimals {
+        transforms.push(round_numbers(decimals));
+    }
+
+    match transforms.len() {
+        0 => None,
+        1 => transforms.pop(),
+        _ => Some(Box::new(move |data| transforms.iter().fold(data, |data, transform| transform(data)))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_builtin_transforms() {
+        let data = serde_json::json!({
+            "btc_price_usd": 96062.4712,
+            "fng_value": 10,
+            "us_stock_indices": { "SPY": { "price": 512.346 } },
+        });
+
+        let rounded = round_numbers(2)(data.clone());
+        assert_eq!(rounded["btc_price_usd"], serde_json::json!(96062.47));
+        assert_eq!(rounded["fng_value"], serde_json::json!(10));
+        assert_eq!(rounded["us_stock_indices"]["SPY"]["price"], serde_json::json!(512.35));
+
+        let crypto_only = field_allowlist(vec!["btc_price_usd".to_string()])(data);
+        assert_eq!(crypto_only, serde_json::json!({ "btc_price_usd": 96062.4712 }));
+    }
+
+    #[test]
+    fn test_output_case_round_trips_dashboard_fields() {
+        let snake = serde_json::json!({
+            "btc_price_usd": 96062.47, "btc_change_24h": 1.47, "btc_market_cap_percentage": 57.2, "btc_rsi_14": 33.4,
+            "eth_price_usd": 3177.25, "eth_change_24h": 2.95, "eth_market_cap_percentage": 11.4,
+            "sol_price_usd": 141.15, "sol_change_24h": 3.24, "xrp_price_usd": 2.25, "xrp_change_24h": 0.07,
+            "ada_price_usd": 0.5, "ada_change_24h": 0.79, "link_price_usd": 14.2, "link_change_24h": 1.64,
+            "bnb_price_usd": 935.51, "bnb_change_24h": 4.13,
+            "market_cap_usd": 3.3e12, "volume_24h_usd": 2.0e11, "market_cap_change_percentage_24h_usd": 0.87,
+            "us_stock_indices": { "SPY": { "symbol": "SPY", "name": "S&P 500", "price": 512.3, "change": 1.0, "change_percent": 0.2, "status": "success" } },
+            "fetch_duration_ms": 114, "partial_failure": false, "stale_fields": ["fng_value"],
+            "timings": { "crypto_ms": 1, "global_ms": 2, "fng_ms": 3, "rsi_ms": 4, "indices_ms": 5 },
+            "last_updated": "t", "timestamp": "t",
+        });
+        let camel = serde_json::to_value(crate::dto::DashboardData::deserialize(&snake).unwrap()).unwrap();
+        assert_eq!(camel["btcChange24h"], 1.47);
+
+        let recased = apply_output_case(camel.clone(), OutputCase::Snake);
+        for (field, value) in snake.as_object().unwrap() {
+            assert_eq!(&recased[field], value, "{}", field);
+        }
+        assert_eq!(recased["stale_fields"], serde_json::json!(["fng_value"]));
+
+        let both = apply_output_case(camel.clone(), OutputCase::Both);
+        assert_eq!(both["btcChange24h"], both["btc_change_24h"]);
+        assert_eq!(both["usStockIndices"]["SPY"]["changePercent"], 0.2);
+        assert_eq!(both["us_stock_indices"]["SPY"]["change_percent"], 0.2);
+        assert_eq!(apply_output_case(camel.clone(), OutputCase::Camel), camel);
+    }
+
+    #[test]
+    fn test_recase_message_covers_every_dashboard_message() {
+        let update = serde_json::json!({ "type": "DashboardUpdate", "payload": { "data": { "btcPriceUsd": 1.0 }, "timestamp": "t" } });
+        let delta = serde_json::json!({ "type": "DashboardDelta", "payload": { "changes": { "fngValue": 10 } } });
+        let history = serde_json::json!({ "type": "History", "payload": { "entries": [{ "ageMs": 5 }] } });
+        let notice = serde_json::json!({ "type": "Notice", "payload": { "message": "m", "levelName": "x" } });
+        let batch = serde_json::json!({ "type": "Batch", "payload": [update.clone(), notice.clone()] });
+
+        assert_eq!(recase_message(update.clone(), OutputCase::Snake)["payload"]["data"], serde_json::json!({ "btc_price_usd": 1.0 }));
+        assert_eq!(recase_message(delta, OutputCase::Snake)["payload"]["changes"], serde_json::json!({ "fng_value": 10 }));
+        assert_eq!(recase_message(history, OutputCase::Both)["payload"]["entries"], serde_json::json!([{ "ageMs": 5, "age_ms": 5 }]));
+        assert_eq!(recase_message(notice.clone(), OutputCase::Snake), notice);
+        assert_eq!(recase_message(batch.clone(), OutputCase::Snake)["payload"][0]["payload"]["data"]["btc_price_usd"], 1.0);
+        assert_eq!(recase_message(batch.clone(), OutputCase::Camel), batch);
+
+        assert!(carries_dashboard(&batch));
+        assert!(!carries_dashboard(&notice));
+        assert_eq!(serde_json::from_str::<OutputCase>(r#""snake""#).unwrap(), OutputCase::Snake);
+    }
+}
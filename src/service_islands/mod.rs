@@ -8,11 +8,219 @@ pub mod layer2_external_services;
 pub mod layer3_communication;
 
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicUsize};
+use std::time::{Duration, Instant};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{watch, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+use crate::admin_auth::AdminRequestVerifier;
 
 use layer1_infrastructure::{CacheSystemIsland, LeaderElectionService};
+use layer1_infrastructure::cache_system_island::CacheL2;
+use layer1_infrastructure::cache_system_island::stream_consumer::{
+    self, MarketDataStreamConsumer, MarketDataStreamEntry, MARKET_DATA_STREAM, MARKET_DATA_STREAM_MAXLEN,
+};
 use layer2_external_services::ExternalApisIsland;
+use layer2_external_services::external_apis_island::api_aggregator::dashboard_aggregator::dashboard_content_hash;
+use layer2_external_services::external_apis_island::api_aggregator::source_switches::DATA_GROUPS;
+use layer2_external_services::external_apis_island::circuit_breaker::{CircuitBreakerStatus, CircuitState};
+use layer2_external_services::external_apis_island::price_vwap::PriceMode;
 use layer3_communication::WebSocketServiceIsland;
+use layer3_communication::websocket_service::connection_manager::ConnectionSlot;
+use layer3_communication::websocket_service::transforms::{self, DashboardTransform};
+use crate::config::Config;
+use crate::dto::ServerMessage;
+use crate::dto::websocket::{DashboardUpdatePayload, NoticeLevel, NoticePayload};
+
+/// Shared admin flag (see `LeaderElectionService::set_shared_flag`) of a disabled data group
+fn source_disabled_flag(group: &str) -> String {
+    format!("source_disabled:{}", group)
+}
+
+/// Shared admin flag raised while the cluster is in maintenance mode
+const MAINTENANCE_FLAG: &str = "maintenance";
+
+/// Shared admin flag raised while dashboard broadcasting is paused
+const BROADCASTING_PAUSED_FLAG: &str = "broadcasting_paused";
+
+/// Part of the shutdown budget kept for releasing leadership (see `shutdown_drain_deadline`)
+const LEADERSHIP_RELEASE_RESERVE: Duration = Duration::from_secs(3);
+
+/// Health of one island, with the failure reason when unhealthy
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentHealth {
+    pub healthy: bool,
+    /// Error chain of the failed check, e.g. "Redis health probe failed: Connection refused"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ComponentHealth {
+    fn from_result(result: anyhow::Result<()>) -> Self {
+        match result {
+            Ok(()) => Self { healthy: true, error: None },
+            Err(e) => Self { healthy: false, error: Some(format!("{:#}", e)) },
+        }
+    }
+
+    fn symbol(&self) -> String {
+        match &self.error {
+            None => "✅".to_string(),
+            Some(error) => format!("❌ {}", error),
+        }
+    }
+}
+
+/// Result of `ServiceIslands::health_check_detailed`, serialized as the `/health` details
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    /// Core services (cache, websocket) are healthy; reported via the HTTP status code
+    #[serde(skip)]
+    pub healthy: bool,
+    /// "healthy", "degraded" (external APIs down or circuits open) or "unhealthy"
+    pub status: &'static str,
+    pub cache_system: ComponentHealth,
+    pub external_apis: ComponentHealth,
+    pub websocket_service: ComponentHealth,
+    pub circuit_breakers: Vec<CircuitBreakerStatus>,
+    /// Leadership changed hands more than 3 times in the last minute (reported as degraded)
+    pub leadership_unstable: bool,
+}
+
+/// Optional features active in this process, captured by `ServiceIslands::initialize`
+///
+/// Logged once at startup and reported as `features` on `/health`.
+#[derive(Debug, Clone, Serialize)]
+pub struct InitReport {
+    /// "redis" or "memory" (`CACHE_L2`)
+    pub cache_l2: &'static str,
+    pub redis_read_replica: bool,
+    /// `CACHE_KEY_PREFIX`, if set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_key_prefix: Option<String>,
+    /// "redis" (distributed lock) or "standalone" (always leader)
+    pub leader_election: &'static str,
+    /// CoinMarketCap fallback for global data (CMC_API_KEY set)
+    pub cmc_fallback: bool,
+    /// US stock indices from Finnhub (FINNHUB_API_KEY set), with the configured symbols
+    pub us_indices: bool,
+    pub stock_indices: Vec<String>,
+    pub binance_websocket: bool,
+    /// "single" or "vwap"
+    pub price_mode: &'static str,
+    /// MAX_CONCURRENT_FETCHES (5 = all data groups at once)
+    pub max_concurrent_fetches: usize,
+    pub follower_stream_consumer: bool,
+    pub shadow_mode: bool,
+}
+
+impl InitReport {
+    fn new(config: &Config, cache_system: &CacheSystemIsland) -> Self {
+        Self {
+            cache_l2: match config.cache_l2 {
+                CacheL2::Redis => "redis",
+                CacheL2::None => "memory",
+            },
+            redis_read_replica: cache_system.has_read_replica(),
+            cache_key_prefix: Some(config.cache_key_prefix.clone()).filter(|prefix| !prefix.is_empty()),
+            leader_election: match config.cache_l2 {
+                CacheL2::Redis => "redis",
+                CacheL2::None => "standalone",
+            },
+            cmc_fallback: config.cmc_api_key.is_some(),
+            us_indices: config.finnhub_api_key.is_some(),
+            stock_indices: config.stock_indices.iter().map(|(symbol, _)| symbol.clone()).collect(),
+            binance_websocket: config.binance_ws_enabled,
+            price_mode: match config.price_mode {
+                PriceMode::Single => "single",
+                PriceMode::Vwap { .. } => "vwap",
+            },
+            max_concurrent_fetches: config.max_concurrent_fetches,
+            follower_stream_consumer: config.follower_stream_consumer,
+            shadow_mode: config.shadow_mode,
+        }
+    }
+
+    /// Log the report as a single structured line
+    pub fn log(&self) {
+        info!(
+            cache_l2 = self.cache_l2,
+            redis_read_replica = self.redis_read_replica,
+            cache_key_prefix = self.cache_key_prefix.as_deref().unwrap_or(""),
+            leader_election = self.leader_election,
+            cmc_fallback = self.cmc_fallback,
+            us_indices = self.us_indices,
+            stock_indices = %self.stock_indices.join(","),
+            binance_websocket = self.binance_websocket,
+            price_mode = self.price_mode,
+            max_concurrent_fetches = self.max_concurrent_fetches,
+            follower_stream_consumer = self.follower_stream_consumer,
+            shadow_mode = self.shadow_mode,
+            "Service Islands features"
+        );
+    }
+}
+
+/// Where a broadcast dashboard came from, reported as the envelope's `source`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataOrigin {
+    /// Freshly aggregated from the external APIs (leader)
+    Api,
+    /// Read from `latest_market_data` (follower)
+    Cache,
+    /// Consumed from `market_data_stream`
+    Stream,
+}
+
+impl DataOrigin {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Api => "api",
+            Self::Cache => "cache",
+            Self::Stream => "stream",
+        }
+    }
+
+    /// `origin` inside the broadcast data: `live` when this node just fetched it,
+    /// `cache` when it was relayed from Redis (see `age_ms` for how old)
+    pub fn freshness(self) -> &'static str {
+        match self {
+            Self::Api => "live",
+            Self::Cache | Self::Stream => "cache",
+        }
+    }
+}
+
+/// Milliseconds since the dashboard's `timestamp` (0 when missing or in the future)
+fn data_age_ms(data: &serde_json::Value) -> u64 {
+    data["timestamp"]
+        .as_str()
+        .and_then(|timestamp| chrono::DateTime::parse_from_rfc3339(timestamp).ok())
+        .map(|timestamp| (chrono::Utc::now() - timestamp.with_timezone(&chrono::Utc)).num_milliseconds().max(0) as u64)
+        .unwrap_or(0)
+}
+
+/// Check a dashboard before it is cached, published or broadcast
+///
+/// Applied to what the leader (or a seeding follower) fetched and again to what
+/// followers read back from Redis: it must deserialize as `DashboardData` and have
+/// no `DashboardData::invalid_fields`.
+fn validate_published_dashboard(data: &serde_json::Value) -> Result<(), anyhow::Error> {
+    let dashboard = crate::dto::DashboardData::deserialize(data)
+        .map_err(|e| anyhow::anyhow!("not a dashboard: {}", e))?;
+    let invalid = dashboard.invalid_fields();
+    if !invalid.is_empty() {
+        anyhow::bail!("implausible values: {}", invalid.join(", "));
+    }
+    Ok(())
+}
+
+/// Whether `error` came from encoding a message as JSON or MessagePack
+fn is_serialization_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.is::<serde_json::Error>() || cause.is::<rmp_serde::encode::Error>())
+}
 
 /// WebSocket Service Islands Registry
 ///
@@ -21,6 +229,15 @@ use layer3_communication::WebSocketServiceIsland;
 /// - Layer 2: External APIs for market data fetching
 /// - Layer 3: WebSocket service
 pub struct ServiceIslands {
+    // Configuration loaded at startup
+    pub config: Config,
+
+    // Optional features enabled at startup (logged by main, reported on /health)
+    pub init_report: InitReport,
+
+    // When the islands were initialized (process uptime)
+    pub start_instant: Instant,
+
     // Layer 1: Infrastructure Islands
     pub cache_system: Arc<CacheSystemIsland>,
 
@@ -33,9 +250,51 @@ pub struct ServiceIslands {
     // Distributed Coordination: Leader Election
     pub leader_election: Arc<LeaderElectionService>,
     pub is_leader: Arc<AtomicBool>,
+    // Leadership heartbeat task, stopped before leadership is released on shutdown
+    leader_monitor: parking_lot::Mutex<Option<JoinHandle<()>>>,
+
+    // Set by `begin_shutdown` to the deadline the whole shutdown must meet
+    shutdown_tx: watch::Sender<Option<tokio::time::Instant>>,
+
+    // Cleared by `POST /admin/broadcast/pause`: the fetcher keeps the cache fresh
+    // but dashboard updates are not sent to this instance's clients
+    pub broadcasting_enabled: Arc<AtomicBool>,
+
+    // MAINTENANCE_MODE or `POST /admin/maintenance/enable`: the fetcher is suspended
+    // and new clients get the maintenance notice plus the last good dashboard
+    maintenance_mode: AtomicBool,
+
+    // Consecutive follower ticks that found no `latest_market_data` (FOLLOWER_COLD_FETCH_TICKS)
+    cold_cache_ticks: AtomicU32,
+
+    // Signature check for admin requests (ADMIN_HMAC_SECRET)
+    pub admin_verifier: Option<AdminRequestVerifier>,
 
     // WebSocket connection tracking
     pub active_ws_connections: Arc<AtomicUsize>,
+    pub max_ws_connections: usize,
+
+    // Content hash of the last dashboard broadcast (skip unchanged data)
+    last_broadcast_hash: Mutex<Option<String>>,
+
+    // Single-flight guard for `dashboard_snapshot` refreshes on a cache miss
+    snapshot_refresh: Mutex<()>,
+
+    // Optional reshaping of dashboard data right before broadcast
+    transform: Option<DashboardTransform>,
+
+    // Coalescing state for `MIN_BROADCAST_INTERVAL_MS`
+    broadcast_throttle: Mutex<BroadcastThrottle>,
+
+    // Follower position in `market_data_stream` (FOLLOWER_STREAM_CONSUMER)
+    stream_consumer: MarketDataStreamConsumer,
+}
+
+/// Last send time and the newest dashboard held back by the broadcast interval
+#[derive(Default)]
+struct BroadcastThrottle {
+    last_sent: Option<Instant>,
+    pending: Option<(serde_json::Value, DataOrigin)>,
 }
 
 impl ServiceIslands {
@@ -43,31 +302,29 @@ impl ServiceIslands {
     ///
     /// This method initializes only the necessary service islands:
     /// Layer 1 (Infrastructure/Cache), Layer 2 (External APIs), Layer 3 (Communication)
-    pub async fn initialize() -> Result<Self, anyhow::Error> {
+    pub async fn initialize(config: Config) -> Result<Self, anyhow::Error> {
+        let start_instant = Instant::now();
         println!("🏝️ Initializing WebSocket Service Islands...");
 
         // Initialize Layer 1: Infrastructure (Cache System only)
         println!("🏗️ Initializing Layer 1: Cache System Island...");
-        let cache_system = Arc::new(CacheSystemIsland::new().await?);
+        let cache_system = Arc::new(
+            CacheSystemIsland::with_l2(config.cache_l2, &config.redis_url, config.redis_replica_url.as_deref(), &config.cache_key_prefix).await?
+                .with_realtime_ttl(Duration::from_secs(config.realtime_cache_ttl_seconds))
+        );
         println!("✅ Cache System Island initialized!");
 
         // Initialize Leader Election Service
         println!("🎖️ Initializing Leader Election Service...");
-        let redis_url = std::env::var("REDIS_URL")
-            .unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
-
-        // Generate unique node ID from Railway or UUID
-        let node_id = std::env::var("RAILWAY_REPLICA_ID")
-            .or_else(|_| std::env::var("RAILWAY_INSTANCE_ID"))
-            .unwrap_or_else(|_| format!("ws-{}", uuid::Uuid::new_v4()));
-
-        let leader_election = Arc::new(
-            LeaderElectionService::new(&redis_url, node_id).await?
-        );
+        let leader_election = Arc::new(match config.cache_l2 {
+            CacheL2::Redis => LeaderElectionService::new(&config.redis_url, config.node_id.clone()).await?
+                .with_key_prefix(&config.cache_key_prefix),
+            CacheL2::None => LeaderElectionService::standalone(config.node_id.clone())?,
+        });
         let is_leader = Arc::new(AtomicBool::new(false));
 
         // Spawn background leadership monitoring task
-        tokio::spawn({
+        let leader_monitor = tokio::spawn({
             let leader_election = Arc::clone(&leader_election);
             let is_leader = Arc::clone(&is_leader);
             async move {
@@ -79,9 +336,9 @@ impl ServiceIslands {
 
         // Initialize Layer 2: External Services (depends on Layer 1 - Cache System)
         println!("🌐 Initializing Layer 2: External APIs Island with Cache...");
-        let taapi_secret = std::env::var("TAAPI_SECRET").unwrap_or_else(|_| "default_secret".to_string());
-        let cmc_api_key = std::env::var("CMC_API_KEY").ok();
-        let finnhub_api_key = std::env::var("FINNHUB_API_KEY").ok();
+        let taapi_secret = config.taapi_secret.clone();
+        let cmc_api_key = config.cmc_api_key.clone();
+        let finnhub_api_key = config.finnhub_api_key.clone();
 
         if cmc_api_key.is_some() {
             println!("🔑 CoinMarketCap API key found - enabling fallback support");
@@ -99,8 +356,16 @@ impl ServiceIslands {
             taapi_secret,
             cmc_api_key,
             finnhub_api_key,
-            Some(Arc::clone(&cache_system))
+            Some(Arc::clone(&cache_system)),
+            config.category_intervals(),
+            config.binance_min_symbols,
+            config.price_mode,
         ).await?);
+        external_apis.set_adaptive_source_ordering(config.adaptive_source_ordering);
+        external_apis.set_required_global_fields(&config.required_global_fields);
+        external_apis.set_stock_indices(&config.stock_indices);
+        external_apis.set_max_concurrent_fetches(config.max_concurrent_fetches);
+        external_apis.set_max_retries_per_tick(config.max_retries_per_tick);
         println!("✅ External APIs Island initialized!");
 
         // Initialize Layer 3: Communication (WebSocket)
@@ -110,10 +375,16 @@ impl ServiceIslands {
         let websocket_service = Arc::new(
             WebSocketServiceIsland::with_external_apis_and_cache(
                 Arc::clone(&external_apis),
-                Arc::clone(&cache_system)
+                Arc::clone(&cache_system),
+                &config,
             ).await?
         );
         println!("✅ WebSocket Service Island initialized!");
+        println!("🔒 Max WebSocket connections: {}", config.max_ws_connections);
+
+        if config.shadow_mode {
+            println!("🕶️ SHADOW_MODE enabled - broadcasts and stream publishes are logged, not sent");
+        }
 
         println!("✅ WebSocket Service Islands Architecture initialized!");
         println!("📊 Architecture Status:");
@@ -121,16 +392,42 @@ impl ServiceIslands {
         println!("  🌐 Layer 2 - External Services: External APIs");
         println!("  📡 Layer 3 - Communication: WebSocket");
 
+        let init_report = InitReport::new(&config, &cache_system);
+
         Ok(Self {
+            start_instant,
+            init_report,
+            max_ws_connections: config.max_ws_connections,
             cache_system,
             external_apis,
             websocket_service,
             leader_election,
             is_leader,
+            leader_monitor: parking_lot::Mutex::new(Some(leader_monitor)),
+            shutdown_tx: watch::channel(None).0,
+            broadcasting_enabled: Arc::new(AtomicBool::new(true)),
+            maintenance_mode: AtomicBool::new(config.maintenance_mode),
+            cold_cache_ticks: AtomicU32::new(0),
+            admin_verifier: config.admin_hmac_secret.clone().map(AdminRequestVerifier::new),
             active_ws_connections: Arc::new(AtomicUsize::new(0)),
+            last_broadcast_hash: Mutex::new(None),
+            snapshot_refresh: Mutex::new(()),
+            broadcast_throttle: Mutex::new(BroadcastThrottle::default()),
+            stream_consumer: MarketDataStreamConsumer::new(),
+            transform: transforms::from_config(&config),
+            config,
         })
     }
 
+    /// Replace the broadcast transform (including any configured built-ins)
+    ///
+    /// Applied to the dashboard `data` in `broadcast_to_websocket_clients`, after the
+    /// unchanged-content check and before serialization.
+    pub fn with_transform(mut self, transform: DashboardTransform) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
     /// Fetch market data from External APIs and publish to Redis Streams
     pub async fn fetch_and_publish_market_data(&self, force_refresh: bool) -> Result<serde_json::Value, anyhow::Error> {
         // Fetch data directly from External APIs
@@ -138,10 +435,20 @@ impl ServiceIslands {
             .fetch_dashboard_summary_v2(force_refresh)
             .await?;
 
+        self.publish_market_data(data).await
+    }
+
+    /// Cache and stream a freshly fetched dashboard, returning it for broadcast
+    ///
+    /// Data failing `validate_published_dashboard` is rejected before it reaches the
+    /// cache or the stream, so neither this instance nor any follower broadcasts it.
+    async fn publish_market_data(&self, data: serde_json::Value) -> Result<serde_json::Value, anyhow::Error> {
+        validate_published_dashboard(&data)
+            .map_err(|e| anyhow::anyhow!("fetched market data rejected: {}", e))?;
+
         // Store in cache for main service to read
         if let Err(e) = self.cache_system.cache_manager()
-            .set_with_strategy("latest_market_data", data.clone(),
-                layer1_infrastructure::cache_system_island::cache_manager::realtime_strategy())
+            .set_with_strategy("latest_market_data", data.clone(), self.cache_system.realtime_strategy())
             .await
         {
             eprintln!("⚠️ Failed to cache market data: {}", e);
@@ -155,68 +462,702 @@ impl ServiceIslands {
         Ok(data)
     }
 
+    /// One tick of the periodic market data fetcher
+    ///
+    /// The leader fetches from the APIs, caches and broadcasts; followers broadcast what
+    /// the leader published (from `market_data_stream` with `FOLLOWER_STREAM_CONSUMER`,
+    /// otherwise from `latest_market_data`), skipping data that fails
+    /// `validate_published_dashboard`. A cache-polling follower that finds the cache
+    /// empty for `FOLLOWER_COLD_FETCH_TICKS` ticks fetches once itself (see `seed_cold_cache`).
+    /// Failures are logged, never returned, so the caller's loop keeps ticking.
+    pub async fn market_data_tick(self: &Arc<Self>) {
+        self.sync_shared_flags().await;
+
+        if self.maintenance_mode() {
+            // No API calls at all, including the upstream price stream
+            self.sync_upstream_streams(false);
+            debug!("Maintenance mode, market data fetch skipped");
+            return;
+        }
+
+        // Check if this instance is the leader (confirmed in Redis unless disabled)
+        let is_leader = self.confirm_leadership().await;
+        self.sync_upstream_streams(is_leader);
+        let paused = !self.broadcasting_enabled();
+
+        if is_leader {
+            // Stream entries published while leading aren't replayed after stepping down
+            self.stream_consumer.reset();
+
+            // LEADER MODE: Fetch from API and cache
+            info!("🎖️ [LEADER] Fetching market data from APIs...");
+
+            match self.fetch_and_publish_market_data(true).await {
+                Ok(data) => {
+                    info!("✅ [LEADER] Market data fetched successfully from APIs");
+                    if paused {
+                        info!("⏸️ [LEADER] Broadcasting paused, cache refreshed without notifying clients");
+                        return;
+                    }
+
+                    // Broadcast to all WebSocket clients
+                    match self.broadcast_to_websocket_clients(data, DataOrigin::Api).await {
+                        Ok(true) => info!("📡 [LEADER] Broadcasted to {} WebSocket clients",
+                                          self.active_connections()),
+                        Ok(false) => info!("⏭️ [LEADER] Market data unchanged or coalesced, broadcast skipped"),
+                        Err(e) => error!("❌ [LEADER] Failed to broadcast to WebSocket clients: {}", e),
+                    }
+                }
+                Err(e) => {
+                    error!("❌ [LEADER] Failed to fetch market data: {}", e);
+                }
+            }
+        } else if self.config.follower_stream_consumer {
+            // FOLLOWER MODE: Consume the leader's stream, replaying anything missed
+            match self.read_stream_updates().await {
+                Ok(updates) if updates.is_empty() => {
+                    info!("⏭️ [FOLLOWER] No new entries in market_data_stream");
+                }
+                Ok(updates) if paused => {
+                    // Still read so the stream position stays current and resume doesn't replay the pause
+                    info!("⏸️ [FOLLOWER] Broadcasting paused, skipped {} market_data_stream entries", updates.len());
+                }
+                Ok(updates) => {
+                    let latest_btc = updates.last().and_then(|entry| entry.btc_price);
+                    info!("✅ [FOLLOWER] {} new market_data_stream entries (latest BTC: {:?})", updates.len(), latest_btc);
+                    for entry in updates {
+                        if let Err(e) = validate_published_dashboard(&entry.data) {
+                            warn!("⚠️ [FOLLOWER] Skipping invalid market_data_stream entry ({:?}): {}", entry.timestamp, e);
+                            continue;
+                        }
+                        if let Err(e) = self.broadcast_to_websocket_clients(entry.data, DataOrigin::Stream).await {
+                            error!("❌ [FOLLOWER] Failed to broadcast to WebSocket clients: {}", e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    error!("❌ [FOLLOWER] Failed to read market_data_stream (will resume from the last entry): {}", e);
+                }
+            }
+        } else if paused {
+            info!("⏸️ [FOLLOWER] Broadcasting paused, not reading cached market data");
+        } else {
+            // FOLLOWER MODE: Read from cache only
+            info!("👥 [FOLLOWER] Reading market data from cache...");
+
+            // Try to get latest data from cache (read replica when configured)
+            match self.cache_system.read_cache_manager()
+                .get("latest_market_data")
+                .await
+            {
+                Ok(Some(data)) => {
+                    self.cold_cache_ticks.store(0, Ordering::Relaxed);
+                    if let Err(e) = validate_published_dashboard(&data) {
+                        warn!("⚠️ [FOLLOWER] Cached market data failed validation, broadcast skipped: {}", e);
+                        return;
+                    }
+                    info!("✅ [FOLLOWER] Market data loaded from cache");
+
+                    // Broadcast to all WebSocket clients
+                    match self.broadcast_to_websocket_clients(data, DataOrigin::Cache).await {
+                        Ok(true) => info!("📡 [FOLLOWER] Broadcasted cached data to {} WebSocket clients",
+                                          self.active_connections()),
+                        Ok(false) => info!("⏭️ [FOLLOWER] Market data unchanged or coalesced, broadcast skipped"),
+                        Err(e) => error!("❌ [FOLLOWER] Failed to broadcast to WebSocket clients: {}", e),
+                    }
+                }
+                Ok(None) if self.cold_cache_threshold_reached() => {
+                    self.seed_cold_cache().await;
+                }
+                Ok(None) => {
+                    warn!("⚠️ [FOLLOWER] No cached data available yet (leader may still be fetching)");
+                }
+                Err(e) => {
+                    error!("❌ [FOLLOWER] Failed to read from cache: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Count a follower tick that found the cache empty; true once that has happened
+    /// `FOLLOWER_COLD_FETCH_TICKS` times in a row (the count then starts over)
+    fn cold_cache_threshold_reached(&self) -> bool {
+        let threshold = self.config.follower_cold_fetch_ticks;
+        let cold_ticks = self.cold_cache_ticks.fetch_add(1, Ordering::Relaxed) + 1;
+        if threshold == 0 || cold_ticks < threshold {
+            return false;
+        }
+        self.cold_cache_ticks.store(0, Ordering::Relaxed);
+        true
+    }
+
+    /// Fetch from the APIs as a follower to seed a cache the leader hasn't filled yet
+    ///
+    /// Only the follower that wins the cluster-wide claim fetches; the claim lasts one
+    /// threshold window, so a missing leader costs at most one fetch per window. Once
+    /// the leader writes the cache, hits reset the count and this stops.
+    async fn seed_cold_cache(self: &Arc<Self>) {
+        let window = Duration::from_secs(
+            self.config.fetch_interval_seconds * u64::from(self.config.follower_cold_fetch_ticks),
+        );
+        match self.leader_election.try_claim_cold_fetch(window).await {
+            Ok(true) => {}
+            Ok(false) => {
+                info!("👥 [FOLLOWER] Cache still cold, another follower is seeding it");
+                return;
+            }
+            Err(e) => {
+                warn!("⚠️ [FOLLOWER] Cache still cold, could not claim the seeding fetch: {}", e);
+                return;
+            }
+        }
+
+        warn!("🌱 [FOLLOWER] Cache cold for {} ticks, fetching from the APIs to seed it",
+              self.config.follower_cold_fetch_ticks);
+        match self.fetch_and_publish_market_data(false).await {
+            Ok(data) => match self.broadcast_to_websocket_clients(data, DataOrigin::Api).await {
+                Ok(true) => info!("📡 [FOLLOWER] Broadcasted seeded data to {} WebSocket clients",
+                                  self.active_connections()),
+                Ok(false) => info!("⏭️ [FOLLOWER] Seeded data unchanged or coalesced, broadcast skipped"),
+                Err(e) => error!("❌ [FOLLOWER] Failed to broadcast seeded data: {}", e),
+            },
+            Err(e) => error!("❌ [FOLLOWER] Failed to seed the cold cache: {}", e),
+        }
+    }
+
+    /// Latest dashboard for `GET /dashboard`
+    ///
+    /// Served from `latest_market_data`. On a miss, one caller refreshes while every
+    /// concurrent caller waits on the same guard and then re-reads the cache, so a
+    /// page-load spike triggers at most one aggregation. Only the leader fetches from
+    /// the APIs; followers return `None` until the leader has populated the cache.
+    pub async fn dashboard_snapshot(&self) -> Result<Option<serde_json::Value>, anyhow::Error> {
+        if let Some(data) = self.cache_system.read_cache_manager().get("latest_market_data").await? {
+            return Ok(Some(data));
+        }
+
+        let _refresh = self.snapshot_refresh.lock().await;
+
+        // Whoever held the guard before us may already have filled the cache. Check the
+        // primary: a lagging read replica would miss that write and every waiter would refetch
+        if let Some(data) = self.cache_system.cache_manager().get("latest_market_data").await? {
+            debug!("Dashboard snapshot served from a coalesced refresh");
+            return Ok(Some(data));
+        }
+
+        if !self.is_leader.load(Ordering::Relaxed) || self.maintenance_mode() {
+            return Ok(None);
+        }
+
+        info!("🔄 Dashboard snapshot cache miss, refreshing");
+        self.fetch_and_publish_market_data(false).await.map(Some)
+    }
+
+    /// Start shutting down: background tasks (fetcher, stream consumer) stop at their next check
+    ///
+    /// Returns the deadline (`SHUTDOWN_TIMEOUT_SECONDS` from the first call) by which
+    /// the rest of the shutdown must finish. Later calls return the same deadline.
+    pub fn begin_shutdown(&self) -> tokio::time::Instant {
+        let budget = Duration::from_secs(self.config.shutdown_timeout_seconds);
+        let mut deadline = tokio::time::Instant::now() + budget;
+        self.shutdown_tx.send_if_modified(|current| match current {
+            Some(existing) => {
+                deadline = *existing;
+                false
+            }
+            None => {
+                *current = Some(deadline);
+                true
+            }
+        });
+        deadline
+    }
+
+    /// When the shutdown stages before releasing leadership must give up
+    ///
+    /// `LEADERSHIP_RELEASE_RESERVE` before the shutdown `deadline` (at most half of
+    /// `SHUTDOWN_TIMEOUT_SECONDS`), so a slow fetcher or lingering client can't use up
+    /// the time the lock release needs before the force exit.
+    pub fn shutdown_drain_deadline(&self, deadline: tokio::time::Instant) -> tokio::time::Instant {
+        let budget = Duration::from_secs(self.config.shutdown_timeout_seconds);
+        deadline - LEADERSHIP_RELEASE_RESERVE.min(budget / 2)
+    }
+
+    /// Receiver that changes once `begin_shutdown` has been called
+    pub fn subscribe_shutdown(&self) -> watch::Receiver<Option<tokio::time::Instant>> {
+        self.shutdown_tx.subscribe()
+    }
+
+    /// Stop renewing (or acquiring) leadership, so a released lock stays released
+    pub fn stop_leader_monitor(&self) {
+        if let Some(monitor) = self.leader_monitor.lock().take() {
+            monitor.abort();
+        }
+    }
+
+    /// Stop the leadership monitor, then release the leader lock
+    ///
+    /// Runs whatever the `is_leader` flag says: the flag can be stale (e.g. cleared by
+    /// a late heartbeat while the lock is still ours), and the check-and-delete in
+    /// `LeaderElectionService::release_leadership` only removes a lock this node owns.
+    /// Calling it again is harmless.
+    pub async fn release_leadership(&self) -> Result<(), anyhow::Error> {
+        self.stop_leader_monitor();
+        self.is_leader.store(false, Ordering::Relaxed);
+        self.leader_election.release_leadership().await
+    }
+
+    /// Whether this node may run a leader-only fetch right now
+    ///
+    /// The `is_leader` flag is only refreshed once per heartbeat, so after a pause it
+    /// can claim leadership the lock no longer backs. With `verify_leadership_before_fetch`
+    /// the lock is checked in Redis first; a lost lock clears the flag, and a failed
+    /// check skips this leader fetch (the monitor decides on the next heartbeat).
+    pub async fn confirm_leadership(&self) -> bool {
+        if !self.is_leader.load(Ordering::Relaxed) {
+            return false;
+        }
+        if !self.config.verify_leadership_before_fetch {
+            return true;
+        }
+
+        match self.leader_election.is_leader().await {
+            Ok(true) => true,
+            Ok(false) => {
+                warn!("⚠️ Leader flag was stale (lock held by another node), continuing as follower");
+                self.is_leader.store(false, Ordering::Relaxed);
+                false
+            }
+            Err(e) => {
+                warn!("⚠️ Could not verify leadership, skipping leader fetch this tick: {}", e);
+                false
+            }
+        }
+    }
+
     /// Publish data to Redis Stream
+    ///
+    /// In shadow mode nothing is published (the stream is not trimmed either).
     async fn publish_to_redis_stream(&self, data: &serde_json::Value) -> Result<(), anyhow::Error> {
-        // Convert JSON to string for storage in stream
-        let data_str = serde_json::to_string(data)?;
+        if self.config.shadow_mode {
+            info!("🕶️ [SHADOW] Would publish market data to {} (MAXLEN {})", MARKET_DATA_STREAM, MARKET_DATA_STREAM_MAXLEN);
+            return Ok(());
+        }
 
-        // Create stream fields
-        let fields = vec![("data".to_string(), data_str)];
+        // Summary fields (btc_price, timestamp, partial_failure) plus the full JSON as `data`
+        let fields = match MarketDataStreamEntry::from_dashboard(data).to_fields() {
+            Ok(fields) => fields,
+            Err(e) => {
+                self.skip_unserializable("market_data_stream publish", &e);
+                return Ok(());
+            }
+        };
 
         // Publish to market_data_stream using cache manager's stream functionality
-        // Limit stream to 1000 entries (MAXLEN)
+        // Limit stream to MARKET_DATA_STREAM_MAXLEN entries
         self.cache_system
             .cache_manager()
-            .publish_to_stream("market_data_stream", fields, Some(1000))
+            .publish_to_stream(MARKET_DATA_STREAM, fields, Some(MARKET_DATA_STREAM_MAXLEN))
             .await?;
 
         Ok(())
     }
 
+    /// Dashboards the leader published to `market_data_stream` since the last call
+    ///
+    /// Oldest first. After a failed read (e.g. Redis blip) the next call replays
+    /// everything published since the last processed entry.
+    pub async fn read_stream_updates(&self) -> Result<Vec<MarketDataStreamEntry>, anyhow::Error> {
+        self.stream_consumer.read_new(self.cache_system.read_cache_manager()).await
+    }
+
+    /// Market-wide statistics for `GET /dashboard/statistics`
+    ///
+    /// Derived from `latest_market_data`, else the last dashboard this instance broadcast;
+    /// never aggregates. `None` until either exists.
+    pub async fn market_statistics(&self) -> Option<crate::dto::MarketStatistics> {
+        let cached = match self.cache_system.read_cache_manager().get("latest_market_data").await {
+            Ok(cached) => cached.and_then(|data| crate::dto::DashboardData::deserialize(&data).ok()),
+            Err(e) => {
+                warn!("Failed to read the cached dashboard for statistics: {}", e);
+                None
+            }
+        };
+        cached
+            .or_else(|| self.websocket_service.history.recent_history(1).pop())
+            .map(|dashboard| dashboard.statistics())
+    }
+
+    /// The dashboard `market_data_stream` holds under entry `id`, for `GET /dashboard/history/:id`
+    ///
+    /// `Ok(None)` when the entry doesn't exist (e.g. it is older than the stream's retention);
+    /// an error for malformed ids or a failed read.
+    pub async fn dashboard_at(&self, id: &str) -> Result<Option<serde_json::Value>, anyhow::Error> {
+        let entry = stream_consumer::read_entry(self.cache_system.read_cache_manager(), id).await?;
+        Ok(entry.map(|entry| entry.data))
+    }
+
     /// Broadcast data to all connected WebSocket clients
-    pub async fn broadcast_to_websocket_clients(&self, data: serde_json::Value) -> Result<(), anyhow::Error> {
-        // Wrap data in WebSocket message format with type field
+    ///
+    /// Returns `Ok(false)` without sending when the market content is unchanged since
+    /// the last broadcast (same `content_hash`, computed here if the data lacks one),
+    /// or in shadow mode, where the broadcast is only logged.
+    ///
+    /// Sent as `ServerMessage::DashboardUpdate` with `source` (see `DataOrigin`) in the
+    /// payload; degraded data is flagged by `partialFailure` and `criticalFailure` in `data`.
+    ///
+    /// With `MIN_BROADCAST_INTERVAL_MS` set, data arriving within the interval of the
+    /// last broadcast is held back (replacing anything already held) and sent when
+    /// the window elapses; this call then returns `Ok(false)`.
+    pub async fn broadcast_to_websocket_clients(self: &Arc<Self>, data: serde_json::Value, origin: DataOrigin) -> Result<bool, anyhow::Error> {
+        if !self.broadcasting_enabled() {
+            debug!("Broadcasting paused, dashboard update not sent");
+            return Ok(false);
+        }
+
+        let min_interval = Duration::from_millis(self.config.min_broadcast_interval_ms);
+        if min_interval.is_zero() {
+            return self.send_dashboard_broadcast(data, origin).await;
+        }
+
+        let mut throttle = self.broadcast_throttle.lock().await;
+        let wait = throttle.last_sent
+            .map_or(Duration::ZERO, |last_sent| min_interval.saturating_sub(last_sent.elapsed()));
+        if !wait.is_zero() {
+            if throttle.pending.replace((data, origin)).is_none() {
+                let islands = Arc::clone(self);
+                tokio::spawn(async move {
+                    tokio::time::sleep(wait).await;
+                    islands.flush_throttled_broadcast().await;
+                });
+            }
+            debug!("Coalescing dashboard broadcast, next window in {} ms", wait.as_millis());
+            return Ok(false);
+        }
+
+        // Newer data supersedes anything still waiting for its window
+        throttle.pending = None;
+        let sent = self.send_dashboard_broadcast(data, origin).await?;
+        if sent {
+            throttle.last_sent = Some(Instant::now());
+        }
+        Ok(sent)
+    }
+
+    /// Send the dashboard held back by `MIN_BROADCAST_INTERVAL_MS`, if still pending
+    async fn flush_throttled_broadcast(&self) {
+        let mut throttle = self.broadcast_throttle.lock().await;
+        let Some((data, origin)) = throttle.pending.take() else {
+            return;
+        };
+        if !self.broadcasting_enabled() {
+            return;
+        }
+
+        match self.send_dashboard_broadcast(data, origin).await {
+            Ok(true) => throttle.last_sent = Some(Instant::now()),
+            Ok(false) => {}
+            Err(e) => warn!("Failed to send coalesced dashboard broadcast: {}", e),
+        }
+    }
+
+    /// Dedupe, transform and send one dashboard broadcast
+    ///
+    /// Data missing critical groups (`critical_failure`) is dropped unless
+    /// `BROADCAST_ON_CRITICAL_FAILURE` is set, so clients keep the last good dashboard.
+    async fn send_dashboard_broadcast(&self, data: serde_json::Value, origin: DataOrigin) -> Result<bool, anyhow::Error> {
+        let critical_failure = data["critical_failure"].as_bool().unwrap_or(false);
+        if critical_failure && !self.config.broadcast_on_critical_failure {
+            warn!("Skipping dashboard broadcast: critical data (crypto prices or global) missing");
+            return Ok(false);
+        }
+
+        if self.config.shadow_mode {
+            info!("🕶️ [SHADOW] Would broadcast dashboard update to {} WebSocket clients",
+                  self.active_connections());
+            return Ok(false);
+        }
+
+        let content_hash = data["content_hash"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| dashboard_content_hash(&data));
+        // Held until the send, so the hash is only recorded once the update was really queued
+        let mut last_hash = self.last_broadcast_hash.lock().await;
+        if last_hash.as_deref() == Some(content_hash.as_str()) {
+            debug!("Skipping dashboard broadcast: content unchanged ({})", content_hash);
+            return Ok(false);
+        }
+
+        // Freshness travels inside the data, so transforms, history and clients all see it
+        let mut data = data;
+        let age_ms = data_age_ms(&data);
+        if let Some(fields) = data.as_object_mut() {
+            fields.insert("origin".to_string(), origin.freshness().into());
+            fields.insert("age_ms".to_string(), age_ms.into());
+        }
+
+        let data = match &self.transform {
+            Some(transform) => transform(data),
+            None => data,
+        };
+
+        // Typed copy for delta-mode diffing (None falls back to full snapshots) and history
+        let typed_data = crate::dto::DashboardData::deserialize(&data).ok();
+        if let Some(typed) = &typed_data {
+            self.websocket_service.history.push(typed.clone());
+        }
+
+        // `ServerMessage::DashboardUpdate` envelope around exactly the fields the transform left,
+        // camelCase where they are `DashboardData` fields; untyped data keeps its own keys.
+        // OUTPUT_CASE (or a connection's own `case`) is applied when it is prepared
+        let data = match &typed_data {
+            Some(typed) => match serde_json::to_value(typed) {
+                Ok(typed) => transforms::camel_case_fields(data, &typed),
+                Err(e) => {
+                    self.skip_unserializable("dashboard broadcast", &e.into());
+                    return Ok(false);
+                }
+            },
+            None => data,
+        };
         let ws_message = serde_json::json!({
-            "type": "dashboard_update",
-            "data": data,
-            "timestamp": chrono::Utc::now().to_rfc3339(),
-            "source": "external_apis"
+            "type": "DashboardUpdate",
+            "payload": {
+                "data": data,
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+                "source": origin.as_str(),
+            },
         });
 
-        let data_str = serde_json::to_string(&ws_message)?;
-        self.websocket_service.broadcast_service.broadcast(data_str).await;
-        Ok(())
+        let receivers = match self.websocket_service.broadcast_service.broadcast_dashboard(ws_message, typed_data).await {
+            Ok(receivers) => receivers,
+            Err(e) if is_serialization_error(&e) => {
+                self.skip_unserializable("dashboard broadcast", &e);
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        };
+        *last_hash = Some(content_hash);
+        self.websocket_service.metrics.record_broadcast(receivers);
+        debug!(receivers, "Dashboard broadcast queued");
+        Ok(true)
+    }
+
+    /// Log a broadcast or publish dropped because its data couldn't be encoded
+    ///
+    /// Counted in `serialization_errors` on `/metrics`; the next update is tried as usual.
+    fn skip_unserializable(&self, what: &str, error: &anyhow::Error) {
+        self.websocket_service.metrics.record_serialization_error();
+        error!("❌ Skipping {}: serialization failed: {:#}", what, error);
+    }
+
+    /// Broadcast an operator notice to every client connected to this instance
+    ///
+    /// Returns the number of connections it was queued for (0 in shadow mode, where it's only logged).
+    pub async fn broadcast_notice(&self, notice: NoticePayload) -> Result<usize, anyhow::Error> {
+        if self.config.shadow_mode {
+            info!("🕶️ [SHADOW] Would broadcast {:?} notice to {} WebSocket clients: {}",
+                  notice.level, self.active_connections(), notice.message);
+            return Ok(0);
+        }
+
+        info!("📢 Broadcasting {:?} notice: {}", notice.level, notice.message);
+        self.websocket_service.broadcast_service
+            .broadcast_all(&ServerMessage::Notice(notice))
+            .await
+    }
+
+    /// Whether dashboard updates are currently sent to clients
+    pub fn broadcasting_enabled(&self) -> bool {
+        self.broadcasting_enabled.load(Ordering::Relaxed)
+    }
+
+    /// Pause or resume dashboard broadcasts for the whole cluster
+    ///
+    /// Returns `false` when broadcasting was already in the requested state. Applied here
+    /// right away; the switch is shared through Redis like maintenance mode, so other
+    /// instances follow at their next tick. Standalone only this instance is affected.
+    pub async fn set_broadcasting_enabled(&self, enabled: bool) -> Result<bool, anyhow::Error> {
+        let changed_here = self.switch_broadcasting(enabled);
+        let changed = self.leader_election.set_shared_flag(BROADCASTING_PAUSED_FLAG, !enabled).await?;
+        Ok(changed.unwrap_or(changed_here))
+    }
+
+    /// Flip this instance's broadcasting switch; `false` when already in that state
+    fn switch_broadcasting(&self, enabled: bool) -> bool {
+        let changed = self.broadcasting_enabled.swap(enabled, Ordering::Relaxed) != enabled;
+        if changed {
+            if enabled {
+                info!("▶️ Dashboard broadcasting resumed");
+            } else {
+                warn!("⏸️ Dashboard broadcasting paused (cache refresh continues)");
+            }
+        }
+        changed
+    }
+
+    /// Switch a data group on or off for the whole cluster
+    ///
+    /// Only the leader aggregates, and the admin request may reach any instance, so the
+    /// switch is stored in Redis and every node applies it on its next tick (see
+    /// `sync_shared_flags`); this instance applies it right away. Returns whether the
+    /// state changed. Unknown groups and Redis failures are errors.
+    pub async fn set_source_enabled(&self, group: &str, enabled: bool) -> Result<bool, anyhow::Error> {
+        let changed_here = self.external_apis.set_source_enabled(group, enabled)?;
+        let changed = self.leader_election.set_shared_flag(&source_disabled_flag(group), !enabled).await?;
+        Ok(changed.unwrap_or(changed_here))
+    }
+
+    /// Apply admin switches made through any instance (disabled data sources, maintenance
+    /// mode, paused broadcasting) to this one
+    ///
+    /// Runs every fetcher tick on leaders and followers alike, so whichever node leads
+    /// next aggregates with the cluster's switches. On a failed read the local switches stay.
+    /// Entering maintenance this way sends the default `MAINTENANCE_MESSAGE` notice to
+    /// this instance's clients.
+    async fn sync_shared_flags(&self) {
+        let flags = match self.leader_election.shared_flags().await {
+            Ok(Some(flags)) => flags,
+            Ok(None) => return,
+            Err(e) => {
+                warn!("⚠️ Failed to read shared admin flags, keeping this instance's switches: {}", e);
+                return;
+            }
+        };
+
+        for group in DATA_GROUPS {
+            let enabled = !flags.contains(&source_disabled_flag(group));
+            if self.external_apis.set_source_enabled(group, enabled).unwrap_or(false) {
+                warn!(source = group, enabled, "Data source switched by another instance");
+            }
+        }
+
+        let maintenance = flags.contains(MAINTENANCE_FLAG);
+        if self.switch_maintenance_mode(maintenance) {
+            warn!(maintenance, "Maintenance mode switched by another instance");
+            if maintenance {
+                if let Err(e) = self.broadcast_notice(self.maintenance_notice()).await {
+                    warn!("⚠️ Failed to send maintenance start notice: {}", e);
+                }
+            }
+        }
+
+        let broadcasting = !flags.contains(BROADCASTING_PAUSED_FLAG);
+        if self.switch_broadcasting(broadcasting) {
+            warn!(broadcasting, "Dashboard broadcasting switched by another instance");
+        }
+    }
+
+    /// Whether maintenance mode is on (fetching suspended)
+    pub fn maintenance_mode(&self) -> bool {
+        self.maintenance_mode.load(Ordering::Relaxed)
+    }
+
+    /// Enter or leave maintenance mode; returns `false` when already in that state
+    ///
+    /// Takes effect at the next fetcher tick. The switch is shared through Redis, so other
+    /// instances follow at their next tick; standalone only this instance is affected.
+    pub async fn set_maintenance_mode(&self, enabled: bool) -> Result<bool, anyhow::Error> {
+        let changed_here = self.switch_maintenance_mode(enabled);
+        let changed = self.leader_election.set_shared_flag(MAINTENANCE_FLAG, enabled).await?;
+        Ok(changed.unwrap_or(changed_here))
+    }
+
+    /// Flip this instance's maintenance switch; `false` when already in that state
+    fn switch_maintenance_mode(&self, enabled: bool) -> bool {
+        let changed = self.maintenance_mode.swap(enabled, Ordering::Relaxed) != enabled;
+        if changed {
+            if enabled {
+                warn!("🚧 Maintenance mode on, market data fetching suspended");
+            } else {
+                info!("✅ Maintenance mode off, market data fetching resumes");
+            }
+        }
+        changed
+    }
+
+    /// `MAINTENANCE_MESSAGE` as a warning `Notice`
+    pub fn maintenance_notice(&self) -> NoticePayload {
+        NoticePayload {
+            level: NoticeLevel::Warning,
+            message: self.config.maintenance_message.clone(),
+            timestamp: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    /// What a client connecting during maintenance is sent
+    ///
+    /// The maintenance notice, then the last good dashboard labeled `origin: cache` with
+    /// its age. Never fetches. `latest_market_data` expires with its short realtime TTL
+    /// during a long maintenance, so the newest `market_data_stream` entry (kept until
+    /// trimmed) comes next, and this process's history last.
+    pub async fn maintenance_greeting(&self) -> Vec<ServerMessage> {
+        let mut messages = vec![ServerMessage::Notice(self.maintenance_notice())];
+
+        let cache_manager = self.cache_system.read_cache_manager();
+        let cached = match cache_manager.get("latest_market_data").await {
+            Ok(cached) => cached,
+            Err(e) => {
+                warn!("Failed to read the last dashboard for a maintenance greeting: {}", e);
+                None
+            }
+        };
+        let cached = match cached {
+            Some(data) => Some(data),
+            None => match stream_consumer::read_latest_entry(cache_manager).await {
+                Ok(entry) => entry.map(|entry| entry.data),
+                Err(e) => {
+                    warn!("Failed to read market_data_stream for a maintenance greeting: {:#}", e);
+                    None
+                }
+            },
+        };
+        let last_good = cached
+            .and_then(|data| crate::dto::DashboardData::deserialize(&data).ok())
+            .or_else(|| self.websocket_service.history.recent_history(1).pop());
+        if let Some(mut data) = last_good {
+            data.origin = DataOrigin::Cache.freshness().to_string();
+            data.age_ms = data_age_ms(&serde_json::json!({ "timestamp": data.timestamp }));
+            messages.push(ServerMessage::DashboardUpdate(DashboardUpdatePayload::new(data, DataOrigin::Cache.as_str())));
+        }
+        messages
     }
 
     /// Perform health check on all Service Islands with logging
     pub async fn health_check(&self) -> bool {
-        let (is_healthy, details) = self.health_check_detailed().await;
+        let report = self.health_check_detailed().await;
 
         // Log issues if health check fails
-        if !is_healthy {
-            tracing::warn!("Service Islands health check failed: {:?}", details);
+        if !report.healthy {
+            warn!("Service Islands health check failed: {:?}", report);
         }
 
-        is_healthy
+        report.healthy
     }
 
-    /// Detailed health check that returns status and details
+    /// Detailed health check that returns status and per-component details
     /// Core services (cache, websocket) must be healthy
     /// External APIs being down won't fail the health check (degraded mode)
-    pub async fn health_check_detailed(&self) -> (bool, serde_json::Value) {
-        use tracing::warn;
-
+    pub async fn health_check_detailed(&self) -> HealthReport {
         println!("🔍 Performing WebSocket Service Islands health check...");
 
-        let cache_system_healthy = self.cache_system.health_check().await;
-        let external_apis_healthy = self.external_apis.health_check().await.unwrap_or(false);
-        let websocket_service_healthy = self.websocket_service.health_check().await.is_ok();
+        let cache_system = ComponentHealth::from_result(self.cache_system.health_check().await);
+        let external_apis = ComponentHealth::from_result(self.external_apis.health_check().await);
+        let websocket_service = ComponentHealth::from_result(self.websocket_service.health_check().await);
+        let circuit_breakers = self.external_apis.circuit_breaker_status().await;
+        let open_circuits: Vec<&str> = circuit_breakers.iter()
+            .filter(|b| b.state == CircuitState::Open)
+            .map(|b| b.service.as_str())
+            .collect();
+        let leadership_unstable = self.leader_election.leadership_unstable();
 
         // Core services: Cache and WebSocket MUST be healthy
         // External APIs can be degraded without failing health check
-        let core_healthy = cache_system_healthy && websocket_service_healthy;
+        let core_healthy = cache_system.healthy && websocket_service.healthy;
 
-        let status = if core_healthy && external_apis_healthy {
+        // An open circuit means that provider is being skipped - serve, but report degraded
+        let status = if core_healthy && external_apis.healthy && open_circuits.is_empty() && !leadership_unstable {
             "healthy"
         } else if core_healthy {
             "degraded" // Core services OK, but external APIs down
@@ -224,34 +1165,304 @@ impl ServiceIslands {
             "unhealthy"
         };
 
-        if core_healthy && external_apis_healthy {
+        if !open_circuits.is_empty() {
+            println!("⚠️ Circuit breakers open for: {}", open_circuits.join(", "));
+        }
+        if leadership_unstable {
+            println!("⚠️ Leadership is flapping between nodes");
+        }
+
+        if core_healthy && external_apis.healthy {
             println!("✅ All WebSocket Service Islands are healthy!");
-        } else if core_healthy {
-            println!("⚠️ Core services healthy, but External APIs are degraded");
-            println!("   Cache System Island: {}", if cache_system_healthy { "✅" } else { "❌" });
-            println!("   External APIs Island: {}", if external_apis_healthy { "✅" } else { "⚠️ degraded" });
-            println!("   WebSocket Service Island: {}", if websocket_service_healthy { "✅" } else { "❌" });
         } else {
-            println!("❌ Core WebSocket Service Islands are unhealthy!");
-            println!("   Cache System Island: {}", if cache_system_healthy { "✅" } else { "❌" });
-            println!("   External APIs Island: {}", if external_apis_healthy { "✅" } else { "❌" });
-            println!("   WebSocket Service Island: {}", if websocket_service_healthy { "✅" } else { "❌" });
+            if core_healthy {
+                println!("⚠️ Core services healthy, but External APIs are degraded");
+            } else {
+                println!("❌ Core WebSocket Service Islands are unhealthy!");
+            }
+            println!("   Cache System Island: {}", cache_system.symbol());
+            println!("   External APIs Island: {}", external_apis.symbol());
+            println!("   WebSocket Service Island: {}", websocket_service.symbol());
         }
 
-        let details = serde_json::json!({
-            "cache_system": cache_system_healthy,
-            "external_apis": external_apis_healthy,
-            "websocket_service": websocket_service_healthy,
-            "status": status,
-        });
+        // Healthy if core services are healthy (even if external APIs are down)
+        HealthReport {
+            healthy: core_healthy,
+            status,
+            cache_system,
+            external_apis,
+            websocket_service,
+            circuit_breakers,
+            leadership_unstable,
+        }
+    }
 
-        // Return healthy if core services are healthy (even if external APIs are down)
-        (core_healthy, details)
+    /// Run upstream streams (Binance WebSocket) only while this node is leader
+    pub fn sync_upstream_streams(&self, is_leader: bool) {
+        let binance_ws = self.external_apis.binance_ws();
+        if is_leader && self.config.binance_ws_enabled {
+            binance_ws.start();
+        } else {
+            binance_ws.stop();
+        }
+    }
+
+    /// Reserve a WebSocket connection slot, or None if at `max_ws_connections`
+    pub fn try_reserve_connection(&self) -> Option<ConnectionSlot> {
+        ConnectionSlot::try_reserve(&self.active_ws_connections, self.max_ws_connections)
+    }
+
+    /// Time since the islands were initialized
+    pub fn uptime(&self) -> Duration {
+        self.start_instant.elapsed()
     }
 
-    /// Get number of active WebSocket connections
+    /// Current number of active WebSocket connections
     pub fn active_connections(&self) -> usize {
         use std::sync::atomic::Ordering;
         self.active_ws_connections.load(Ordering::SeqCst)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use layer3_communication::websocket_service::serialization_pool::TOPIC_DASHBOARD;
+
+    async fn standalone_islands() -> Arc<ServiceIslands> {
+        Arc::new(standalone_islands_with(|_| None).await)
+    }
+
+    /// Islands without Redis (`CACHE_L2=none`), configured by `overrides` otherwise
+    async fn standalone_islands_with(overrides: impl Fn(&str) -> Option<&'static str>) -> ServiceIslands {
+        let config = Config::from_lookup(|name| match name {
+            "CACHE_L2" => Some("none".to_string()),
+            _ => overrides(name).map(str::to_string),
+        }).unwrap();
+        ServiceIslands::initialize(config).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_reserves_time_for_releasing_leadership() {
+        let islands = standalone_islands().await;
+        let deadline = islands.begin_shutdown();
+        assert_eq!(deadline - islands.shutdown_drain_deadline(deadline), LEADERSHIP_RELEASE_RESERVE);
+
+        // A short budget keeps half of it for the drain
+        let islands = standalone_islands_with(|name| (name == "SHUTDOWN_TIMEOUT_SECONDS").then_some("2")).await;
+        let deadline = islands.begin_shutdown();
+        assert_eq!(deadline - islands.shutdown_drain_deadline(deadline), Duration::from_secs(1));
+    }
+
+    fn sample_dashboard() -> serde_json::Value {
+        serde_json::json!({
+            "btc_price_usd": 65_000.0, "btc_change_24h": 1.5, "btc_market_cap_percentage": 57.2,
+            "eth_price_usd": 3_177.25, "eth_change_24h": 2.9,
+            "sol_price_usd": 141.15, "sol_change_24h": 3.2,
+            "xrp_price_usd": 2.26, "xrp_change_24h": 0.1,
+            "ada_price_usd": 0.51, "ada_change_24h": 0.8,
+            "link_price_usd": 14.2, "link_change_24h": 1.6,
+            "bnb_price_usd": 935.5, "bnb_change_24h": 4.1,
+            "market_cap_usd": 3.33e12, "volume_24h_usd": 2.09e11, "market_cap_change_percentage_24h_usd": 0.87,
+            "fetch_duration_ms": 114,
+            "partial_failure": false,
+            "last_updated": chrono::Utc::now().to_rfc3339(),
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_carries_exactly_the_allowlisted_fields() {
+        let mut data = sample_dashboard();
+        let mut allowlist: Vec<String> = data.as_object().unwrap().keys().cloned().collect();
+        allowlist.push("content_hash".to_string());
+        data["content_hash"] = "abc123".into();
+        data["fng_value"] = 10.into();
+        data["us_stock_indices"] = serde_json::json!({ "SPY": { "price": 512.3, "change": 1.0, "change_percent": 0.2, "status": "success" } });
+
+        let islands = standalone_islands_with(|_| None).await
+            .with_transform(transforms::field_allowlist(allowlist));
+        let mut receiver = islands.websocket_service.broadcast_service.subscribe_topic(TOPIC_DASHBOARD);
+
+        assert!(islands.send_dashboard_broadcast(data, DataOrigin::Api).await.unwrap());
+
+        let message = receiver.try_recv().expect("dashboard should be broadcast");
+        let sent: serde_json::Value = serde_json::from_str(&message.json).unwrap();
+        let fields = sent["payload"]["data"].as_object().unwrap();
+        assert_eq!(fields["btcPriceUsd"], 65_000.0);
+        assert_eq!(fields["contentHash"], "abc123");
+        for removed in ["fngValue", "usStockIndices", "fng_value", "origin", "ageMs", "sources"] {
+            assert!(!fields.contains_key(removed), "{} should not be broadcast", removed);
+        }
+        assert!(fields.keys().all(|field| !field.contains('_')), "{:?}", fields.keys());
+    }
+
+    #[test]
+    fn test_serialization_errors_are_recognized_through_context() {
+        let bad_keys: std::collections::HashMap<(u8, u8), u8> = [((1, 2), 3)].into();
+        let error = anyhow::Error::from(serde_json::to_string(&bad_keys).unwrap_err()).context("Encoding dashboard");
+        assert!(is_serialization_error(&error));
+        assert!(!is_serialization_error(&anyhow::anyhow!("Serialization worker dropped the job")));
+    }
+
+    #[tokio::test]
+    async fn test_follower_tick_broadcasts_cached_data() {
+        let islands = standalone_islands().await;
+        // Keep the standalone monitor from promoting this node mid-test
+        islands.stop_leader_monitor();
+        islands.is_leader.store(false, Ordering::Relaxed);
+
+        islands.cache_system.cache_manager()
+            .set_with_strategy("latest_market_data", sample_dashboard(), islands.cache_system.realtime_strategy())
+            .await
+            .unwrap();
+        let mut receiver = islands.websocket_service.broadcast_service.subscribe_topic(TOPIC_DASHBOARD);
+
+        islands.market_data_tick().await;
+
+        let message = receiver.try_recv().expect("cached dashboard should be broadcast");
+        match serde_json::from_str::<ServerMessage>(&message.json).unwrap() {
+            ServerMessage::DashboardUpdate(update) => {
+                assert_eq!(update.source, DataOrigin::Cache.as_str());
+                assert_eq!(update.data.btc_price_usd, 65_000.0);
+                assert_eq!(update.data.origin, DataOrigin::Cache.freshness());
+            }
+            other => panic!("Expected DashboardUpdate, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_follower_tick_skips_invalid_cached_data() {
+        let islands = standalone_islands().await;
+        islands.stop_leader_monitor();
+        islands.is_leader.store(false, Ordering::Relaxed);
+
+        let mut dashboard = sample_dashboard();
+        dashboard["btc_price_usd"] = serde_json::json!(-1.0);
+        islands.cache_system.cache_manager()
+            .set_with_strategy("latest_market_data", dashboard, islands.cache_system.realtime_strategy())
+            .await
+            .unwrap();
+        let mut receiver = islands.websocket_service.broadcast_service.subscribe_topic(TOPIC_DASHBOARD);
+
+        islands.market_data_tick().await;
+        assert!(receiver.try_recv().is_err(), "invalid cached dashboard must not be broadcast");
+        assert!(validate_published_dashboard(&sample_dashboard()).is_ok());
+        assert!(validate_published_dashboard(&serde_json::json!({ "btc_price_usd": 1.0 })).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_fetched_data_is_neither_cached_nor_published() {
+        let islands = standalone_islands().await;
+        let mut dashboard = sample_dashboard();
+        dashboard["btc_price_usd"] = serde_json::json!(-1.0);
+
+        assert!(islands.publish_market_data(dashboard).await.is_err());
+        assert!(islands.cache_system.cache_manager().get("latest_market_data").await.unwrap().is_none());
+
+        let published = islands.publish_market_data(sample_dashboard()).await.unwrap();
+        assert_eq!(islands.cache_system.cache_manager().get("latest_market_data").await.unwrap(), Some(published));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis running
+    async fn test_broadcasting_pause_is_shared_between_instances() {
+        let redis_islands = || async {
+            let config = Config::from_lookup(|name| (name == "CACHE_KEY_PREFIX").then(|| "test_broadcasting_pause".to_string())).unwrap();
+            let islands = Arc::new(ServiceIslands::initialize(config).await.unwrap());
+            islands.stop_leader_monitor();
+            islands
+        };
+        let (paused_here, other) = (redis_islands().await, redis_islands().await);
+
+        paused_here.set_broadcasting_enabled(true).await.unwrap();
+        assert!(paused_here.set_broadcasting_enabled(false).await.unwrap());
+        assert!(other.broadcasting_enabled(), "applied at the other instance's next tick");
+        other.sync_shared_flags().await;
+        assert!(!other.broadcasting_enabled());
+
+        assert!(other.set_broadcasting_enabled(true).await.unwrap());
+        paused_here.sync_shared_flags().await;
+        assert!(paused_here.broadcasting_enabled());
+    }
+
+    #[tokio::test]
+    async fn test_cold_cache_threshold_counts_consecutive_misses() {
+        let islands = standalone_islands().await;
+        assert!(!islands.cold_cache_threshold_reached(), "disabled by default");
+
+        let islands = standalone_islands_with(|name| (name == "FOLLOWER_COLD_FETCH_TICKS").then_some("3")).await;
+        assert!(!islands.cold_cache_threshold_reached());
+        assert!(!islands.cold_cache_threshold_reached());
+        assert!(islands.cold_cache_threshold_reached());
+        // The count starts over after each seeding attempt
+        assert!(!islands.cold_cache_threshold_reached());
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_mode_suspends_fetching_and_greets_with_cached_data() {
+        let islands = standalone_islands().await;
+        islands.stop_leader_monitor();
+        islands.is_leader.store(true, Ordering::Relaxed);
+        assert!(islands.set_maintenance_mode(true).await.unwrap());
+        assert!(!islands.set_maintenance_mode(true).await.unwrap());
+
+        islands.cache_system.cache_manager()
+            .set_with_strategy("latest_market_data", sample_dashboard(), islands.cache_system.realtime_strategy())
+            .await
+            .unwrap();
+        let mut receiver = islands.websocket_service.broadcast_service.subscribe_topic(TOPIC_DASHBOARD);
+
+        // The leader would fetch from the APIs; in maintenance nothing is fetched or broadcast
+        islands.market_data_tick().await;
+        assert!(receiver.try_recv().is_err());
+
+        match islands.maintenance_greeting().await.as_slice() {
+            [ServerMessage::Notice(notice), ServerMessage::DashboardUpdate(update)] => {
+                assert_eq!(notice.message, crate::config::DEFAULT_MAINTENANCE_MESSAGE);
+                assert_eq!(update.data.btc_price_usd, 65_000.0);
+                assert_eq!(update.data.origin, DataOrigin::Cache.freshness());
+            }
+            other => panic!("Unexpected maintenance greeting: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_market_statistics_come_from_the_cache_or_the_last_broadcast() {
+        let islands = standalone_islands().await;
+        assert!(islands.market_statistics().await.is_none());
+
+        assert!(islands.send_dashboard_broadcast(sample_dashboard(), DataOrigin::Api).await.unwrap());
+        let stats = islands.market_statistics().await.expect("last broadcast dashboard");
+        assert_eq!(stats.total_market_cap_usd, 3.33e12);
+        assert_eq!(stats.market_cap_weighted_change_24h, Some(1.5));
+
+        let mut cached = sample_dashboard();
+        cached["market_cap_usd"] = 3.5e12.into();
+        islands.cache_system.cache_manager()
+            .set_with_strategy("latest_market_data", cached, islands.cache_system.realtime_strategy())
+            .await
+            .unwrap();
+        assert_eq!(islands.market_statistics().await.unwrap().total_market_cap_usd, 3.5e12);
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_greeting_falls_back_to_the_stream_once_the_cache_expired() {
+        let islands = standalone_islands().await;
+        let mut published = sample_dashboard();
+        published["btc_price_usd"] = 64_000.0.into();
+        let fields = MarketDataStreamEntry::from_dashboard(&published).to_fields().unwrap();
+        islands.cache_system.cache_manager()
+            .publish_to_stream(MARKET_DATA_STREAM, fields, None)
+            .await
+            .unwrap();
+
+        match islands.maintenance_greeting().await.as_slice() {
+            [ServerMessage::Notice(_), ServerMessage::DashboardUpdate(update)] => {
+                assert_eq!(update.data.btc_price_usd, 64_000.0);
+                assert_eq!(update.source, DataOrigin::Cache.as_str());
+            }
+            other => panic!("Unexpected maintenance greeting: {:?}", other),
+        }
+    }
+}